@@ -1,10 +1,757 @@
-use crate::export::ExportFormat;
-use clap::{Args, ValueEnum};
+use crate::export::{ExportFormat, ExportOptions};
+use crate::utils::date_utils::parse_cli_date;
+use crate::utils::size_utils::parse_size;
+use crate::utils::validation::parse_csv_delimiter;
+use clap::builder::{PossibleValue, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::{Args, Subcommand, ValueEnum};
 use image_manager_lib::SimilarityThreshold;
+use std::ffi::OsStr;
 
-#[derive(Args)]
+#[derive(Args, Clone)]
+#[command(
+    args_conflicts_with_subcommands = true,
+    mut_arg("export_format", |arg| arg.default_value("csv"))
+)]
 pub struct OrganizeArgs {
-    #[arg(help = "Directory to scan for images (default: current directory)")]
+    #[command(subcommand)]
+    pub action: Option<OrganizeAction>,
+
+    #[arg(
+        default_value = ".",
+        help = "Directory to scan for images (default: current directory)"
+    )]
+    pub directory: std::path::PathBuf,
+
+    #[arg(
+        short = 'r',
+        long,
+        help = "Scan directories recursively (default: false)"
+    )]
+    pub recursive: bool,
+
+    #[arg(
+        long,
+        help = "Also scan directories reached through symlinks (requires --recursive)"
+    )]
+    pub follow_symlinks: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Only include files up to N folders below the directory; 0 keeps the top level only (requires --recursive)"
+    )]
+    pub max_depth: Option<usize>,
+
+    #[arg(
+        long,
+        value_parser = FormatFilterParser,
+        help = "Filter by specific image format"
+    )]
+    pub format: Option<ImageFormatFilter>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Only process the first N files (sorted by path)"
+    )]
+    pub limit: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "GLOB",
+        help = "Skip files whose path relative to the directory matches GLOB (repeatable)"
+    )]
+    pub exclude: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "GLOB",
+        help = "Only process files whose path relative to the directory matches GLOB (repeatable; --exclude wins)"
+    )]
+    pub include: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help = "Ignore files smaller than SIZE (e.g. 100KB, 2MB)"
+    )]
+    pub min_size: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help = "Ignore files larger than SIZE (e.g. 50MB, 1GB)"
+    )]
+    pub max_size: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "PIXELS",
+        help = "Ignore images narrower than PIXELS"
+    )]
+    pub min_width: Option<u32>,
+
+    #[arg(long, value_name = "PIXELS", help = "Ignore images lower than PIXELS")]
+    pub min_height: Option<u32>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "any",
+        help = "Only include landscape, portrait or square images"
+    )]
+    pub orientation: Orientation,
+
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        value_parser = parse_cli_date,
+        help = "Only process files dated on or after this day"
+    )]
+    pub after: Option<chrono::NaiveDate>,
+
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        value_parser = parse_cli_date,
+        help = "Only process files dated on or before this day"
+    )]
+    pub before: Option<chrono::NaiveDate>,
+
+    #[command(flatten)]
+    pub export: ExportArgs,
+
+    #[arg(
+        long,
+        help = "Write exported file paths relative to the scanned directory"
+    )]
+    pub relative_paths: bool,
+
+    #[arg(
+        long,
+        help = "Target directory for organized files (required with --copy)"
+    )]
+    pub target_path: Option<std::path::PathBuf>,
+
+    #[arg(long, help = "Copy files to target directory (default: preview only)")]
+    pub copy: bool,
+
+    #[arg(
+        long = "move",
+        conflicts_with = "copy",
+        help = "Move files to target directory instead of copying them"
+    )]
+    pub move_files: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "copy",
+        help = "How files are placed in the target directory (symlink/hardlink imply --copy)"
+    )]
+    pub link_mode: LinkMode,
+
+    #[arg(
+        long,
+        help = "Folder layout under the target directory (tokens: {year}, {month}, {day}, {ext}, {camera})"
+    )]
+    pub template: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["template", "granularity", "group_by"],
+        help = "Put all files directly in the target directory without date folders"
+    )]
+    pub flatten: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "date",
+        help = "Top-level grouping of organized files (camera adds a folder per EXIF camera model; extension replaces date folders with one folder per file type)"
+    )]
+    pub group_by: GroupBy,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "day",
+        conflicts_with = "template",
+        help = "Date folder depth when no --template is given"
+    )]
+    pub granularity: Granularity,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "asc",
+        help = "Order of the date folders in the preview and the transfer: asc (oldest first) or desc (newest first)"
+    )]
+    pub sort: SortOrder,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "list",
+        help = "Layout of the preview: list (one section per target folder) or tree (one level per folder of the template)"
+    )]
+    pub view: PreviewView,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "rename",
+        help = "What to do when a file already exists in the target directory"
+    )]
+    pub on_conflict: ConflictStrategy,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "counter",
+        help = "How renamed files are suffixed on a name conflict: counter (photo_1.jpg), parenthetical (photo (1).jpg), timestamp (photo_1760620000.jpg) or hash (photo_3fa2b1c0.jpg, skipping files already filed under that name)"
+    )]
+    pub rename_pattern: NamingPolicy,
+
+    #[arg(
+        long,
+        default_value_t = true,
+        action = clap::ArgAction::Set,
+        value_name = "BOOL",
+        help = "Keep the original modification time on copied and moved files"
+    )]
+    pub preserve_timestamps: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Where to read each image's date from (falls back to modification time)"
+    )]
+    pub date_source: Option<DateSource>,
+
+    #[arg(
+        long,
+        help = "Hash source and destination after each copy or move and report mismatches"
+    )]
+    pub verify: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Maximum number of files transferred in parallel (default: one per CPU core)"
+    )]
+    pub copy_threads: Option<usize>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "continue",
+        help = "Stop at the first failed transfer (abort) or report failures at the end (continue)"
+    )]
+    pub on_error: ErrorPolicy,
+
+    #[arg(
+        long,
+        help = "Copy even if the target volume looks too small for the files"
+    )]
+    pub force: bool,
+
+    #[arg(
+        long,
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help = "Abort transfers if free space on the target drops below SIZE (e.g. 500MB, 1GB)"
+    )]
+    pub min_free_space: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        default_value = "Unknown",
+        help = "Folder for files without a usable date when copying or moving"
+    )]
+    pub unknown_dir: String,
+
+    #[arg(
+        long,
+        value_name = "PLAN",
+        help = "Copy files according to a saved organize JSON export instead of scanning"
+    )]
+    pub from_plan: Option<std::path::PathBuf>,
+}
+
+#[derive(Subcommand, Clone)]
+pub enum OrganizeAction {
+    /// Revert the last copy, move or link run recorded in a target directory
+    Undo(UndoArgs),
+}
+
+#[derive(Args, Clone)]
+pub struct UndoArgs {
+    #[arg(help = "Target directory of the organize run to revert")]
+    pub target: std::path::PathBuf,
+}
+
+impl Default for OrganizeArgs {
+    fn default() -> Self {
+        Self {
+            action: None,
+            directory: std::path::PathBuf::from("."),
+            recursive: false,
+            follow_symlinks: false,
+            max_depth: None,
+            format: None,
+            limit: None,
+            exclude: Vec::new(),
+            include: Vec::new(),
+            min_size: None,
+            max_size: None,
+            min_width: None,
+            min_height: None,
+            orientation: Orientation::Any,
+            after: None,
+            before: None,
+            export: ExportArgs {
+                format: ExportFormat::Csv,
+                ..Default::default()
+            },
+            relative_paths: false,
+            target_path: None,
+            copy: false,
+            move_files: false,
+            link_mode: LinkMode::Copy,
+            template: None,
+            flatten: false,
+            group_by: GroupBy::Date,
+            granularity: Granularity::Day,
+            sort: SortOrder::Asc,
+            view: PreviewView::List,
+            on_conflict: ConflictStrategy::Rename,
+            rename_pattern: NamingPolicy::Counter,
+            preserve_timestamps: true,
+            date_source: None,
+            verify: false,
+            copy_threads: None,
+            on_error: ErrorPolicy::Continue,
+            force: false,
+            min_free_space: None,
+            unknown_dir: "Unknown".to_string(),
+            from_plan: None,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct DuplicatesArgs {
+    #[arg(help = "Directory to scan for duplicate images (default: current directory)")]
+    pub directory: std::path::PathBuf,
+
+    #[arg(
+        help = "Second directory to compare against; only duplicates found in both directories are reported"
+    )]
+    pub compare_directory: Option<std::path::PathBuf>,
+
+    #[arg(
+        short = 'r',
+        long,
+        help = "Scan directories recursively (default: false)"
+    )]
+    pub recursive: bool,
+
+    #[arg(
+        long,
+        help = "Also scan directories reached through symlinks (requires --recursive and --mode exact)"
+    )]
+    pub follow_symlinks: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Only include files up to N folders below the directory; 0 keeps the top level only (requires --recursive)"
+    )]
+    pub max_depth: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Similarity threshold for duplicate detection (0.0-1.0, e.g., 0.85)"
+    )]
+    pub threshold: Option<f32>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Preset similarity threshold level (overrides --threshold, default: medium)"
+    )]
+    pub sensitivity: Option<ThresholdLevel>,
+
+    #[arg(
+        long,
+        help = "Fail instead of warning when options conflict (e.g. --threshold with --sensitivity)"
+    )]
+    pub strict: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Only report duplicates among the first N files (sorted by path); only those files are compared in exact mode or when comparing two directories, otherwise every file is scanned and the output limited"
+    )]
+    pub limit: Option<usize>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 2,
+        help = "Only report duplicate groups with at least N files"
+    )]
+    pub min_group_size: usize,
+
+    #[arg(
+        long,
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help = "Ignore files smaller than SIZE (e.g. 100KB, 2MB)"
+    )]
+    pub min_size: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "SIZE",
+        value_parser = parse_size,
+        help = "Ignore files larger than SIZE (e.g. 50MB, 1GB)"
+    )]
+    pub max_size: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "PIXELS",
+        help = "Ignore images narrower than PIXELS"
+    )]
+    pub min_width: Option<u32>,
+
+    #[arg(long, value_name = "PIXELS", help = "Ignore images lower than PIXELS")]
+    pub min_height: Option<u32>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "any",
+        help = "Only include landscape, portrait or square images"
+    )]
+    pub orientation: Orientation,
+
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        value_parser = parse_cli_date,
+        help = "Only process files dated on or after this day"
+    )]
+    pub after: Option<chrono::NaiveDate>,
+
+    #[arg(
+        long,
+        value_name = "YYYY-MM-DD",
+        value_parser = parse_cli_date,
+        help = "Only process files dated on or before this day"
+    )]
+    pub before: Option<chrono::NaiveDate>,
+
+    #[arg(
+        long,
+        value_name = "GLOB",
+        help = "Skip files whose path relative to the directory matches GLOB (repeatable)"
+    )]
+    pub exclude: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "GLOB",
+        help = "Only process files whose path relative to the directory matches GLOB (repeatable; --exclude wins)"
+    )]
+    pub include: Vec<String>,
+
+    #[command(flatten)]
+    pub export: ExportArgs,
+
+    #[arg(
+        long,
+        requires = "export",
+        conflicts_with = "manifest",
+        help = "Write one CSV per duplicate group (group_1.csv, ...) plus an index.csv into a directory named after --export (dups.csv -> dups/)"
+    )]
+    pub split_groups: bool,
+
+    #[arg(
+        long,
+        help = "Write exported file paths relative to the scanned directory"
+    )]
+    pub relative_paths: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "size_filtered",
+        help = "Duplicate detection mode; exact only groups byte-identical files. Filters skip files before detection in exact mode or when comparing two directories, and are applied to the groups found otherwise (default: size_filtered)"
+    )]
+    pub mode: DuplicateScanMode,
+
+    #[arg(
+        long,
+        help = "Skip SHA-256 content hashes in exported duplicate records (faster export)"
+    )]
+    pub no_hash: bool,
+
+    #[arg(
+        long,
+        help = "Delete the redundant files in each duplicate group, keeping one copy"
+    )]
+    pub delete: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "first",
+        help = "Which file of each duplicate group to keep"
+    )]
+    pub keep: KeepStrategy,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "size",
+        help = "Order of duplicate groups in the preview: size (most reclaimable space first) or count (most files first)"
+    )]
+    pub sort_groups: GroupSort,
+
+    #[arg(long, help = "Also apply --sort-groups to the export")]
+    pub sort_export: bool,
+
+    #[arg(
+        long,
+        help = "Choose which file of each duplicate group to keep, then delete the rest"
+    )]
+    pub interactive: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["delete", "interactive"],
+        help = "Replace the redundant files in each duplicate group with hardlinks to the kept copy"
+    )]
+    pub hardlink: bool,
+
+    #[arg(
+        short = 'y',
+        long,
+        help = "Skip the confirmation prompt for --delete and --hardlink (with --interactive, keep the --keep choice for every group)"
+    )]
+    pub yes: bool,
+}
+
+impl Default for DuplicatesArgs {
+    fn default() -> Self {
+        Self {
+            directory: std::path::PathBuf::from("."),
+            compare_directory: None,
+            recursive: false,
+            follow_symlinks: false,
+            max_depth: None,
+            threshold: None,
+            sensitivity: None,
+            limit: None,
+            min_group_size: 2,
+            strict: false,
+            min_size: None,
+            max_size: None,
+            min_width: None,
+            min_height: None,
+            orientation: Orientation::Any,
+            after: None,
+            before: None,
+            exclude: Vec::new(),
+            include: Vec::new(),
+            export: ExportArgs::default(),
+            split_groups: false,
+            relative_paths: false,
+            mode: DuplicateScanMode::SizeFiltered,
+            no_hash: false,
+            delete: false,
+            keep: KeepStrategy::First,
+            sort_groups: GroupSort::Size,
+            sort_export: false,
+            interactive: false,
+            hardlink: false,
+            yes: false,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct StatsArgs {
+    #[arg(
+        default_value = ".",
+        help = "Directory to profile (default: current directory)"
+    )]
+    pub directory: std::path::PathBuf,
+
+    #[arg(
+        short = 'r',
+        long,
+        help = "Scan directories recursively (default: false)"
+    )]
+    pub recursive: bool,
+
+    #[command(flatten)]
+    pub export: ExportArgs,
+}
+
+impl Default for StatsArgs {
+    fn default() -> Self {
+        Self {
+            directory: std::path::PathBuf::from("."),
+            recursive: false,
+            export: ExportArgs::default(),
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct RenameArgs {
+    #[arg(
+        default_value = ".",
+        help = "Directory with images to rename (default: current directory)"
+    )]
+    pub directory: std::path::PathBuf,
+
+    #[arg(
+        short = 'r',
+        long,
+        help = "Scan directories recursively (default: false)"
+    )]
+    pub recursive: bool,
+
+    #[arg(
+        long,
+        default_value = crate::utils::path_template::DEFAULT_RENAME_TEMPLATE,
+        help = "File name template using {date}, {seq}, {ext} and {orig}"
+    )]
+    pub template: String,
+
+    #[arg(long, help = "Show the new names without renaming any files")]
+    pub dry_run: bool,
+
+    #[command(flatten)]
+    pub export: ExportArgs,
+
+    #[arg(
+        long,
+        help = "Write exported file paths relative to the scanned directory"
+    )]
+    pub relative_paths: bool,
+}
+
+impl Default for RenameArgs {
+    fn default() -> Self {
+        Self {
+            directory: std::path::PathBuf::from("."),
+            recursive: false,
+            template: crate::utils::path_template::DEFAULT_RENAME_TEMPLATE.to_string(),
+            dry_run: false,
+            export: ExportArgs::default(),
+            relative_paths: false,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct ConvertArgs {
+    #[arg(
+        default_value = ".",
+        help = "Directory with images to convert (default: current directory)"
+    )]
+    pub directory: std::path::PathBuf,
+
+    #[arg(
+        short = 'r',
+        long,
+        help = "Scan directories recursively (default: false)"
+    )]
+    pub recursive: bool,
+
+    #[arg(long, value_enum, help = "Format to convert images to")]
+    pub to: ConvertFormat,
+
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u8).range(1..=100),
+        help = "Quality for lossy formats (1-100, JPEG only, default: 85)"
+    )]
+    pub quality: Option<u8>,
+
+    #[arg(long, help = "Directory the converted images are written to")]
+    pub target_path: std::path::PathBuf,
+
+    #[arg(
+        long,
+        help = "Delete each original after it was converted successfully"
+    )]
+    pub delete_originals: bool,
+}
+
+#[derive(Args)]
+pub struct ExifArgs {
+    #[arg(
+        default_value = ".",
+        help = "Directory with images to inspect (default: current directory)"
+    )]
+    pub directory: std::path::PathBuf,
+
+    #[arg(
+        short = 'r',
+        long,
+        help = "Scan directories recursively (default: false)"
+    )]
+    pub recursive: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "date,make,model,gps",
+        help = "Comma-separated EXIF tags to show"
+    )]
+    pub tags: Vec<ExifTag>,
+
+    #[command(flatten)]
+    pub export: ExportArgs,
+
+    #[arg(
+        long,
+        help = "Write exported file paths relative to the scanned directory"
+    )]
+    pub relative_paths: bool,
+}
+
+impl Default for ExifArgs {
+    fn default() -> Self {
+        Self {
+            directory: std::path::PathBuf::from("."),
+            recursive: false,
+            tags: vec![ExifTag::Date, ExifTag::Make, ExifTag::Model, ExifTag::Gps],
+            export: ExportArgs::default(),
+            relative_paths: false,
+        }
+    }
+}
+
+#[derive(Args)]
+#[command(mut_arg("export_format", |arg| arg.default_value("csv")))]
+pub struct DedupeArgs {
+    #[arg(
+        default_value = ".",
+        help = "Directory to deduplicate and organize (default: current directory)"
+    )]
     pub directory: std::path::PathBuf,
 
     #[arg(
@@ -14,47 +761,163 @@ pub struct OrganizeArgs {
     )]
     pub recursive: bool,
 
-    #[arg(long, value_enum, help = "Filter by specific image format")]
-    pub format: Option<ImageFormatFilter>,
+    #[arg(long, help = "Directory the surviving images are organized into")]
+    pub target_path: std::path::PathBuf,
 
-    #[arg(long, help = "Export results to file")]
-    pub export: Option<std::path::PathBuf>,
+    #[arg(
+        long,
+        help = "Similarity threshold for duplicate detection (0.0-1.0, e.g., 0.85)"
+    )]
+    pub threshold: Option<f32>,
 
     #[arg(
         long,
         value_enum,
-        default_value = "csv",
-        help = "Export format (csv or json)"
+        help = "Preset similarity threshold level (overrides --threshold, default: medium)"
     )]
-    pub export_format: ExportFormat,
+    pub sensitivity: Option<ThresholdLevel>,
 
     #[arg(
         long,
-        help = "Target directory for organized files (required with --copy)"
+        value_enum,
+        default_value = "size_filtered",
+        help = "Duplicate detection mode; exact only groups byte-identical files (default: size_filtered)"
     )]
-    pub target_path: Option<std::path::PathBuf>,
+    pub mode: DuplicateScanMode,
 
-    #[arg(long, help = "Copy files to target directory (default: preview only)")]
-    pub copy: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value = "first",
+        help = "Which file of each duplicate group to keep"
+    )]
+    pub keep: KeepStrategy,
+
+    #[arg(
+        long,
+        help = "Folder layout under the target directory (tokens: {year}, {month}, {day}, {ext}, {camera})"
+    )]
+    pub template: Option<String>,
+
+    #[arg(
+        long,
+        help = "Show what would be copied and deleted without changing any files"
+    )]
+    pub dry_run: bool,
+
+    #[arg(
+        short = 'y',
+        long,
+        help = "Delete the duplicates without asking for confirmation"
+    )]
+    pub yes: bool,
+
+    #[command(flatten)]
+    pub export: ExportArgs,
 }
 
-impl Default for OrganizeArgs {
-    fn default() -> Self {
-        Self {
-            directory: std::path::PathBuf::from("."),
-            recursive: false,
-            format: None,
-            export: None,
-            export_format: ExportFormat::Csv,
-            target_path: None,
-            copy: false,
+impl DedupeArgs {
+    pub fn get_similarity_threshold(&self) -> Result<SimilarityThreshold, String> {
+        similarity_threshold(self.sensitivity, self.threshold)
+    }
+
+    /// Organize settings used to copy the surviving images.
+    pub fn organize_args(&self) -> OrganizeArgs {
+        OrganizeArgs {
+            directory: self.directory.clone(),
+            target_path: Some(self.target_path.clone()),
+            copy: true,
+            template: self.template.clone(),
+            ..Default::default()
         }
     }
 }
 
 #[derive(Args)]
-pub struct DuplicatesArgs {
-    #[arg(help = "Directory to scan for duplicate images (default: current directory)")]
+pub struct WatchArgs {
+    #[arg(
+        default_value = ".",
+        help = "Directory to watch for new images (default: current directory)"
+    )]
+    pub directory: std::path::PathBuf,
+
+    #[arg(short = 'r', long, help = "Also watch subdirectories (default: false)")]
+    pub recursive: bool,
+
+    #[arg(long, help = "Directory new images are copied into")]
+    pub target_path: std::path::PathBuf,
+
+    #[arg(
+        long,
+        help = "Folder layout under the target directory (tokens: {year}, {month}, {day}, {ext}, {camera})"
+    )]
+    pub template: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "rename",
+        help = "What to do when a file already exists in the target directory"
+    )]
+    pub on_conflict: ConflictStrategy,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "counter",
+        help = "How renamed files are suffixed on a name conflict: counter (photo_1.jpg), parenthetical (photo (1).jpg), timestamp (photo_1760620000.jpg) or hash (photo_3fa2b1c0.jpg, skipping files already filed under that name)"
+    )]
+    pub rename_pattern: NamingPolicy,
+
+    #[arg(
+        long,
+        value_name = "MS",
+        default_value = "1000",
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Wait this long after the last change before filing a file"
+    )]
+    pub debounce_ms: u64,
+}
+
+impl WatchArgs {
+    /// Organize settings used to copy each batch of new files.
+    pub fn organize_args(&self) -> OrganizeArgs {
+        OrganizeArgs {
+            directory: self.directory.clone(),
+            target_path: Some(self.target_path.clone()),
+            copy: true,
+            template: self.template.clone(),
+            on_conflict: self.on_conflict,
+            rename_pattern: self.rename_pattern,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    #[arg(value_name = "PLAN", help = "Organize JSON export to check against")]
+    pub plan: std::path::PathBuf,
+
+    #[arg(
+        long,
+        help = "Target directory the files were organized into (default: the one recorded in the plan)"
+    )]
+    pub target_path: Option<std::path::PathBuf>,
+
+    #[arg(
+        long,
+        help = "Only compare sizes, not SHA-256 hashes against the originals"
+    )]
+    pub no_hash: bool,
+}
+
+#[derive(Args)]
+pub struct ResizeArgs {
+    #[arg(
+        default_value = ".",
+        help = "Directory with images to resize (default: current directory)"
+    )]
     pub directory: std::path::PathBuf,
 
     #[arg(
@@ -66,75 +929,277 @@ pub struct DuplicatesArgs {
 
     #[arg(
         long,
-        help = "Similarity threshold for duplicate detection (0.0-1.0, e.g., 0.85)"
+        value_name = "PIXELS",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Maximum width of the resized images"
     )]
-    pub threshold: Option<f32>,
+    pub max_width: Option<u32>,
 
     #[arg(
         long,
-        value_enum,
-        help = "Preset similarity threshold level (overrides --threshold, default: medium)"
+        value_name = "PIXELS",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Maximum height of the resized images"
     )]
-    pub sensitivity: Option<ThresholdLevel>,
+    pub max_height: Option<u32>,
 
-    #[arg(long, help = "Export results to file")]
-    pub export: Option<std::path::PathBuf>,
+    #[arg(long, help = "Directory the resized images are written to")]
+    pub target_path: std::path::PathBuf,
+}
 
+/// Export flags shared by every command that can write its results to a file.
+#[derive(Args, Clone)]
+pub struct ExportArgs {
     #[arg(
-        long,
+        long = "export",
+        id = "export",
+        value_name = "EXPORT",
+        help = "Export results to file (use - for stdout)"
+    )]
+    pub path: Option<std::path::PathBuf>,
+
+    #[arg(
+        long = "export-format",
+        id = "export_format",
+        value_name = "EXPORT_FORMAT",
         value_enum,
         default_value = "json",
-        help = "Export format (csv or json)"
+        help = "Export format (csv, json, ndjson, yaml, xml, html or markdown)"
     )]
-    pub export_format: ExportFormat,
+    pub format: ExportFormat,
 
     #[arg(
         long,
-        value_enum,
-        default_value = "size_filtered",
-        help = "Duplicate detection mode (default: size_filtered)"
+        default_value = ",",
+        value_parser = parse_csv_delimiter,
+        help = "Field delimiter for CSV exports (single ASCII character or \"tab\")"
     )]
-    pub mode: DuplicateScanMode,
+    pub csv_delimiter: u8,
+
+    #[arg(
+        long,
+        help = "Start CSV exports with a UTF-8 byte order mark so Excel reads non-ASCII file names correctly"
+    )]
+    pub csv_bom: bool,
+
+    #[arg(long, help = "Gzip-compress the export file (appends .gz to the path)")]
+    pub compress: bool,
+
+    #[arg(
+        long,
+        help = "Append to an existing CSV export instead of overwriting it (header is only written to new or empty files; CSV only)"
+    )]
+    pub export_append: bool,
+
+    #[arg(
+        long,
+        help = "Allow an export file extension that doesn't match --export-format"
+    )]
+    pub force_extension: bool,
+
+    #[arg(
+        long,
+        help = "Write JSON exports without pretty-printing (NDJSON is always compact)"
+    )]
+    pub json_compact: bool,
+
+    #[arg(
+        long,
+        requires = "export",
+        help = "Create the export file's parent directory if it does not exist"
+    )]
+    pub export_mkdir: bool,
+
+    #[arg(
+        long,
+        help = "Write a .sha256 checksum manifest next to the export file"
+    )]
+    pub manifest: bool,
 }
 
-impl Default for DuplicatesArgs {
+impl Default for ExportArgs {
     fn default() -> Self {
         Self {
-            directory: std::path::PathBuf::from("."),
-            recursive: false,
-            threshold: None,
-            sensitivity: None,
-            export: None,
-            export_format: ExportFormat::Json,
-            mode: DuplicateScanMode::SizeFiltered,
+            path: None,
+            format: ExportFormat::Json,
+            csv_delimiter: b',',
+            csv_bom: false,
+            compress: false,
+            export_append: false,
+            force_extension: false,
+            json_compact: false,
+            export_mkdir: false,
+            manifest: false,
+        }
+    }
+}
+
+impl ExportArgs {
+    pub fn options(&self) -> ExportOptions {
+        ExportOptions {
+            csv_delimiter: self.csv_delimiter,
+            csv_bom: self.csv_bom,
+            compress: self.compress,
+            append: self.export_append,
+            force_extension: self.force_extension,
+            json_compact: self.json_compact,
+        }
+    }
+}
+
+impl OrganizeArgs {
+    /// The folder template used for the target layout, derived from
+    /// `--granularity` unless `--template` or `--flatten` was given, and
+    /// prefixed with the camera folder for `--group-by camera`.
+    pub fn folder_template(&self) -> String {
+        if self.flatten {
+            return crate::utils::path_template::FLAT_TEMPLATE.to_string();
+        }
+
+        let date_template = self
+            .template
+            .as_deref()
+            .unwrap_or_else(|| self.granularity.template());
+
+        match self.group_by {
+            GroupBy::Date => date_template.to_string(),
+            GroupBy::Camera => format!("{{camera}}/{}", date_template),
+            GroupBy::Extension => "{ext}".to_string(),
         }
     }
 }
 
 impl DuplicatesArgs {
     pub fn get_similarity_threshold(&self) -> Result<SimilarityThreshold, String> {
-        if let Some(preset_level) = self.sensitivity {
-            Ok(preset_level.into())
-        } else if let Some(custom_threshold) = self.threshold {
-            SimilarityThreshold::new(custom_threshold)
-                .map_err(|e| format!("Invalid threshold: {}", e))
-        } else {
-            Ok(SimilarityThreshold::medium())
-        }
+        similarity_threshold(self.sensitivity, self.threshold)
+    }
+}
+
+fn similarity_threshold(
+    sensitivity: Option<ThresholdLevel>,
+    threshold: Option<f32>,
+) -> Result<SimilarityThreshold, String> {
+    if let Some(preset_level) = sensitivity {
+        Ok(preset_level.into())
+    } else if let Some(custom_threshold) = threshold {
+        SimilarityThreshold::new(custom_threshold).map_err(|e| format!("Invalid threshold: {}", e))
+    } else {
+        Ok(SimilarityThreshold::medium())
     }
 }
 
 #[derive(ValueEnum, Clone)]
 pub enum ImageFormatFilter {
+    #[value(alias = "jpg")]
     Jpeg,
     Png,
     Gif,
+    #[value(alias = "tif")]
     Tiff,
+    #[value(name = "webp", alias = "web-p")]
     WebP,
     Bmp,
     Ico,
 }
 
+/// Value parser for `--format` that keeps the formats visible in `--help`
+/// and shell completions while suggesting the closest one for a typo.
+#[derive(Clone)]
+struct FormatFilterParser;
+
+impl TypedValueParser for FormatFilterParser {
+    type Value = ImageFormatFilter;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        let value = value.to_string_lossy();
+        parse_format_filter(&value).map_err(|message| {
+            let arg = arg.map_or_else(|| "...".to_string(), ToString::to_string);
+            clap::Error::raw(
+                ErrorKind::InvalidValue,
+                format!("invalid value '{}' for '{}': {}\n", value, arg, message),
+            )
+            .with_cmd(cmd)
+        })
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            ImageFormatFilter::value_variants()
+                .iter()
+                .filter_map(ValueEnum::to_possible_value),
+        ))
+    }
+}
+
+/// Parses `--format` case-insensitively. An unknown value is answered with
+/// the closest known format and the list of valid ones.
+fn parse_format_filter(value: &str) -> Result<ImageFormatFilter, String> {
+    let value = value.trim();
+    if let Ok(format) = ImageFormatFilter::from_str(value, true) {
+        return Ok(format);
+    }
+
+    let possible_values: Vec<PossibleValue> = ImageFormatFilter::value_variants()
+        .iter()
+        .filter_map(ValueEnum::to_possible_value)
+        .collect();
+
+    let typed = value.to_lowercase();
+    let suggestion = possible_values
+        .iter()
+        .flat_map(PossibleValue::get_name_and_aliases)
+        .map(|name| (edit_distance(&typed, name), name))
+        .min_by_key(|(distance, _)| *distance)
+        .filter(|(distance, _)| *distance <= 2)
+        .map(|(_, name)| format!(" (did you mean '{}'?)", name))
+        .unwrap_or_default();
+
+    let valid: Vec<&str> = possible_values
+        .iter()
+        .map(PossibleValue::get_name)
+        .collect();
+    Err(format!(
+        "unknown image format{}; valid values: {}",
+        suggestion,
+        valid.join(", ")
+    ))
+}
+
+/// Edit distance between two strings where swapping two neighbouring
+/// characters counts as one edit, so `jpge` is as close to `jpeg` as `jpg`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
 impl From<ImageFormatFilter> for image_manager_lib::config::ImageFormat {
     fn from(filter: ImageFormatFilter) -> Self {
         match filter {
@@ -149,6 +1214,256 @@ impl From<ImageFormatFilter> for image_manager_lib::config::ImageFormat {
     }
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkMode {
+    #[value(name = "copy")]
+    Copy,
+    #[value(name = "symlink")]
+    Symlink,
+    #[value(name = "hardlink")]
+    Hardlink,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Granularity {
+    #[value(name = "year")]
+    Year,
+    #[value(name = "month")]
+    Month,
+    #[value(name = "day")]
+    Day,
+}
+
+impl Granularity {
+    pub fn template(self) -> &'static str {
+        match self {
+            Granularity::Year => "{year}",
+            Granularity::Month => "{year}/{month}",
+            Granularity::Day => crate::utils::path_template::DEFAULT_TEMPLATE,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupBy {
+    #[value(name = "date")]
+    Date,
+    #[value(name = "camera")]
+    Camera,
+    #[value(name = "extension")]
+    Extension,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    #[value(name = "rename")]
+    Rename,
+    #[value(name = "skip")]
+    Skip,
+    #[value(name = "overwrite")]
+    Overwrite,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NamingPolicy {
+    /// `photo_1.jpg`, `photo_2.jpg`, ...
+    #[value(name = "counter")]
+    Counter,
+    /// `photo (1).jpg`, `photo (2).jpg`, ...
+    #[value(name = "parenthetical")]
+    Parenthetical,
+    /// `photo_1760620000.jpg` (Unix seconds at the time of the transfer)
+    #[value(name = "timestamp")]
+    Timestamp,
+    /// `photo_3fa2b1c0.jpg` (start of the SHA-256 of the file's content)
+    #[value(name = "hash")]
+    Hash,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeepStrategy {
+    #[value(name = "first")]
+    First,
+    #[value(name = "largest")]
+    Largest,
+    #[value(name = "newest")]
+    Newest,
+    #[value(name = "shortest-path")]
+    ShortestPath,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExifTag {
+    #[value(name = "date")]
+    Date,
+    #[value(name = "make")]
+    Make,
+    #[value(name = "model")]
+    Model,
+    #[value(name = "lens")]
+    Lens,
+    #[value(name = "iso")]
+    Iso,
+    #[value(name = "exposure")]
+    Exposure,
+    #[value(name = "aperture")]
+    Aperture,
+    #[value(name = "focal-length")]
+    FocalLength,
+    #[value(name = "orientation")]
+    Orientation,
+    #[value(name = "gps")]
+    Gps,
+}
+
+impl ExifTag {
+    /// Column name used in the table and exports.
+    pub fn name(self) -> &'static str {
+        match self {
+            ExifTag::Date => "date",
+            ExifTag::Make => "make",
+            ExifTag::Model => "model",
+            ExifTag::Lens => "lens",
+            ExifTag::Iso => "iso",
+            ExifTag::Exposure => "exposure",
+            ExifTag::Aperture => "aperture",
+            ExifTag::FocalLength => "focal_length",
+            ExifTag::Orientation => "orientation",
+            ExifTag::Gps => "gps",
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupSort {
+    #[value(name = "size")]
+    Size,
+    #[value(name = "count")]
+    Count,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvertFormat {
+    #[value(name = "jpeg")]
+    Jpeg,
+    #[value(name = "png")]
+    Png,
+    #[value(name = "webp")]
+    Webp,
+}
+
+impl ConvertFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ConvertFormat::Jpeg => "jpg",
+            ConvertFormat::Png => "png",
+            ConvertFormat::Webp => "webp",
+        }
+    }
+
+    pub fn image_format(&self) -> image::ImageFormat {
+        match self {
+            ConvertFormat::Jpeg => image::ImageFormat::Jpeg,
+            ConvertFormat::Png => image::ImageFormat::Png,
+            ConvertFormat::Webp => image::ImageFormat::WebP,
+        }
+    }
+
+    /// Whether `path` already has this format, judged by its extension.
+    pub fn matches(&self, path: &std::path::Path) -> bool {
+        image::ImageFormat::from_path(path).is_ok_and(|format| format == self.image_format())
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    #[value(name = "abort")]
+    Abort,
+    #[value(name = "continue")]
+    Continue,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateSource {
+    #[value(name = "exif")]
+    Exif,
+    #[value(name = "mtime")]
+    Mtime,
+    #[value(name = "filename")]
+    Filename,
+}
+
+impl DateSource {
+    pub fn name(self) -> &'static str {
+        match self {
+            DateSource::Exif => "EXIF",
+            DateSource::Mtime => "modification time",
+            DateSource::Filename => "filename",
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    #[value(name = "landscape")]
+    Landscape,
+    #[value(name = "portrait")]
+    Portrait,
+    #[value(name = "square")]
+    Square,
+    #[value(name = "any")]
+    Any,
+}
+
+impl Orientation {
+    pub fn name(self) -> &'static str {
+        match self {
+            Orientation::Landscape => "landscape",
+            Orientation::Portrait => "portrait",
+            Orientation::Square => "square",
+            Orientation::Any => "any",
+        }
+    }
+
+    pub fn of(width: u32, height: u32) -> Self {
+        match width.cmp(&height) {
+            std::cmp::Ordering::Greater => Orientation::Landscape,
+            std::cmp::Ordering::Less => Orientation::Portrait,
+            std::cmp::Ordering::Equal => Orientation::Square,
+        }
+    }
+
+    pub fn matches(self, width: u32, height: u32) -> bool {
+        self == Orientation::Any || self == Orientation::of(width, height)
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    #[value(name = "asc")]
+    Asc,
+    #[value(name = "desc")]
+    Desc,
+}
+
+impl SortOrder {
+    /// Turns an ascending comparison into one for this direction.
+    pub fn apply(self, ordering: std::cmp::Ordering) -> std::cmp::Ordering {
+        match self {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PreviewView {
+    #[value(name = "list")]
+    List,
+    #[value(name = "tree")]
+    Tree,
+}
+
 #[derive(ValueEnum, Clone, Copy, Debug)]
 pub enum ThresholdLevel {
     #[value(name = "low")]
@@ -169,12 +1484,14 @@ impl From<ThresholdLevel> for SimilarityThreshold {
     }
 }
 
-#[derive(ValueEnum, Clone, Copy, Debug)]
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DuplicateScanMode {
     #[value(name = "size_filtered")]
     SizeFiltered,
     #[value(name = "complete")]
     Complete,
+    #[value(name = "exact")]
+    Exact,
 }
 
 impl From<DuplicateScanMode> for image_manager_lib::config::DuplicateMode {
@@ -183,7 +1500,98 @@ impl From<DuplicateScanMode> for image_manager_lib::config::DuplicateMode {
             DuplicateScanMode::SizeFiltered => {
                 image_manager_lib::config::DuplicateMode::SizeFiltered
             }
-            DuplicateScanMode::Complete => image_manager_lib::config::DuplicateMode::Complete,
+            // Exact mode never reaches the library scan.
+            DuplicateScanMode::Complete | DuplicateScanMode::Exact => {
+                image_manager_lib::config::DuplicateMode::Complete
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::FromArgMatches;
+
+    #[test]
+    fn test_edit_distance_counts_transpositions_once() {
+        assert_eq!(edit_distance("jpeg", "jpeg"), 0);
+        assert_eq!(edit_distance("jpge", "jpeg"), 1);
+        assert_eq!(edit_distance("pgn", "png"), 1);
+        assert_eq!(edit_distance("", "gif"), 3);
+    }
+
+    #[test]
+    fn test_parse_format_filter_accepts_case_and_aliases() {
+        assert!(matches!(
+            parse_format_filter("JPEG"),
+            Ok(ImageFormatFilter::Jpeg)
+        ));
+        assert!(matches!(
+            parse_format_filter(" Png "),
+            Ok(ImageFormatFilter::Png)
+        ));
+        assert!(matches!(
+            parse_format_filter("web-p"),
+            Ok(ImageFormatFilter::WebP)
+        ));
+        assert!(matches!(
+            parse_format_filter("TIF"),
+            Ok(ImageFormatFilter::Tiff)
+        ));
+    }
+
+    #[test]
+    fn test_parse_format_filter_suggests_close_match() {
+        let error = parse_format_filter("jpge").err().unwrap();
+        assert!(error.contains("did you mean 'jpeg'?"));
+        assert!(error.contains("valid values: jpeg, png, gif, tiff, webp, bmp, ico"));
+
+        let error = parse_format_filter("raw-sensor-data").err().unwrap();
+        assert!(!error.contains("did you mean"));
+        assert!(error.contains("valid values:"));
+    }
+
+    #[test]
+    fn test_format_parser_reports_possible_values() {
+        let command = OrganizeArgs::augment_args(clap::Command::new("organize"));
+        let format = command
+            .get_arguments()
+            .find(|arg| arg.get_id() == "format")
+            .unwrap();
+        let names: Vec<String> = format
+            .get_possible_values()
+            .iter()
+            .map(|value| value.get_name().to_string())
+            .collect();
+        assert_eq!(names, ["jpeg", "png", "gif", "tiff", "webp", "bmp", "ico"]);
+
+        let error = command
+            .try_get_matches_from(["organize", "--format", "jpge"])
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidValue);
+        assert!(error.to_string().contains("did you mean 'jpeg'?"));
+    }
+
+    #[test]
+    fn test_export_format_default_per_command() {
+        let parse = |command: clap::Command, argv: &[&str]| {
+            ExportArgs::from_arg_matches(&command.get_matches_from(argv))
+                .unwrap()
+                .format
+        };
+
+        let organize = OrganizeArgs::augment_args(clap::Command::new("organize"));
+        assert_eq!(parse(organize, &["organize"]), ExportFormat::Csv);
+
+        let stats = StatsArgs::augment_args(clap::Command::new("stats"));
+        assert_eq!(parse(stats, &["stats"]), ExportFormat::Json);
+
+        let stats = StatsArgs::augment_args(clap::Command::new("stats"));
+        assert_eq!(
+            parse(stats, &["stats", "--export-format", "yaml"]),
+            ExportFormat::Yaml
+        );
+    }
+}
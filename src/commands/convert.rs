@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use console::style;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image_manager_lib::{ImageManager, ImageManagerConfig};
+
+use super::{ConvertArgs, ConvertFormat, NamingPolicy};
+use crate::output::display_errors;
+use crate::output::emoji::{emoji, INFO, PICTURE};
+use crate::progress::{
+    create_copy_progress, create_scanner_progress, finish_with_message, start_progress_monitoring,
+};
+use crate::status;
+use crate::utils::{file_ops, image_ops, operation_log, validation};
+
+pub fn handle_convert(args: ConvertArgs, max_display: usize) -> Result<()> {
+    validation::validate_convert_args(&args)?;
+
+    let progress = create_scanner_progress();
+    progress.set_message("Initializing image manager...");
+
+    let config = ImageManagerConfig {
+        recursive_scan: args.recursive,
+        parallel_processing: true,
+        ..Default::default()
+    };
+
+    let manager = ImageManager::with_config(config);
+    finish_with_message(&progress, "Image manager initialized");
+
+    let progress_handle = image_manager_lib::ProgressHandle::new();
+    let progress_for_monitoring = progress_handle.clone();
+
+    let monitor_handle = start_progress_monitoring(progress_for_monitoring, "Scanning images...");
+
+    let (organized_images, errors) = manager
+        .organize_by_date_with_progress(&args.directory, &progress_handle)
+        .with_context(|| {
+            format!(
+                "Failed to scan images in directory: {}",
+                args.directory.display()
+            )
+        })?;
+
+    let _ = monitor_handle.join();
+
+    let mut files: Vec<PathBuf> = organized_images.into_values().flatten().collect();
+    files.sort();
+
+    fs::create_dir_all(&args.target_path).with_context(|| {
+        format!(
+            "Failed to create target directory: {}",
+            args.target_path.display()
+        )
+    })?;
+
+    let operation_start = std::time::Instant::now();
+    let outcome = convert_files(&files, &args);
+
+    status!(
+        "\n{} Conversion completed in {:.1}s",
+        style("✓").green(),
+        operation_start.elapsed().as_secs_f64()
+    );
+
+    display_convert_results(&outcome, &args);
+
+    let error_strings: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    display_errors(&error_strings, "Processing Errors", max_display);
+    display_errors(&outcome.errors, "Conversion Errors", max_display);
+    display_errors(&outcome.delete_errors, "Delete Errors", max_display);
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct ConvertOutcome {
+    converted: usize,
+    skipped: usize,
+    deleted: usize,
+    errors: Vec<String>,
+    delete_errors: Vec<String>,
+}
+
+/// Converts each file into the target directory, keeping its path relative
+/// to the source directory. Failures are collected instead of stopping the
+/// run; originals are only deleted after a successful conversion.
+fn convert_files(files: &[PathBuf], args: &ConvertArgs) -> ConvertOutcome {
+    let mut outcome = ConvertOutcome::default();
+    let progress = create_copy_progress(files.iter().map(|file| file_ops::file_size(file)).sum());
+    progress.set_message(format!("Converting to {}...", args.to.extension()));
+
+    for file in files {
+        progress.inc(file_ops::file_size(file));
+
+        if args.to.matches(file) {
+            outcome.skipped += 1;
+            continue;
+        }
+
+        let quality = args.quality.unwrap_or(image_ops::DEFAULT_JPEG_QUALITY);
+        match convert_file(file, &args.directory, &args.target_path, args.to, quality) {
+            Ok(destination) => {
+                operation_log::record("converted", file, Some(&destination.to_string_lossy()));
+                outcome.converted += 1;
+                if args.delete_originals {
+                    match fs::remove_file(file) {
+                        Ok(()) => {
+                            operation_log::record("deleted", file, None);
+                            outcome.deleted += 1;
+                        }
+                        Err(e) => {
+                            operation_log::record("error", file, Some(&e.to_string()));
+                            outcome
+                                .delete_errors
+                                .push(format!("{}: {}", file.display(), e));
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                let message = format!("{}: {}", e, e.root_cause());
+                operation_log::record("error", file, Some(&message));
+                outcome.errors.push(message);
+            }
+        }
+    }
+
+    progress.finish_and_clear();
+    outcome
+}
+
+fn convert_file(
+    file: &Path,
+    source_dir: &Path,
+    target_dir: &Path,
+    format: ConvertFormat,
+    quality: u8,
+) -> Result<PathBuf> {
+    let relative_dir = file
+        .parent()
+        .and_then(|parent| parent.strip_prefix(source_dir).ok())
+        .unwrap_or(Path::new(""));
+    let destination_dir = target_dir.join(relative_dir);
+    fs::create_dir_all(&destination_dir)?;
+
+    let file_name =
+        Path::new(file.file_stem().unwrap_or_default()).with_extension(format.extension());
+    let destination = file_ops::get_unique_filename(
+        &destination_dir.join(file_name),
+        NamingPolicy::Counter,
+        Path::exists,
+    )?;
+
+    image_ops::convert_image(file, &destination, format.image_format(), quality)?;
+    Ok(destination)
+}
+
+fn display_convert_results(outcome: &ConvertOutcome, args: &ConvertArgs) {
+    status!(
+        "\n{} {}",
+        style(emoji(PICTURE)).cyan(),
+        style("Conversion Results").bold().cyan()
+    );
+    status!("{}", style("━".repeat(50)).dim());
+    status!("Target dir: {}", style(args.target_path.display()).cyan());
+    status!("Format: {}", style(args.to.extension()).cyan());
+    status!("Converted: {}", style(outcome.converted).yellow());
+
+    if outcome.skipped > 0 {
+        status!(
+            "Skipped: {} (already {})",
+            style(outcome.skipped).yellow(),
+            args.to.extension()
+        );
+    }
+
+    if args.delete_originals {
+        status!("Originals deleted: {}", style(outcome.deleted).yellow());
+    }
+
+    if args.to != ConvertFormat::Jpeg && args.quality.is_some() {
+        status!(
+            "{} --quality only applies to JPEG output",
+            style(emoji(INFO)).cyan()
+        );
+    }
+}
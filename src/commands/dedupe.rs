@@ -0,0 +1,226 @@
+use anyhow::{Context, Result};
+use console::style;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use image_manager_lib::{ImageManager, ImageManagerConfig, SimilarityThreshold};
+
+use super::{copy_to_target, delete_duplicates, redundant_files, DedupeArgs, DuplicateScanMode};
+use crate::export::data::TargetConfig;
+use crate::export::{export_and_report, ExportData};
+use crate::output::emoji::{emoji, DUPLICATE, FILES, INFO, TRASH, WARNING};
+use crate::output::{display_errors, is_json_output, print_json_result, print_organize_preview};
+use crate::progress::{create_scanner_progress, finish_with_message, start_progress_monitoring};
+use crate::status;
+use crate::utils::scan_filter::ScanFilter;
+use crate::utils::size_utils::format_bytes;
+use crate::utils::{duplicate_utils, validation};
+
+pub fn handle_dedupe(args: DedupeArgs, max_display: usize) -> Result<()> {
+    validation::validate_dedupe_args(&args)?;
+
+    let progress = create_scanner_progress();
+    progress.set_message("Initializing image manager...");
+
+    let similarity_threshold = if args.mode == DuplicateScanMode::Exact {
+        if args.threshold.is_some() || args.sensitivity.is_some() {
+            status!(
+                "{} --threshold and --sensitivity are ignored in exact mode",
+                style(emoji(WARNING)).yellow()
+            );
+        }
+        SimilarityThreshold::new(1.0)
+            .map_err(|e| anyhow::anyhow!("Invalid similarity threshold: {}", e))?
+    } else {
+        args.get_similarity_threshold()
+            .map_err(|e| anyhow::anyhow!("Invalid similarity threshold: {}", e))?
+    };
+
+    let mut config = ImageManagerConfig {
+        recursive_scan: args.recursive,
+        similarity_threshold,
+        parallel_processing: true,
+        ..Default::default()
+    };
+
+    config.duplicate_mode = args.mode.into();
+
+    let manager = ImageManager::with_config(config);
+    finish_with_message(&progress, "Image manager initialized");
+
+    let operation_start = std::time::Instant::now();
+    let (duplicate_groups, mut errors, hash_errors) = if args.mode == DuplicateScanMode::Exact {
+        let progress = create_scanner_progress();
+        progress.set_message("Hashing files for exact duplicates...");
+        let scan_roots = [args.directory.as_path()];
+        let scanned = duplicate_utils::scan_image_files(
+            &scan_roots,
+            args.recursive,
+            &ScanFilter::new(&scan_roots),
+        );
+        let (groups, mut hash_errors) = duplicate_utils::find_exact_duplicates(scanned.files);
+        hash_errors.extend(scanned.errors);
+        progress.finish_and_clear();
+        (groups, Vec::new(), hash_errors)
+    } else {
+        let progress_handle = image_manager_lib::ProgressHandle::new();
+        let progress_for_monitoring = progress_handle.clone();
+
+        let monitor_handle =
+            start_progress_monitoring(progress_for_monitoring, "Scanning for duplicate images...");
+
+        let (groups, errors) = manager
+            .find_duplicates_with_progress(&args.directory, &progress_handle)
+            .with_context(|| "Failed to find duplicates")?;
+
+        let _ = monitor_handle.join();
+        (groups, errors, Vec::new())
+    };
+
+    let progress_handle = image_manager_lib::ProgressHandle::new();
+    let progress_for_monitoring = progress_handle.clone();
+
+    let monitor_handle = start_progress_monitoring(progress_for_monitoring, "Organizing images...");
+
+    let (organized_images, organize_errors) = manager
+        .organize_by_date_with_progress(&args.directory, &progress_handle)
+        .with_context(|| {
+            format!(
+                "Failed to organize images in directory: {}",
+                args.directory.display()
+            )
+        })?;
+
+    let _ = monitor_handle.join();
+    errors.extend(organize_errors);
+
+    status!(
+        "\n{} Scan completed in {:.1}s",
+        style("✓").green(),
+        operation_start.elapsed().as_secs_f64()
+    );
+
+    let redundant = redundant_files(&duplicate_groups, args.keep);
+    let survivors = without_files(organized_images, &redundant);
+
+    display_removal_plan(&duplicate_groups, &redundant, &args, max_display);
+
+    let organize_args = args.organize_args();
+    print_organize_preview(
+        &survivors,
+        &errors,
+        Some(&args.target_path),
+        &organize_args.folder_template(),
+        &organize_args.unknown_dir,
+        organize_args.sort,
+        max_display,
+    );
+
+    if args.export.path.is_some() || is_json_output() {
+        let total_processed: usize = survivors.values().map(|v| v.len()).sum();
+        let target_config = TargetConfig {
+            base_path: Some(args.target_path.clone()),
+            template: Some(organize_args.folder_template()),
+            unknown_dir: Some(organize_args.unknown_dir.clone()),
+        };
+        let export_data_obj = ExportData::organize(
+            survivors.clone(),
+            target_config,
+            args.directory.clone(),
+            total_processed,
+        );
+        export_and_report(&export_data_obj, &args.export)?;
+
+        if is_json_output() {
+            print_json_result(&export_data_obj)?;
+        }
+    }
+
+    let error_strings: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    display_errors(&error_strings, "Processing Errors", max_display);
+    display_errors(&hash_errors, "Hash Errors", max_display);
+
+    if args.dry_run {
+        status!(
+            "\n{} Dry run: no files were copied or deleted",
+            style(emoji(INFO)).blue()
+        );
+        return Ok(());
+    }
+
+    // Survivors are copied before anything is deleted, so a failed copy never
+    // leaves a group without any file.
+    let copied = copy_to_target(&survivors, &organize_args, true, max_display)?;
+    status!(
+        "\n{} Copied {} images to {}",
+        style(emoji(FILES)).blue(),
+        style(copied.values().map(|v| v.len()).sum::<usize>()).green(),
+        style(args.target_path.display()).cyan()
+    );
+
+    delete_duplicates(redundant, args.yes, max_display)?;
+
+    Ok(())
+}
+
+/// Drops `files` from the date groups, removing groups left empty.
+fn without_files(
+    organized_images: HashMap<String, Vec<PathBuf>>,
+    files: &[&PathBuf],
+) -> HashMap<String, Vec<PathBuf>> {
+    let excluded: HashSet<&PathBuf> = files.iter().copied().collect();
+
+    organized_images
+        .into_iter()
+        .map(|(date, files)| {
+            let kept: Vec<PathBuf> = files
+                .into_iter()
+                .filter(|file| !excluded.contains(file))
+                .collect();
+            (date, kept)
+        })
+        .filter(|(_, files)| !files.is_empty())
+        .collect()
+}
+
+fn display_removal_plan(
+    duplicate_groups: &image_manager_lib::duplicates::DuplicateGroups,
+    redundant: &[&PathBuf],
+    args: &DedupeArgs,
+    max_display: usize,
+) {
+    status!(
+        "\n{} {}",
+        emoji(DUPLICATE),
+        style("Duplicate Removal Preview").bold().cyan()
+    );
+    status!("{}", style("━".repeat(50)).dim());
+
+    if redundant.is_empty() {
+        status!("No duplicates found; every image will be organized");
+        return;
+    }
+
+    let reclaimable: u64 = duplicate_groups
+        .iter()
+        .map(|group| duplicate_utils::reclaimable_bytes(group, args.keep))
+        .sum();
+
+    status!(
+        "Duplicate groups: {}",
+        style(duplicate_groups.len()).yellow()
+    );
+    status!("Files to delete: {}", style(redundant.len()).yellow());
+    status!(
+        "Space reclaimed: {}",
+        style(format_bytes(reclaimable)).green()
+    );
+
+    for file in redundant.iter().take(max_display) {
+        status!("  {} {}", style(emoji(TRASH)).red(), file.display());
+    }
+
+    if redundant.len() > max_display {
+        status!("  ... and {} more files", redundant.len() - max_display);
+    }
+}
@@ -1,25 +1,71 @@
 use anyhow::{Context, Result};
 use console::style;
-use image_manager_lib::{ImageManager, ImageManagerConfig};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use super::DuplicatesArgs;
-use crate::export::{data::DuplicateGroup, export_data, ExportData};
-use crate::output::print_duplicates_preview;
-use crate::progress::{config, create_scanner_progress, start_progress_monitoring};
-use crate::utils::validation;
-use crate::DUPLICATE;
+use image_manager_lib::{ImageManager, ImageManagerConfig, SimilarityThreshold};
 
-pub fn handle_duplicates(args: DuplicatesArgs) -> Result<()> {
+use super::{DuplicateScanMode, DuplicatesArgs, KeepStrategy};
+use crate::export::trait_impl::export_split_groups;
+use crate::export::{data::DuplicateGroup, export_and_report, report_export, ExportData};
+use crate::output::emoji::{emoji, DISK, DUPLICATE, INFO, LINK, TRASH, WARNING};
+use crate::output::{
+    display_errors, is_json_output, print_duplicates_preview, print_duplicates_summary,
+    print_json_result, print_limit_notice, print_paths_outside_source, print_skipped_files,
+};
+use crate::progress::{create_scanner_progress, finish_with_message, start_progress_monitoring};
+use crate::status;
+use crate::utils::scan_filter::{skip_errors, ScanFilter};
+use crate::utils::size_utils::format_bytes;
+use crate::utils::staged_tree::StagedTree;
+use crate::utils::symlinks::{self, LinkedDirectories};
+use crate::utils::{duplicate_utils, file_ops, operation_log, prompt, validation};
+
+pub fn handle_duplicates(args: DuplicatesArgs, max_display: usize) -> Result<()> {
     validation::validate_duplicates_args(&args)?;
 
+    let mut scan_roots = vec![args.directory.as_path()];
+    scan_roots.extend(args.compare_directory.as_deref());
+
+    // Linked directories are hashed as additional roots, which is why
+    // following symlinks requires exact mode.
+    let linked = if args.follow_symlinks {
+        symlinks::find_linked_directories(&scan_roots)
+    } else {
+        LinkedDirectories::default()
+    };
+    scan_roots.extend(linked.directories.iter().map(PathBuf::as_path));
+    let scan_filter = ScanFilter::new(&scan_roots)
+        .with_exclude(&args.exclude)?
+        .with_include(&args.include)?
+        .with_ignore_files(args.recursive)?
+        .with_max_depth(args.max_depth)
+        .with_size_range(args.min_size, args.max_size)
+        .with_min_dimensions(args.min_width, args.min_height)
+        .with_orientation(args.orientation)
+        .with_date_range(args.after, args.before, None);
+
     let progress = create_scanner_progress();
     progress.set_message("Initializing image manager...");
 
+    let similarity_threshold = if args.mode == DuplicateScanMode::Exact {
+        if args.threshold.is_some() || args.sensitivity.is_some() {
+            status!(
+                "{} --threshold and --sensitivity are ignored in exact mode",
+                style(emoji(WARNING)).yellow()
+            );
+        }
+        SimilarityThreshold::new(1.0)
+            .map_err(|e| anyhow::anyhow!("Invalid similarity threshold: {}", e))?
+    } else {
+        args.get_similarity_threshold()
+            .map_err(|e| anyhow::anyhow!("Invalid similarity threshold: {}", e))?
+    };
+
     let mut config = ImageManagerConfig {
         recursive_scan: args.recursive,
-        similarity_threshold: args
-            .get_similarity_threshold()
-            .map_err(|e| anyhow::anyhow!("Invalid similarity threshold: {}", e))?,
+        similarity_threshold,
         parallel_processing: true,
         ..Default::default()
     };
@@ -27,35 +73,395 @@ pub fn handle_duplicates(args: DuplicatesArgs) -> Result<()> {
     config.duplicate_mode = args.mode.into();
 
     let manager = ImageManager::with_config(config.clone());
-    progress.finish_with_message("Image manager initialized");
+    finish_with_message(&progress, "Image manager initialized");
 
-    let progress_handle = image_manager_lib::ProgressHandle::new();
-    let progress_for_monitoring = progress_handle.clone();
+    // Exact mode and comparisons collect the files themselves, so the filters
+    // and --limit apply before detection.
+    let operation_start = std::time::Instant::now();
+    let (duplicate_groups, errors, hash_errors, skipped, limited_out) = if args.mode
+        == DuplicateScanMode::Exact
+        || args.compare_directory.is_some()
+    {
+        let progress = create_scanner_progress();
+        progress.set_message("Collecting image files...");
+        let scanned = duplicate_utils::scan_image_files(&scan_roots, args.recursive, &scan_filter);
+        let mut files = scanned.files;
+        let limited_out = args.limit.map_or(0, |limit| {
+            let limited_out = files.len().saturating_sub(limit);
+            files.truncate(limit);
+            limited_out
+        });
 
-    let monitor_handle =
-        start_progress_monitoring(progress_for_monitoring, "Scanning for duplicate images...");
+        let (groups, errors, mut hash_errors) = if args.mode == DuplicateScanMode::Exact {
+            progress.set_message("Hashing files for exact duplicates...");
+            let (groups, hash_errors) = duplicate_utils::find_exact_duplicates(files);
+            progress.finish_and_clear();
+            (groups, Vec::new(), hash_errors)
+        } else {
+            progress.finish_and_clear();
+            find_similar_across(&config, &scan_roots, &files)?
+        };
+        hash_errors.extend(scanned.errors);
+        (groups, errors, hash_errors, scanned.skipped, limited_out)
+    } else {
+        let progress_handle = image_manager_lib::ProgressHandle::new();
+        let progress_for_monitoring = progress_handle.clone();
 
-    let operation_start = std::time::Instant::now();
-    let (duplicate_groups, errors) = manager
-        .find_duplicates_with_progress(&args.directory, &progress_handle)
-        .with_context(|| "Failed to find duplicates")?;
+        let monitor_handle =
+            start_progress_monitoring(progress_for_monitoring, "Scanning for duplicate images...");
 
-    let _ = monitor_handle.join();
+        let (groups, errors) = manager
+            .find_duplicates_with_progress(&args.directory, &progress_handle)
+            .with_context(|| "Failed to find duplicates")?;
+
+        let _ = monitor_handle.join();
+
+        // The library scans the directory itself, so the filters can only
+        // remove files from the groups it found.
+        let (groups, skipped) = scan_filter.filter_groups(groups);
+        let (groups, limited_out) = match args.limit {
+            Some(limit) => limit_duplicate_groups(groups, limit),
+            None => (groups, 0),
+        };
+        (groups, errors, Vec::new(), skipped, limited_out)
+    };
 
     let elapsed = operation_start.elapsed();
-    println!(
+    status!(
         "\n{} Duplicate detection completed in {:.1}s",
         style("✓").green(),
         elapsed.as_secs_f64()
     );
 
+    display_errors(&linked.loops, "Symlink Loops", max_display);
+
+    print_skipped_files(&skipped);
+    display_errors(&skip_errors(&skipped), "Unreadable Files", max_display);
+
+    if let Some(limit) = args.limit {
+        print_limit_notice(limit, limited_out);
+    }
+
+    let duplicate_groups: image_manager_lib::duplicates::DuplicateGroups = duplicate_groups
+        .into_iter()
+        .filter(|group| group.len() >= args.min_group_size)
+        .filter(|group| {
+            args.compare_directory.as_deref().is_none_or(|compare| {
+                duplicate_utils::spans_directories(group, &args.directory, compare)
+            })
+        })
+        .collect();
+
+    let duplicate_groups = if args.sort_export {
+        let order = duplicate_utils::group_order(&duplicate_groups, args.sort_groups, args.keep);
+        let mut groups: Vec<Option<Vec<PathBuf>>> =
+            duplicate_groups.into_iter().map(Some).collect();
+        order
+            .into_iter()
+            .filter_map(|index| groups[index].take())
+            .collect()
+    } else {
+        duplicate_groups
+    };
+
     display_duplicates_results(
         &duplicate_groups,
         &errors,
         &args,
         &config.similarity_threshold,
+        max_display,
     )?;
 
+    display_errors(&hash_errors, "Hash Errors", max_display);
+
+    if args.interactive {
+        let redundant = choose_redundant_files(&duplicate_groups, args.keep, args.yes)?;
+        delete_duplicates(redundant, true, max_display)?;
+    } else if args.delete {
+        delete_duplicates(
+            redundant_files(&duplicate_groups, args.keep),
+            args.yes,
+            max_display,
+        )?;
+    } else if args.hardlink {
+        hardlink_duplicates(&duplicate_groups, args.keep, args.yes, max_display)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct DeletionReport {
+    deleted: usize,
+    freed_bytes: u64,
+    errors: Vec<String>,
+}
+
+/// Every file except the survivor of each group.
+pub fn redundant_files(
+    duplicate_groups: &image_manager_lib::duplicates::DuplicateGroups,
+    keep: KeepStrategy,
+) -> Vec<&PathBuf> {
+    duplicate_groups
+        .iter()
+        .filter(|group| group.len() > 1)
+        .flat_map(|group| {
+            let survivor = duplicate_utils::select_survivor(group, keep);
+            non_survivors(group, survivor)
+        })
+        .collect()
+}
+
+fn non_survivors(group: &[PathBuf], survivor: usize) -> impl Iterator<Item = &PathBuf> {
+    group
+        .iter()
+        .enumerate()
+        .filter(move |&(index, _)| index != survivor)
+        .map(|(_, file)| file)
+}
+
+/// Asks which file to keep for each group, offering the `--keep` choice as the
+/// default. Groups the user skips are left untouched. With `auto_keep` the
+/// default is taken without prompting.
+fn choose_redundant_files(
+    duplicate_groups: &image_manager_lib::duplicates::DuplicateGroups,
+    keep: KeepStrategy,
+    auto_keep: bool,
+) -> Result<Vec<&PathBuf>> {
+    let groups: Vec<&Vec<PathBuf>> = duplicate_groups
+        .iter()
+        .filter(|group| group.len() > 1)
+        .collect();
+    let mut redundant = Vec::new();
+
+    for (group_index, group) in groups.iter().enumerate() {
+        let default = duplicate_utils::select_survivor(group, keep);
+        if auto_keep {
+            redundant.extend(non_survivors(group, default));
+            continue;
+        }
+
+        let mut items: Vec<String> = group
+            .iter()
+            .map(|file| {
+                let size = fs::metadata(file)
+                    .map(|metadata| format_bytes(metadata.len()))
+                    .unwrap_or_else(|_| "unknown size".to_string());
+                format!("Keep {} ({})", file.display(), size)
+            })
+            .collect();
+        items.push("Skip this group".to_string());
+
+        let question = format!("Group {} of {}", group_index + 1, groups.len());
+        match prompt::select(&question, &items, default)? {
+            Some(survivor) if survivor < group.len() => {
+                redundant.extend(non_survivors(group, survivor))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(redundant)
+}
+
+/// Deletes the given files after asking for confirmation (skipped with
+/// `--yes`).
+pub fn delete_duplicates(
+    redundant: Vec<&PathBuf>,
+    skip_confirmation: bool,
+    max_display: usize,
+) -> Result<()> {
+    if redundant.is_empty() {
+        status!(
+            "\n{} No duplicate files to delete",
+            style(emoji(INFO)).blue()
+        );
+        return Ok(());
+    }
+
+    let total_bytes: u64 = redundant
+        .iter()
+        .filter_map(|file| fs::metadata(file).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    if !skip_confirmation {
+        let question = format!(
+            "Delete {} duplicate files ({})?",
+            redundant.len(),
+            format_bytes(total_bytes)
+        );
+        if !prompt::confirm(&question)? {
+            status!(
+                "\n{} Deletion cancelled (use --yes to delete without prompting)",
+                style(emoji(INFO)).blue()
+            );
+            return Ok(());
+        }
+    }
+
+    let mut report = DeletionReport::default();
+    for file in redundant {
+        let size = fs::metadata(file)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        match fs::remove_file(file) {
+            Ok(()) => {
+                operation_log::record("deleted", file, None);
+                report.deleted += 1;
+                report.freed_bytes += size;
+            }
+            Err(e) => {
+                operation_log::record("error", file, Some(&e.to_string()));
+                report
+                    .errors
+                    .push(format!("Failed to delete {}: {}", file.display(), e));
+            }
+        }
+    }
+
+    status!(
+        "\n{} Deleted {} duplicate files, freed {}",
+        style(emoji(TRASH)).green(),
+        style(report.deleted).yellow(),
+        style(format_bytes(report.freed_bytes)).cyan()
+    );
+
+    display_errors(&report.errors, "Deletion Errors", max_display);
+
+    Ok(())
+}
+
+/// Runs the library's perceptual scan over `files` from several directories
+/// at once. The library scans a single directory, so the files are staged
+/// under a temporary directory and the groups mapped back to the originals.
+/// Returns the groups, the library's errors and the files that could not be
+/// staged.
+fn find_similar_across(
+    config: &ImageManagerConfig,
+    roots: &[&Path],
+    files: &[PathBuf],
+) -> Result<(
+    image_manager_lib::duplicates::DuplicateGroups,
+    Vec<image_manager_lib::ProcessingError>,
+    Vec<String>,
+)> {
+    let progress = create_scanner_progress();
+    progress.set_message("Staging files for comparison...");
+    let staged = StagedTree::new(roots, files)?;
+    progress.finish_and_clear();
+
+    let manager = ImageManager::with_config(ImageManagerConfig {
+        recursive_scan: true,
+        ..config.clone()
+    });
+    let progress_handle = image_manager_lib::ProgressHandle::new();
+    let monitor_handle =
+        start_progress_monitoring(progress_handle.clone(), "Scanning for duplicate images...");
+
+    let (groups, errors) = manager
+        .find_duplicates_with_progress(staged.path(), &progress_handle)
+        .with_context(|| "Failed to find duplicates")?;
+
+    let _ = monitor_handle.join();
+    Ok((staged.restore_paths(groups), errors, staged.errors))
+}
+
+/// Restricts the groups to the first `limit` grouped files by path, dropping
+/// groups that no longer contain a duplicate. Returns the limited groups and
+/// the number of files left out.
+fn limit_duplicate_groups(
+    duplicate_groups: image_manager_lib::duplicates::DuplicateGroups,
+    limit: usize,
+) -> (image_manager_lib::duplicates::DuplicateGroups, usize) {
+    let mut files: Vec<&PathBuf> = duplicate_groups.iter().flatten().collect();
+    files.sort();
+    files.dedup();
+
+    let skipped = files.len().saturating_sub(limit);
+    let kept: HashSet<PathBuf> = files.into_iter().take(limit).cloned().collect();
+
+    let limited = duplicate_groups
+        .into_iter()
+        .map(|group| {
+            group
+                .into_iter()
+                .filter(|file| kept.contains(file))
+                .collect::<Vec<_>>()
+        })
+        .filter(|group| group.len() > 1)
+        .collect();
+
+    (limited, skipped)
+}
+
+/// Replaces every file but the survivor of each group with a hardlink to the
+/// survivor after asking for confirmation (skipped with `--yes`). Links never
+/// span groups.
+fn hardlink_duplicates(
+    duplicate_groups: &image_manager_lib::duplicates::DuplicateGroups,
+    keep: KeepStrategy,
+    skip_confirmation: bool,
+    max_display: usize,
+) -> Result<()> {
+    let pairs: Vec<(&PathBuf, &PathBuf)> = duplicate_groups
+        .iter()
+        .filter(|group| group.len() > 1)
+        .flat_map(|group| {
+            let survivor = duplicate_utils::select_survivor(group, keep);
+            non_survivors(group, survivor).map(move |file| (&group[survivor], file))
+        })
+        .collect();
+
+    if pairs.is_empty() {
+        status!(
+            "\n{} No duplicate files to hardlink",
+            style(emoji(INFO)).blue()
+        );
+        return Ok(());
+    }
+
+    if !skip_confirmation {
+        let question = format!("Replace {} duplicate files with hardlinks?", pairs.len());
+        if !prompt::confirm(&question)? {
+            status!(
+                "\n{} Hardlinking cancelled (use --yes to link without prompting)",
+                style(emoji(INFO)).blue()
+            );
+            return Ok(());
+        }
+    }
+
+    let mut links_created = 0;
+    let mut reclaimed_bytes = 0;
+    let mut errors = Vec::new();
+    for (survivor, file) in pairs {
+        let size = fs::metadata(file)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        match file_ops::replace_with_hard_link(survivor, file) {
+            Ok(true) => {
+                operation_log::record("hardlinked", file, Some(&survivor.to_string_lossy()));
+                links_created += 1;
+                reclaimed_bytes += size;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                operation_log::record("error", file, Some(&e.to_string()));
+                errors.push(format!("Failed to hardlink {}: {}", file.display(), e));
+            }
+        }
+    }
+
+    status!(
+        "\n{} Created {} hardlinks, reclaimed {}",
+        style(emoji(LINK)).green(),
+        style(links_created).yellow(),
+        style(format_bytes(reclaimed_bytes)).cyan()
+    );
+
+    display_errors(&errors, "Hardlink Errors", max_display);
+
     Ok(())
 }
 
@@ -64,17 +470,36 @@ fn display_duplicates_results(
     errors: &[image_manager_lib::ProcessingError],
     args: &DuplicatesArgs,
     similarity_threshold: &image_manager_lib::SimilarityThreshold,
+    max_display: usize,
 ) -> Result<()> {
-    println!(
+    status!(
         "\n{} {}",
-        DUPLICATE,
+        emoji(DUPLICATE),
         style("Duplicate Detection Preview").bold().cyan()
     );
-    println!("{}", style("━".repeat(50)).dim());
+    status!("{}", style("━".repeat(50)).dim());
 
-    print_duplicates_preview(duplicate_groups, errors, similarity_threshold);
+    print_duplicates_preview(
+        duplicate_groups,
+        errors,
+        similarity_threshold,
+        args,
+        max_display,
+    );
+
+    let potential_savings: u64 = duplicate_groups
+        .iter()
+        .map(|group| duplicate_utils::reclaimable_bytes(group, args.keep))
+        .sum();
+    if !duplicate_groups.is_empty() {
+        status!(
+            "\n{} Potential savings: {}",
+            style(emoji(DISK)).cyan(),
+            style(format_bytes(potential_savings)).green().bold()
+        );
+    }
 
-    if let Some(export_path) = &args.export {
+    if args.export.path.is_some() || is_json_output() {
         let total_processed: usize = duplicate_groups.iter().map(|group| group.len()).sum();
 
         let export_duplicate_groups: Vec<DuplicateGroup> = duplicate_groups
@@ -87,46 +512,38 @@ fn display_duplicates_results(
             })
             .collect();
 
-        let export_data_obj = ExportData::duplicates(
+        let mut export_data_obj = ExportData::duplicates(
             export_duplicate_groups,
             similarity_threshold.value(),
             args.directory.clone(),
             total_processed,
+            !args.no_hash,
+            args.keep,
         );
 
-        export_data(&export_data_obj, export_path, args.export_format)?;
+        if args.relative_paths {
+            print_paths_outside_source(&export_data_obj.make_paths_relative(), max_display);
+        }
 
-        println!(
-            "\n{} {}",
-            style("📄").green(),
-            style("Export completed").green()
-        );
-        println!("   Format: {}", style(args.export_format.name()).cyan());
-        println!("   Location: {}", style(export_path.display()).cyan());
+        match &args.export.path {
+            Some(export_path) if args.split_groups => {
+                let directory =
+                    export_split_groups(&export_data_obj, export_path, &args.export.options())?;
+                report_export(&directory, &args.export)?;
+            }
+            _ => export_and_report(&export_data_obj, &args.export)?,
+        }
+
+        if is_json_output() {
+            print_json_result(&export_data_obj)?;
+        }
     }
 
     let error_strings: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
 
-    display_errors(&error_strings, "Processing Errors");
-
-    Ok(())
-}
-
-fn display_errors(errors: &[String], error_type: &str) {
-    if !errors.is_empty() {
-        println!("\n{} {}", style("⚠️").yellow(), style(error_type).yellow());
-        println!("{}", style("━".repeat(30)).dim());
+    display_errors(&error_strings, "Processing Errors", max_display);
 
-        for error in errors.iter().take(config::MAX_DISPLAY_ITEMS) {
-            println!("  {}", style(format!("• {}", error)).red());
-        }
+    print_duplicates_summary(duplicate_groups, errors.len(), potential_savings);
 
-        if errors.len() > config::MAX_DISPLAY_ITEMS {
-            println!(
-                "  {} ... and {} more errors",
-                style("•").red(),
-                errors.len() - config::MAX_DISPLAY_ITEMS
-            );
-        }
-    }
+    Ok(())
 }
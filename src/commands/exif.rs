@@ -0,0 +1,77 @@
+use anyhow::{Context, Result};
+use console::style;
+use std::path::PathBuf;
+
+use image_manager_lib::{ImageManager, ImageManagerConfig};
+
+use super::ExifArgs;
+use crate::export::{export_and_report, ExportData};
+use crate::output::{
+    display_errors, is_json_output, print_exif_table, print_json_result, print_paths_outside_source,
+};
+use crate::progress::{create_scanner_progress, finish_with_message, start_progress_monitoring};
+use crate::status;
+use crate::utils::validation;
+
+pub fn handle_exif(args: ExifArgs, max_display: usize) -> Result<()> {
+    validation::validate_exif_args(&args)?;
+
+    let progress = create_scanner_progress();
+    progress.set_message("Initializing image manager...");
+
+    let config = ImageManagerConfig {
+        recursive_scan: args.recursive,
+        parallel_processing: true,
+        ..Default::default()
+    };
+
+    let manager = ImageManager::with_config(config);
+    finish_with_message(&progress, "Image manager initialized");
+
+    let progress_handle = image_manager_lib::ProgressHandle::new();
+    let progress_for_monitoring = progress_handle.clone();
+
+    let monitor_handle = start_progress_monitoring(progress_for_monitoring, "Scanning images...");
+
+    let operation_start = std::time::Instant::now();
+    let (organized_images, errors) = manager
+        .organize_by_date_with_progress(&args.directory, &progress_handle)
+        .with_context(|| {
+            format!(
+                "Failed to scan images in directory: {}",
+                args.directory.display()
+            )
+        })?;
+
+    let _ = monitor_handle.join();
+
+    let mut files: Vec<PathBuf> = organized_images.into_values().flatten().collect();
+    files.sort();
+
+    let mut exif_data = ExportData::exif(&files, &args.tags, args.directory.clone());
+
+    status!(
+        "\n{} Read metadata in {:.1}s",
+        style("✓").green(),
+        operation_start.elapsed().as_secs_f64()
+    );
+
+    print_exif_table(&exif_data, max_display);
+
+    if args.export.path.is_some() || is_json_output() {
+        if args.relative_paths {
+            print_paths_outside_source(&exif_data.make_paths_relative(), max_display);
+        }
+
+        export_and_report(&exif_data, &args.export)?;
+
+        if is_json_output() {
+            print_json_result(&exif_data)?;
+        }
+    }
+
+    let error_strings: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    display_errors(&error_strings, "Processing Errors", max_display);
+
+    Ok(())
+}
@@ -1,7 +1,23 @@
 pub mod args;
+pub mod convert;
+pub mod dedupe;
 pub mod duplicates;
+pub mod exif;
 pub mod organize;
+pub mod rename;
+pub mod resize;
+pub mod stats;
+pub mod verify;
+pub mod watch;
 
 pub use args::*;
+pub use convert::*;
+pub use dedupe::*;
 pub use duplicates::*;
+pub use exif::*;
 pub use organize::*;
+pub use rename::*;
+pub use resize::*;
+pub use stats::*;
+pub use verify::*;
+pub use watch::*;
@@ -1,21 +1,185 @@
 use anyhow::{Context, Result};
 use console::style;
-use std::collections::HashMap;
+use filetime::FileTime;
+use indicatif::ProgressBar;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::OnceLock;
 
 use image_manager_lib::{ImageManager, ImageManagerConfig};
 
-use super::OrganizeArgs;
-use crate::export::{data::TargetConfig, export_data, ExportData};
-use crate::output::print_organize_preview;
-use crate::progress::{config, create_scanner_progress, start_progress_monitoring};
-use crate::utils::{date_utils, file_ops, validation};
-use crate::FILES;
+use super::{
+    ConflictStrategy, DateSource, ErrorPolicy, GroupBy, LinkMode, NamingPolicy, OrganizeAction,
+    OrganizeArgs, PreviewView, SortOrder,
+};
+use crate::export::data::{ExportDataType, TargetConfig};
+use crate::export::{export_and_report, ExportData};
+use crate::output::emoji::{emoji, EMPTY, FILES};
+use crate::output::{
+    display_errors, is_json_output, print_json_result, print_limit_notice, print_organize_preview,
+    print_organize_summary, print_organize_tree, print_paths_outside_source, print_skipped_files,
+};
+use crate::progress::{create_scanner_progress, finish_with_message, start_progress_monitoring};
+use crate::status;
+use crate::utils::scan_filter::{skip_errors, ScanFilter};
+use crate::utils::size_utils::format_bytes;
+use crate::utils::symlinks::{self, LinkedDirectories};
+use crate::utils::undo_log::{self, UndoLog};
+use crate::utils::{date_utils, file_ops, hash_utils, operation_log, path_template, validation};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileOperation {
+    Copy,
+    Move,
+    Symlink,
+    Hardlink,
+}
+
+impl FileOperation {
+    fn from_args(args: &OrganizeArgs) -> Option<Self> {
+        if args.move_files {
+            return Some(FileOperation::Move);
+        }
+
+        match args.link_mode {
+            LinkMode::Symlink => Some(FileOperation::Symlink),
+            LinkMode::Hardlink => Some(FileOperation::Hardlink),
+            LinkMode::Copy if args.copy => Some(FileOperation::Copy),
+            LinkMode::Copy => None,
+        }
+    }
+
+    fn verb(self) -> &'static str {
+        match self {
+            FileOperation::Copy => "copy",
+            FileOperation::Move => "move",
+            FileOperation::Symlink => "symlink",
+            FileOperation::Hardlink => "hardlink",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            FileOperation::Copy => "Copy",
+            FileOperation::Move => "Move",
+            FileOperation::Symlink | FileOperation::Hardlink => "Link",
+        }
+    }
+
+    fn progressive(self) -> &'static str {
+        match self {
+            FileOperation::Copy => "Copying",
+            FileOperation::Move => "Moving",
+            FileOperation::Symlink | FileOperation::Hardlink => "Linking",
+        }
+    }
+
+    fn past(self) -> &'static str {
+        match self {
+            FileOperation::Copy => "Copied",
+            FileOperation::Move => "Moved",
+            FileOperation::Symlink => "Symlinked",
+            FileOperation::Hardlink => "Hardlinked",
+        }
+    }
+
+    /// Whether the destination is a file of its own whose metadata can be set
+    /// without touching the source (links share or point at the original).
+    fn creates_independent_file(self) -> bool {
+        matches!(self, FileOperation::Copy | FileOperation::Move)
+    }
+
+    fn apply(self, source: &Path, destination: &Path) -> Result<()> {
+        match self {
+            FileOperation::Copy => {
+                fs::copy(source, destination)?;
+                Ok(())
+            }
+            FileOperation::Move => file_ops::move_file(source, destination),
+            FileOperation::Symlink => file_ops::symlink_file(source, destination),
+            FileOperation::Hardlink => file_ops::hard_link_file(source, destination),
+        }
+    }
+}
+
+struct TransferOptions {
+    operation: FileOperation,
+    template: String,
+    on_conflict: ConflictStrategy,
+    rename_pattern: NamingPolicy,
+    preserve_timestamps: bool,
+    verify: bool,
+    copy_threads: Option<usize>,
+    abort_on_error: bool,
+    min_free_space: Option<u64>,
+    unknown_dir: String,
+    sort: SortOrder,
+    show_progress: bool,
+    max_display: usize,
+}
+
+impl TransferOptions {
+    fn new(args: &OrganizeArgs, operation: FileOperation, max_display: usize) -> Self {
+        Self {
+            operation,
+            template: args.folder_template(),
+            on_conflict: args.on_conflict,
+            rename_pattern: args.rename_pattern,
+            preserve_timestamps: args.preserve_timestamps,
+            verify: args.verify,
+            copy_threads: args.copy_threads,
+            abort_on_error: args.on_error == ErrorPolicy::Abort,
+            min_free_space: args.min_free_space,
+            unknown_dir: args.unknown_dir.clone(),
+            sort: args.sort,
+            show_progress: true,
+            max_display,
+        }
+    }
+}
+
+struct TransferOutcome {
+    files: HashMap<String, Vec<PathBuf>>,
+    operation: FileOperation,
+    skipped: usize,
+    undo_log_path: Option<PathBuf>,
+    verified: Option<usize>,
+}
+
+pub fn handle_organize(args: OrganizeArgs, max_display: usize) -> Result<()> {
+    if let Some(OrganizeAction::Undo(undo_args)) = &args.action {
+        return handle_organize_undo(&undo_args.target, max_display);
+    }
 
-pub fn handle_organize(args: OrganizeArgs) -> Result<()> {
     validation::validate_organize_args(&args)?;
 
+    if let Some(plan_path) = &args.from_plan {
+        return handle_organize_plan(&args, plan_path, max_display);
+    }
+
+    // The library does not follow symlinks, so linked directories are scanned
+    // as additional roots.
+    let linked = if args.follow_symlinks {
+        symlinks::find_linked_directories(&[args.directory.as_path()])
+    } else {
+        LinkedDirectories::default()
+    };
+    let mut scan_roots = vec![args.directory.as_path()];
+    scan_roots.extend(linked.directories.iter().map(PathBuf::as_path));
+
+    let scan_filter = ScanFilter::new(&scan_roots)
+        .with_exclude(&args.exclude)?
+        .with_include(&args.include)?
+        .with_ignore_files(args.recursive)?
+        .with_max_depth(args.max_depth)
+        .with_size_range(args.min_size, args.max_size)
+        .with_min_dimensions(args.min_width, args.min_height)
+        .with_orientation(args.orientation)
+        .with_date_range(args.after, args.before, args.date_source);
+
     let progress = create_scanner_progress();
     progress.set_message("Initializing image manager...");
 
@@ -30,7 +194,7 @@ pub fn handle_organize(args: OrganizeArgs) -> Result<()> {
     }
 
     let manager = ImageManager::with_config(config.clone());
-    progress.finish_with_message("Image manager initialized");
+    finish_with_message(&progress, "Image manager initialized");
 
     let progress_handle = image_manager_lib::ProgressHandle::new();
     let progress_for_monitoring = progress_handle.clone();
@@ -38,7 +202,7 @@ pub fn handle_organize(args: OrganizeArgs) -> Result<()> {
     let monitor_handle = start_progress_monitoring(progress_for_monitoring, "Organizing images...");
 
     let operation_start = std::time::Instant::now();
-    let (organized_images, errors) = manager
+    let (mut organized_images, mut errors) = manager
         .organize_by_date_with_progress(&args.directory, &progress_handle)
         .with_context(|| {
             format!(
@@ -49,94 +213,409 @@ pub fn handle_organize(args: OrganizeArgs) -> Result<()> {
 
     let _ = monitor_handle.join();
 
+    for directory in &linked.directories {
+        let (linked_images, linked_errors) = manager
+            .organize_by_date_with_progress(directory, &image_manager_lib::ProgressHandle::new())
+            .with_context(|| {
+                format!(
+                    "Failed to organize images in linked directory: {}",
+                    directory.display()
+                )
+            })?;
+
+        for (date, files) in linked_images {
+            organized_images.entry(date).or_default().extend(files);
+        }
+        errors.extend(linked_errors);
+    }
+
     let elapsed = operation_start.elapsed();
-    println!(
+    status!(
         "\n{} Organization completed in {:.1}s",
         style("✓").green(),
         elapsed.as_secs_f64()
     );
 
-    if organized_images.is_empty() && errors.is_empty() {
-        println!(
+    display_errors(&linked.loops, "Symlink Loops", max_display);
+
+    let (organized_images, skipped) = scan_filter.filter_organized(organized_images);
+    print_skipped_files(&skipped);
+    display_errors(&skip_errors(&skipped), "Unreadable Files", max_display);
+
+    if organized_images.is_empty() && errors.is_empty() && !is_json_output() {
+        status!(
             "\n{} {}",
-            style("📭").yellow(),
+            style(emoji(EMPTY)).yellow(),
             style("No supported images found in directory").bold()
         );
         return Ok(());
     }
 
-    if let Some(export_path) = &args.export {
+    let organized_images = match args.date_source {
+        Some(source) => {
+            let (regrouped, fallbacks) = regroup_by_date_source(organized_images, source);
+            display_errors(&fallbacks, "Date Source Fallbacks", max_display);
+            regrouped
+        }
+        None => organized_images,
+    };
+
+    let organized_images = match args.limit {
+        Some(limit) => {
+            let (limited, skipped) = limit_organized_images(organized_images, limit);
+            print_limit_notice(limit, skipped);
+            limited
+        }
+        None => organized_images,
+    };
+
+    if args.export.path.is_some() || is_json_output() {
         let total_processed: usize = organized_images.values().map(|v| v.len()).sum();
         let target_config = TargetConfig {
             base_path: args.target_path.clone(),
+            template: Some(args.folder_template()),
+            unknown_dir: Some(args.unknown_dir.clone()),
         };
 
-        let export_data_obj = ExportData::organize(
+        let mut export_data_obj = ExportData::organize(
             organized_images.clone(),
             target_config,
             args.directory.clone(),
             total_processed,
         );
 
-        export_data(&export_data_obj, export_path, args.export_format)?;
+        if args.relative_paths {
+            print_paths_outside_source(&export_data_obj.make_paths_relative(), max_display);
+        }
 
-        println!(
-            "\n{} {}",
-            style("📄").green(),
-            style("Export completed").green()
-        );
-        println!("   Format: {}", style(args.export_format.name()).cyan());
-        println!("   Location: {}", style(export_path.display()).cyan());
+        export_and_report(&export_data_obj, &args.export)?;
+
+        if is_json_output() {
+            print_json_result(&export_data_obj)?;
+        }
     }
 
-    let final_organized_images = if args.copy {
+    let transfer = if let Some(operation) = FileOperation::from_args(&args) {
         if let Some(target_path) = &args.target_path {
-            copy_files_to_target(&organized_images, target_path)?
+            check_disk_space(&organized_images, target_path, operation, args.force)?;
+            Some(transfer_files_to_target(
+                &organized_images,
+                target_path,
+                &TransferOptions::new(&args, operation, max_display),
+            )?)
         } else {
             return Err(anyhow::anyhow!(
-                "--copy flag requires --target-path to be specified"
+                "--{} flag requires --target-path to be specified",
+                operation.verb()
             ));
         }
     } else {
-        organized_images
+        None
+    };
+
+    let final_organized_images = transfer
+        .as_ref()
+        .map_or(&organized_images, |outcome| &outcome.files);
+
+    display_organize_results(
+        final_organized_images,
+        &errors,
+        args.target_path.as_ref(),
+        transfer.as_ref(),
+        &args,
+        max_display,
+    )?;
+
+    Ok(())
+}
+
+/// Copies files already grouped by date into `args.target_path` the way
+/// `organize --copy` does. Returns the new paths by date. `watch` and
+/// `dedupe` use this to file images they selected themselves.
+pub fn copy_to_target(
+    organized_images: &HashMap<String, Vec<PathBuf>>,
+    args: &OrganizeArgs,
+    show_progress: bool,
+    max_display: usize,
+) -> Result<HashMap<String, Vec<PathBuf>>> {
+    let target_path = args
+        .target_path
+        .as_deref()
+        .context("Copying requires a target path")?;
+    let options = TransferOptions {
+        show_progress,
+        ..TransferOptions::new(args, FileOperation::Copy, max_display)
+    };
+
+    Ok(transfer_files_to_target(organized_images, target_path, &options)?.files)
+}
+
+fn handle_organize_plan(args: &OrganizeArgs, plan_path: &Path, max_display: usize) -> Result<()> {
+    let plan = ExportData::from_json_file(plan_path)?;
+
+    if plan.metadata.command != "organize" {
+        return Err(anyhow::anyhow!(
+            "Plan {} was exported by the '{}' command; --from-plan requires an organize export",
+            plan_path.display(),
+            plan.metadata.command
+        ));
+    }
+
+    let (file_records, target_config) = match plan.data {
+        ExportDataType::Organize {
+            file_records,
+            target_config,
+        } => (file_records, target_config),
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Plan {} does not contain organize records",
+                plan_path.display()
+            ))
+        }
     };
 
-    display_organize_results(&final_organized_images, &errors, &args)?;
+    let mut args = args.clone();
+    apply_plan_layout(&mut args, &target_config)?;
+    let args = &args;
+
+    let target_path = args
+        .target_path
+        .clone()
+        .or(target_config.base_path)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "--from-plan requires --target-path when the plan has no target directory"
+            )
+        })?;
+
+    let relative_paths = plan
+        .metadata
+        .command_metadata
+        .get("relative_paths")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let mut organized_images: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for record in file_records {
+        let original_path = if relative_paths && record.original_path.is_relative() {
+            plan.metadata.source_directory.join(&record.original_path)
+        } else {
+            record.original_path
+        };
+
+        organized_images
+            .entry(record.date_directory)
+            .or_default()
+            .push(original_path);
+    }
+
+    status!(
+        "\n{} Replaying organize plan {} ({} files)",
+        style("↻").cyan(),
+        style(plan_path.display()).cyan(),
+        organized_images.values().map(|v| v.len()).sum::<usize>()
+    );
+
+    let operation = FileOperation::from_args(args).unwrap_or(FileOperation::Copy);
+    check_disk_space(&organized_images, &target_path, operation, args.force)?;
+    let transfer = transfer_files_to_target(
+        &organized_images,
+        &target_path,
+        &TransferOptions::new(args, operation, max_display),
+    )?;
+
+    display_organize_results(
+        &transfer.files,
+        &[],
+        Some(&target_path),
+        Some(&transfer),
+        args,
+        max_display,
+    )
+}
+
+/// Replays place files in the layout recorded in the plan. Layout flags on the
+/// command line may only repeat it.
+fn apply_plan_layout(args: &mut OrganizeArgs, target_config: &TargetConfig) -> Result<()> {
+    let defaults = OrganizeArgs::default();
+
+    if let Some(template) = &target_config.template {
+        let requested = args.folder_template();
+        if requested != defaults.folder_template() && &requested != template {
+            return Err(anyhow::anyhow!(
+                "The folder layout {:?} conflicts with the plan's layout {:?}; drop --template (and --granularity, --group-by, --flatten) to replay the plan",
+                requested,
+                template
+            ));
+        }
+        args.template = Some(template.clone());
+        args.flatten = false;
+        args.group_by = GroupBy::Date;
+    }
+
+    if let Some(unknown_dir) = &target_config.unknown_dir {
+        if args.unknown_dir != defaults.unknown_dir && &args.unknown_dir != unknown_dir {
+            return Err(anyhow::anyhow!(
+                "--unknown-dir {:?} conflicts with the plan's {:?}; drop it to replay the plan",
+                args.unknown_dir,
+                unknown_dir
+            ));
+        }
+        args.unknown_dir = unknown_dir.clone();
+    }
 
     Ok(())
 }
 
+fn handle_organize_undo(target: &Path, max_display: usize) -> Result<()> {
+    validation::validate_directory(target, "Target directory")?;
+
+    let report = undo_log::revert(target)?;
+
+    status!(
+        "\n{} {}",
+        style("↺").cyan(),
+        style("Last organize operation reverted").bold().cyan()
+    );
+    status!("   Target directory: {}", style(target.display()).cyan());
+    status!("   Files removed: {}", style(report.removed_files).green());
+    status!(
+        "   Files moved back: {}",
+        style(report.restored_files).green()
+    );
+    status!(
+        "   Directories removed: {}",
+        style(report.removed_directories).green()
+    );
+
+    display_errors(&report.warnings, "Undo Warnings", max_display);
+
+    Ok(())
+}
+
+/// Keeps the first `limit` files by path so repeated runs pick the same
+/// subset. Returns the limited map and the number of files dropped.
+fn limit_organized_images(
+    organized_images: HashMap<String, Vec<PathBuf>>,
+    limit: usize,
+) -> (HashMap<String, Vec<PathBuf>>, usize) {
+    let mut entries: Vec<(PathBuf, String)> = organized_images
+        .into_iter()
+        .flat_map(|(date, files)| files.into_iter().map(move |file| (file, date.clone())))
+        .collect();
+    entries.sort();
+
+    let skipped = entries.len().saturating_sub(limit);
+    entries.truncate(limit);
+
+    let mut limited: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for (file, date) in entries {
+        limited.entry(date).or_default().push(file);
+    }
+
+    (limited, skipped)
+}
+
+/// Re-buckets scanned images by the date read from `source`, falling back to
+/// the file's modification time (and then to the scanned date) when the
+/// requested source has no date for a file.
+fn regroup_by_date_source(
+    organized_images: HashMap<String, Vec<PathBuf>>,
+    source: DateSource,
+) -> (HashMap<String, Vec<PathBuf>>, Vec<String>) {
+    let mut regrouped: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    let mut fallbacks = Vec::new();
+
+    for (scanned_date, files) in organized_images {
+        for file in files {
+            let date = match date_utils::source_date(&file, source) {
+                Some(date) => date,
+                None => match date_utils::modified_date(&file) {
+                    Some(date) if source != DateSource::Mtime => {
+                        fallbacks.push(format!(
+                            "No {} date for {}; using modification time",
+                            source.name(),
+                            file.display()
+                        ));
+                        date
+                    }
+                    _ => {
+                        fallbacks.push(format!(
+                            "No {} date for {}; keeping scanned date {}",
+                            source.name(),
+                            file.display(),
+                            scanned_date
+                        ));
+                        scanned_date.clone()
+                    }
+                },
+            };
+
+            regrouped.entry(date).or_default().push(file);
+        }
+    }
+
+    (regrouped, fallbacks)
+}
+
 fn display_organize_results(
     organized_images: &HashMap<String, Vec<PathBuf>>,
     errors: &[image_manager_lib::ProcessingError],
+    target_path: Option<&PathBuf>,
+    transfer: Option<&TransferOutcome>,
     args: &OrganizeArgs,
+    max_display: usize,
 ) -> Result<()> {
-    println!(
+    status!(
         "\n{} {}",
-        FILES,
+        emoji(FILES),
         style("Organization Preview").bold().cyan()
     );
-    println!("{}", style("━".repeat(50)).dim());
-    print_organize_preview(organized_images, errors, args.target_path.as_ref());
+    status!("{}", style("━".repeat(50)).dim());
+    match args.view {
+        PreviewView::List => print_organize_preview(
+            organized_images,
+            errors,
+            target_path,
+            &args.folder_template(),
+            &args.unknown_dir,
+            args.sort,
+            max_display,
+        ),
+        PreviewView::Tree => print_organize_tree(
+            organized_images,
+            errors,
+            target_path,
+            &args.folder_template(),
+            &args.unknown_dir,
+            args.sort,
+            max_display,
+        ),
+    }
 
     let error_strings: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
 
-    display_errors(&error_strings, "Processing Errors");
+    display_errors(&error_strings, "Processing Errors", max_display);
 
-    if args.copy {
-        if let Some(target_path) = &args.target_path {
+    if let Some(transfer) = transfer {
+        let operation = transfer.operation;
+        if let Some(target_path) = target_path {
             let target_dir = file_ops::get_target_directory(target_path)?;
-            println!(
+            status!(
                 "\n{} {}",
-                style("📁").blue(),
-                style("Files Copied Successfully").bold().blue()
+                style(emoji(FILES)).blue(),
+                style(format!("Files {} Successfully", operation.past()))
+                    .bold()
+                    .blue()
             );
-            println!(
+            status!(
                 "   Target directory: {}",
                 style(target_dir.display()).cyan()
             );
-            println!(
-                "   Total files copied: {}",
+            status!("   Mode: {}", style(operation.verb()).cyan());
+            status!(
+                "   Total files {}: {}",
+                operation.past().to_lowercase(),
                 style(
                     organized_images
                         .values()
@@ -146,27 +625,106 @@ fn display_organize_results(
                 )
                 .green()
             );
+            if let Some(undo_log_path) = &transfer.undo_log_path {
+                status!(
+                    "   Undo log: {} (revert with `organize undo {}`)",
+                    style(undo_log_path.display()).cyan(),
+                    target_dir.display()
+                );
+            }
+            if let Some(verified) = transfer.verified {
+                status!("   Verified: {}", style(verified).green());
+            }
+            if transfer.skipped > 0 {
+                status!(
+                    "   Skipped (already in target): {}",
+                    style(transfer.skipped).yellow()
+                );
+            }
         } else {
             return Err(anyhow::anyhow!(
-                "Copy flag is set but no target path provided"
+                "{} flag is set but no target path provided",
+                operation.past()
             ));
         }
     }
 
+    print_organize_summary(organized_images, errors.len());
+
     Ok(())
 }
 
-fn copy_files_to_target(
+struct PlannedTransfer<'a> {
+    date: &'a str,
+    source: &'a Path,
+    destination: PathBuf,
+    replaces_existing: bool,
+}
+
+#[derive(Default)]
+struct TransferPlan<'a> {
+    transfers: Vec<PlannedTransfer<'a>>,
+    created_dirs: Vec<PathBuf>,
+    skipped: usize,
+    errors: Vec<String>,
+}
+
+#[derive(Default)]
+struct FileTransferResult {
+    transferred: bool,
+    verified: bool,
+    error: Option<String>,
+    verification_error: Option<String>,
+    timestamp_warning: Option<String>,
+    /// Set when the planned name was taken by the time of the copy.
+    renamed_to: Option<PathBuf>,
+}
+
+impl FileTransferResult {
+    fn failed(&self) -> bool {
+        self.error.is_some() || self.verification_error.is_some()
+    }
+}
+
+/// Refuses a copy that clearly won't fit on the target volume unless
+/// `--force` is given.
+fn check_disk_space(
+    organized_images: &HashMap<String, Vec<PathBuf>>,
+    target_path: &Path,
+    operation: FileOperation,
+    force: bool,
+) -> Result<()> {
+    if operation != FileOperation::Copy || force {
+        return Ok(());
+    }
+
+    validation::validate_target_space(organized_images, target_path)
+}
+
+fn transfer_files_to_target(
     organized_images: &HashMap<String, Vec<PathBuf>>,
     target_base: &std::path::Path,
-) -> Result<HashMap<String, Vec<PathBuf>>> {
+    options: &TransferOptions,
+) -> Result<TransferOutcome> {
+    let operation = options.operation;
     let target_dir = file_ops::get_target_directory(target_base)?;
 
     let total_files: usize = organized_images.values().map(|v| v.len()).sum();
     if total_files == 0 {
-        return Ok(HashMap::new());
+        return Ok(TransferOutcome {
+            files: HashMap::new(),
+            operation,
+            skipped: 0,
+            undo_log_path: None,
+            verified: None,
+        });
     }
 
+    let mut undo_log = UndoLog::new(operation.verb());
+    undo_log
+        .directories
+        .extend(file_ops::missing_directories(&target_dir));
+
     fs::create_dir_all(&target_dir).with_context(|| {
         format!(
             "Failed to create target directory: {}",
@@ -174,94 +732,587 @@ fn copy_files_to_target(
         )
     })?;
 
-    let progress = crate::progress::create_copy_progress(total_files as u64);
-    progress.set_message("Copying files...");
+    let progress = if options.show_progress {
+        crate::progress::create_copy_progress(
+            organized_images
+                .values()
+                .flatten()
+                .map(|file| file_ops::file_size(file))
+                .sum(),
+        )
+    } else {
+        ProgressBar::hidden()
+    };
+    progress.set_message(format!("{} files...", operation.progressive()));
+
+    let plan = plan_transfers(organized_images, &target_dir, options, &progress)?;
+    undo_log.directories.extend(plan.created_dirs);
+    let mut copy_errors = plan.errors;
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(options.copy_threads.unwrap_or(0))
+        .build()
+        .context("Failed to start file transfer threads")?;
+
+    let aborted = AtomicBool::new(false);
+    let started = AtomicUsize::new(0);
+    let low_space: OnceLock<String> = OnceLock::new();
+
+    let results: Vec<FileTransferResult> = pool.install(|| {
+        plan.transfers
+            .par_iter()
+            .map(|transfer| {
+                if aborted.load(Ordering::Relaxed) {
+                    return FileTransferResult::default();
+                }
+
+                if let Some(min_free_space) = options.min_free_space {
+                    let count = started.fetch_add(1, Ordering::Relaxed);
+                    if count.is_multiple_of(file_ops::config::FREE_SPACE_CHECK_INTERVAL) {
+                        if let Some(message) = check_min_free_space(&target_dir, min_free_space) {
+                            let _ = low_space.set(message);
+                            aborted.store(true, Ordering::Relaxed);
+                            return FileTransferResult::default();
+                        }
+                    }
+                }
+
+                progress.set_message(format!(
+                    "{} {}",
+                    operation.progressive(),
+                    transfer
+                        .source
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                ));
+                // Measured before the transfer, since a move removes the source.
+                let size = file_ops::file_size(transfer.source);
+                let result = transfer_file(transfer, options);
+                if options.abort_on_error && result.failed() {
+                    aborted.store(true, Ordering::Relaxed);
+                }
+                progress.inc(size);
+                result
+            })
+            .collect()
+    });
 
-    let mut copied_files = HashMap::new();
-    let mut copy_errors = Vec::new();
+    progress.finish();
 
-    for (date, files) in organized_images {
-        let mut files_for_date = Vec::new();
+    let mut copied_files: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    let mut timestamp_warnings = Vec::new();
+    let mut verification_errors = Vec::new();
+    let mut verified = 0;
+    let mut first_failure = None;
 
-        if let Some((year, month, day)) = date_utils::parse_date_string(date) {
-            let date_dir = target_dir.join(year).join(month).join(day);
-            if let Err(e) = fs::create_dir_all(&date_dir) {
+    for (transfer, result) in plan.transfers.iter().zip(results) {
+        let destination = result.renamed_to.as_ref().unwrap_or(&transfer.destination);
+        // Logged here rather than in the worker threads so the log follows
+        // the plan order.
+        match &result.error {
+            Some(error) => {
+                log::info!("Failed {}: {}", transfer.source.display(), error);
+                operation_log::record("error", transfer.source, Some(error));
+            }
+            None if result.transferred => {
+                log::info!(
+                    "{} {} → {}",
+                    operation.past(),
+                    transfer.source.display(),
+                    destination.display()
+                );
+                operation_log::record(
+                    &operation.past().to_lowercase(),
+                    transfer.source,
+                    Some(&destination.to_string_lossy()),
+                );
+            }
+            None => {}
+        }
+        if let Some(error) = &result.verification_error {
+            operation_log::record("error", transfer.source, Some(error));
+        }
+
+        if first_failure.is_none() {
+            first_failure = result
+                .error
+                .clone()
+                .or_else(|| result.verification_error.clone());
+        }
+
+        copy_errors.extend(result.error);
+        verification_errors.extend(result.verification_error);
+        timestamp_warnings.extend(result.timestamp_warning);
+        if result.verified {
+            verified += 1;
+        }
+
+        if result.transferred {
+            let moved_from = (operation == FileOperation::Move).then_some(transfer.source);
+            if let Err(e) =
+                undo_log.record_file(destination, moved_from, transfer.replaces_existing)
+            {
                 copy_errors.push(format!(
-                    "Failed to create directory {}: {}",
-                    date_dir.display(),
+                    "Failed to record {} in undo log: {}",
+                    destination.display(),
                     e
                 ));
-                continue;
             }
 
-            for file in files {
-                progress.set_message(format!(
-                    "Copying {}",
-                    file.file_name().unwrap_or_default().to_string_lossy()
-                ));
+            copied_files
+                .entry(transfer.date.to_string())
+                .or_default()
+                .push(destination.clone());
+        }
+    }
 
-                let target_file = date_dir.join(file.file_name().unwrap_or_default());
-
-                let final_target_file = if target_file.exists() {
-                    match file_ops::get_unique_filename(&target_file) {
-                        Ok(path) => path,
-                        Err(e) => {
-                            copy_errors.push(format!(
-                                "Failed to generate unique filename for {}: {}",
-                                target_file.display(),
-                                e
-                            ));
-                            continue;
-                        }
-                    }
-                } else {
-                    target_file
+    let undo_log_path = if undo_log.is_empty() {
+        None
+    } else {
+        match undo_log.write(&target_dir) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                copy_errors.push(format!("{:#}", e));
+                None
+            }
+        }
+    };
+
+    let abort_reason = match low_space.into_inner() {
+        Some(message) => Some(message),
+        None if options.abort_on_error => first_failure,
+        None => None,
+    };
+
+    if let Some(reason) = abort_reason {
+        let transferred: usize = copied_files.values().map(|v| v.len()).sum();
+        let undo_hint = match &undo_log_path {
+            Some(_) => format!(" (run `organize undo {}` to revert)", target_dir.display()),
+            None => String::new(),
+        };
+        return Err(anyhow::anyhow!(
+            "{}\nAborted; {} files already {} were left in place{}",
+            reason,
+            transferred,
+            operation.past().to_lowercase(),
+            undo_hint
+        ));
+    }
+
+    if !copy_errors.is_empty() {
+        display_errors(
+            &copy_errors,
+            &format!("{} Errors", operation.label()),
+            options.max_display,
+        );
+    }
+
+    display_errors(
+        &verification_errors,
+        "Verification Errors",
+        options.max_display,
+    );
+    display_errors(
+        &timestamp_warnings,
+        "Timestamp Warnings",
+        options.max_display,
+    );
+
+    Ok(TransferOutcome {
+        files: copied_files,
+        operation,
+        skipped: plan.skipped,
+        undo_log_path,
+        verified: options.verify.then_some(verified),
+    })
+}
+
+fn check_min_free_space(target_dir: &Path, min_free_space: u64) -> Option<String> {
+    let available = file_ops::available_space(target_dir).ok()?;
+    (available < min_free_space).then(|| {
+        format!(
+            "Free space on {} dropped to {}, below the --min-free-space limit of {}",
+            target_dir.display(),
+            format_bytes(available),
+            format_bytes(min_free_space)
+        )
+    })
+}
+
+/// Resolves every destination path and creates the target folders up front,
+/// so the parallel transfer phase never races on directory creation or on
+/// two files claiming the same name.
+fn plan_transfers<'a>(
+    organized_images: &'a HashMap<String, Vec<PathBuf>>,
+    target_dir: &Path,
+    options: &'a TransferOptions,
+    progress: &ProgressBar,
+) -> Result<TransferPlan<'a>> {
+    let mut plan = TransferPlan::default();
+    let mut ready_dirs = HashSet::new();
+    let mut claimed = HashSet::new();
+
+    // Plan in date order, so suffixes for clashing names and the transfer
+    // log come out the same on every run.
+    let mut groups: Vec<_> = organized_images.iter().collect();
+    groups.sort_by(|a, b| options.sort.apply(a.0.cmp(b.0)));
+
+    for (date, files) in groups {
+        let mut files: Vec<&PathBuf> = files.iter().collect();
+        files.sort();
+
+        for file in files {
+            // Files without a usable date go to the unknown folder instead of
+            // being dropped.
+            let (group, subdir) =
+                match path_template::destination_subdir(&options.template, date, file) {
+                    Some(subdir) => (date.as_str(), subdir),
+                    None => (
+                        options.unknown_dir.as_str(),
+                        PathBuf::from(&options.unknown_dir),
+                    ),
                 };
 
-                match fs::copy(file, &final_target_file) {
-                    Ok(_) => {
-                        files_for_date.push(final_target_file);
+            let date_dir = target_dir.join(subdir);
+
+            if !ready_dirs.contains(&date_dir) {
+                let missing_dirs = file_ops::missing_directories(&date_dir);
+                if let Err(e) = fs::create_dir_all(&date_dir) {
+                    let message = format!(
+                        "Failed to create directory {} for {}: {}",
+                        date_dir.display(),
+                        file.display(),
+                        e
+                    );
+                    operation_log::record("error", file, Some(&message));
+                    if options.abort_on_error {
+                        return Err(anyhow::anyhow!(message));
                     }
-                    Err(e) => {
-                        copy_errors.push(format!(
-                            "Failed to copy {} to {}: {}",
-                            file.display(),
-                            final_target_file.display(),
-                            e
-                        ));
+                    plan.errors.push(message);
+                    progress.inc(file_ops::file_size(file));
+                    continue;
+                }
+                plan.created_dirs.extend(missing_dirs);
+                ready_dirs.insert(date_dir.clone());
+            }
+
+            let target_file = date_dir.join(file.file_name().unwrap_or_default());
+            let mut replaces_existing = false;
+
+            // Names claimed earlier in this run always get a fresh suffix so
+            // one transfer never replaces the output of another.
+            let claimed_in_run = claimed.contains(&target_file);
+            let exists = target_file.exists();
+
+            let resolved = if claimed_in_run
+                || (exists && options.on_conflict == ConflictStrategy::Rename)
+            {
+                if options.rename_pattern == NamingPolicy::Hash {
+                    resolve_by_content(&target_file, file, &claimed)
+                } else {
+                    file_ops::get_unique_filename(&target_file, options.rename_pattern, |path| {
+                        path.exists() || claimed.contains(path)
+                    })
+                    .map(Some)
+                }
+            } else if exists && options.on_conflict == ConflictStrategy::Skip {
+                Ok(None)
+            } else {
+                replaces_existing = exists;
+                Ok(Some(target_file.clone()))
+            };
+
+            let destination = match resolved {
+                Ok(Some(path)) => path,
+                Err(e) => {
+                    let message = format!(
+                        "Failed to generate unique filename for {}: {}",
+                        target_file.display(),
+                        e
+                    );
+                    operation_log::record("error", file, Some(&message));
+                    if options.abort_on_error {
+                        return Err(anyhow::anyhow!(message));
                     }
+                    plan.errors.push(message);
+                    progress.inc(file_ops::file_size(file));
+                    continue;
                 }
-                progress.inc(1);
+                Ok(None) => {
+                    operation_log::record("skipped", file, Some("already in target"));
+                    plan.skipped += 1;
+                    progress.inc(file_ops::file_size(file));
+                    continue;
+                }
+            };
+
+            claimed.insert(destination.clone());
+            plan.transfers.push(PlannedTransfer {
+                date: group,
+                source: file,
+                destination,
+                replaces_existing,
+            });
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Names a conflicting file after its content for `--rename-pattern hash`.
+/// Returns `None` when an identical file is already in the target, either
+/// under the original name or under the hashed one. A different file that
+/// happens to share the hashed name gets a counter suffix on top.
+fn resolve_by_content(
+    target_file: &Path,
+    source: &Path,
+    claimed: &HashSet<PathBuf>,
+) -> Result<Option<PathBuf>> {
+    if !claimed.contains(target_file) && same_content(target_file, source)? {
+        return Ok(None);
+    }
+
+    let hashed = file_ops::content_hashed_filename(target_file, source)?;
+    if claimed.contains(&hashed) {
+        return Ok(None);
+    }
+    if !hashed.exists() {
+        return Ok(Some(hashed));
+    }
+    if same_content(&hashed, source)? {
+        return Ok(None);
+    }
+    file_ops::get_unique_filename(&hashed, NamingPolicy::Counter, |path| {
+        path.exists() || claimed.contains(path)
+    })
+    .map(Some)
+}
+
+fn same_content(existing: &Path, source: &Path) -> Result<bool> {
+    Ok(file_ops::file_size(existing) == file_ops::file_size(source)
+        && hash_utils::sha256_file(existing)? == hash_utils::sha256_file(source)?)
+}
+
+fn transfer_file(transfer: &PlannedTransfer, options: &TransferOptions) -> FileTransferResult {
+    let operation = options.operation;
+    let source = transfer.source;
+    let mut result = FileTransferResult::default();
+
+    // Hash and stat the source before applying: a move removes it.
+    let source_hash = if options.verify && operation.creates_independent_file() {
+        match hash_utils::sha256_file(source) {
+            Ok(hash) => Some(hash),
+            Err(e) => {
+                result.error = Some(format!(
+                    "Failed to hash {} for verification: {}",
+                    source.display(),
+                    e
+                ));
+                return result;
             }
         }
+    } else {
+        None
+    };
+
+    let source_mtime = if options.preserve_timestamps && operation.creates_independent_file() {
+        fs::metadata(source)
+            .ok()
+            .map(|metadata| FileTime::from_last_modification_time(&metadata))
+    } else {
+        None
+    };
 
-        copied_files.insert(date.clone(), files_for_date);
+    if transfer.replaces_existing {
+        // Remove first so links and copies never write through an existing
+        // symlink into the file it points at.
+        if let Err(e) = fs::remove_file(&transfer.destination) {
+            result.error = Some(format!(
+                "Failed to replace existing file {}: {}",
+                transfer.destination.display(),
+                e
+            ));
+            return result;
+        }
     }
 
-    progress.finish();
+    // Parallel copies reserve their name atomically; the plan only checked
+    // that it was free.
+    let reserve_name = operation == FileOperation::Copy
+        && !transfer.replaces_existing
+        && options.copy_threads != Some(1);
+    let applied = if reserve_name {
+        file_ops::copy_to_unique_file(source, &transfer.destination, options.rename_pattern)
+    } else {
+        operation
+            .apply(source, &transfer.destination)
+            .map(|_| transfer.destination.clone())
+    };
+    let destination = match applied {
+        Ok(path) => path,
+        Err(e) => {
+            result.error = Some(format!(
+                "Failed to {} {} to {}: {}",
+                operation.verb(),
+                source.display(),
+                transfer.destination.display(),
+                e
+            ));
+            return result;
+        }
+    };
+    if destination != transfer.destination {
+        result.renamed_to = Some(destination.clone());
+    }
+    let destination = &destination;
 
-    if !copy_errors.is_empty() {
-        display_errors(&copy_errors, "Copy Errors");
+    if let Some(expected) = &source_hash {
+        let failure = match hash_utils::sha256_file(destination) {
+            Ok(actual) if &actual == expected => None,
+            Ok(_) => Some("checksum mismatch".to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+
+        match failure {
+            None => result.verified = true,
+            Some(reason) if operation == FileOperation::Copy => {
+                let _ = fs::remove_file(destination);
+                result.verification_error = Some(format!(
+                    "Verification failed for {} ({}); removed the bad copy",
+                    destination.display(),
+                    reason
+                ));
+                return result;
+            }
+            Some(reason) => {
+                result.verification_error = Some(format!(
+                    "Verification failed for {} ({}); the original has already been moved",
+                    destination.display(),
+                    reason
+                ));
+            }
+        }
     }
 
-    Ok(copied_files)
+    if let Some(mtime) = source_mtime {
+        if let Err(e) = filetime::set_file_mtime(destination, mtime) {
+            result.timestamp_warning = Some(format!(
+                "Could not preserve modification time of {}: {}",
+                destination.display(),
+                e
+            ));
+        }
+    }
+
+    result.transferred = true;
+    result
 }
 
-fn display_errors(errors: &[String], error_type: &str) {
-    if !errors.is_empty() {
-        println!("\n{} {}", style("⚠️").yellow(), style(error_type).yellow());
-        println!("{}", style("━".repeat(30)).dim());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{export_data, ExportFormat, ExportOptions};
+    use tempfile::TempDir;
 
-        for error in errors.iter().take(config::MAX_DISPLAY_ITEMS) {
-            println!("  {}", style(format!("• {}", error)).red());
+    fn plan_with_template(temp_dir: &TempDir, template: &str) -> (PathBuf, ExportData) {
+        let source = temp_dir.path().join("source");
+        fs::create_dir_all(&source).unwrap();
+        let mut organized = HashMap::new();
+        for (date, name) in [("2023-01-15", "a.jpg"), ("2024-06-02", "b.jpg")] {
+            let file = source.join(name);
+            fs::write(&file, name).unwrap();
+            organized.insert(date.to_string(), vec![file]);
         }
 
-        if errors.len() > config::MAX_DISPLAY_ITEMS {
-            println!(
-                "  {} ... and {} more errors",
-                style("•").red(),
-                errors.len() - config::MAX_DISPLAY_ITEMS
-            );
+        let target_config = TargetConfig {
+            base_path: Some(temp_dir.path().join("target")),
+            template: Some(template.to_string()),
+            unknown_dir: Some("Undated".to_string()),
+        };
+        let data = ExportData::organize(organized, target_config, source, 2);
+        let plan_path = temp_dir.path().join("plan.json");
+        export_data(
+            &data,
+            &plan_path,
+            ExportFormat::Json,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+        (plan_path, data)
+    }
+
+    #[test]
+    fn test_replay_uses_plan_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let (plan_path, data) = plan_with_template(&temp_dir, "{year}-{month}");
+
+        handle_organize_plan(&OrganizeArgs::default(), &plan_path, 10).unwrap();
+
+        let ExportDataType::Organize { file_records, .. } = data.data else {
+            unreachable!()
+        };
+        for record in file_records {
+            let destination = temp_dir.path().join(&record.target_path);
+            assert!(destination.is_file(), "missing {}", destination.display());
         }
+        assert!(temp_dir.path().join("target/2023-01/a.jpg").is_file());
+        assert!(!temp_dir.path().join("target/2023").exists());
+    }
+
+    #[test]
+    fn test_replay_rejects_conflicting_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let (plan_path, _) = plan_with_template(&temp_dir, "{year}-{month}");
+
+        let args = OrganizeArgs {
+            template: Some("{year}/{month}".to_string()),
+            ..Default::default()
+        };
+        assert!(handle_organize_plan(&args, &plan_path, 10).is_err());
+
+        let args = OrganizeArgs {
+            unknown_dir: "Misc".to_string(),
+            ..Default::default()
+        };
+        assert!(handle_organize_plan(&args, &plan_path, 10).is_err());
+
+        let args = OrganizeArgs {
+            template: Some("{year}-{month}".to_string()),
+            unknown_dir: "Undated".to_string(),
+            ..Default::default()
+        };
+        assert!(handle_organize_plan(&args, &plan_path, 10).is_ok());
+    }
+
+    #[test]
+    fn test_resolve_by_content_checks_existing_hashed_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("photo.jpg");
+        let target = temp_dir.path().join("target").join("photo.jpg");
+        fs::create_dir_all(target.parent().unwrap()).unwrap();
+        fs::write(&source, "new").unwrap();
+        fs::write(&target, "old").unwrap();
+        let claimed = HashSet::new();
+
+        let hashed = file_ops::content_hashed_filename(&target, &source).unwrap();
+        assert_eq!(
+            resolve_by_content(&target, &source, &claimed).unwrap(),
+            Some(hashed.clone())
+        );
+
+        fs::write(&hashed, "new").unwrap();
+        assert_eq!(
+            resolve_by_content(&target, &source, &claimed).unwrap(),
+            None
+        );
+
+        fs::write(&hashed, "unrelated").unwrap();
+        let resolved = resolve_by_content(&target, &source, &claimed).unwrap();
+        let expected = hashed.with_file_name(format!(
+            "{}_1.jpg",
+            hashed.file_stem().unwrap().to_string_lossy()
+        ));
+        assert_eq!(resolved, Some(expected));
     }
 }
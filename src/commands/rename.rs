@@ -0,0 +1,169 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use console::style;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image_manager_lib::{ImageManager, ImageManagerConfig};
+
+use super::{NamingPolicy, RenameArgs};
+use crate::export::{export_and_report, ExportData};
+use crate::output::emoji::{emoji, PENCIL};
+use crate::output::{
+    display_errors, is_json_output, print_json_result, print_paths_outside_source,
+    print_rename_preview,
+};
+use crate::progress::{create_scanner_progress, finish_with_message, start_progress_monitoring};
+use crate::status;
+use crate::utils::{date_utils, file_ops, operation_log, path_template, validation};
+
+pub fn handle_rename(args: RenameArgs, max_display: usize) -> Result<()> {
+    validation::validate_rename_args(&args)?;
+
+    let progress = create_scanner_progress();
+    progress.set_message("Initializing image manager...");
+
+    let config = ImageManagerConfig {
+        recursive_scan: args.recursive,
+        parallel_processing: true,
+        ..Default::default()
+    };
+
+    let manager = ImageManager::with_config(config);
+    finish_with_message(&progress, "Image manager initialized");
+
+    let progress_handle = image_manager_lib::ProgressHandle::new();
+    let progress_for_monitoring = progress_handle.clone();
+
+    let monitor_handle = start_progress_monitoring(progress_for_monitoring, "Scanning images...");
+
+    let operation_start = std::time::Instant::now();
+    let (organized_images, errors) = manager
+        .organize_by_date_with_progress(&args.directory, &progress_handle)
+        .with_context(|| {
+            format!(
+                "Failed to scan images in directory: {}",
+                args.directory.display()
+            )
+        })?;
+
+    let _ = monitor_handle.join();
+
+    let elapsed = operation_start.elapsed();
+    status!(
+        "\n{} Scan completed in {:.1}s",
+        style("✓").green(),
+        elapsed.as_secs_f64()
+    );
+
+    let total_processed: usize = organized_images.values().map(|files| files.len()).sum();
+    let renames = plan_renames(&organized_images, &args.template)?;
+    print_rename_preview(&renames, total_processed - renames.len(), args.dry_run);
+
+    let mut rename_errors = Vec::new();
+    if !args.dry_run && !renames.is_empty() {
+        let mut renamed = 0;
+        for (original, new_path) in &renames {
+            match fs::rename(original, new_path) {
+                Ok(()) => {
+                    operation_log::record("renamed", original, Some(&new_path.to_string_lossy()));
+                    renamed += 1;
+                }
+                Err(e) => {
+                    operation_log::record("error", original, Some(&e.to_string()));
+                    rename_errors.push(format!(
+                        "Failed to rename {} to {}: {}",
+                        original.display(),
+                        new_path.display(),
+                        e
+                    ));
+                }
+            }
+        }
+
+        status!(
+            "\n{} Renamed {} files",
+            style(emoji(PENCIL)).green(),
+            style(renamed).yellow()
+        );
+    }
+
+    if args.export.path.is_some() || is_json_output() {
+        let mut export_data_obj = ExportData::rename(
+            &renames,
+            args.directory.clone(),
+            total_processed,
+            args.dry_run,
+        );
+
+        if args.relative_paths {
+            print_paths_outside_source(&export_data_obj.make_paths_relative(), max_display);
+        }
+
+        export_and_report(&export_data_obj, &args.export)?;
+
+        if is_json_output() {
+            print_json_result(&export_data_obj)?;
+        }
+    }
+
+    let error_strings: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    display_errors(&error_strings, "Processing Errors", max_display);
+    display_errors(&rename_errors, "Rename Errors", max_display);
+
+    Ok(())
+}
+
+/// Works out the new name of every file. Files are numbered per date in path
+/// order; names that are taken on disk or by an earlier file in the plan get a
+/// `_1`, `_2`, ... suffix. Files whose name already matches are left out.
+fn plan_renames(
+    organized_images: &HashMap<String, Vec<PathBuf>>,
+    template: &str,
+) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let mut files: Vec<(String, &PathBuf)> = organized_images
+        .iter()
+        .flat_map(|(date, files)| files.iter().map(move |file| (file_date(date, file), file)))
+        .collect();
+    files.sort();
+
+    let mut sequences: HashMap<&str, usize> = HashMap::new();
+    let mut claimed: HashSet<PathBuf> = HashSet::new();
+    let mut renames = Vec::new();
+
+    for (date, file) in &files {
+        let seq = sequences.entry(date.as_str()).or_insert(0);
+        *seq += 1;
+
+        let new_name = path_template::render_file_name(template, date, *seq, file);
+        let candidate = file.with_file_name(new_name);
+        if &candidate == *file {
+            claimed.insert(candidate);
+            continue;
+        }
+
+        let new_path = file_ops::get_unique_filename(&candidate, NamingPolicy::Counter, |path| {
+            claimed.contains(path) || (path != file.as_path() && path.exists())
+        })?;
+        claimed.insert(new_path.clone());
+
+        if &new_path != *file {
+            renames.push(((*file).clone(), new_path));
+        }
+    }
+
+    Ok(renames)
+}
+
+/// The date the scan grouped `file` under, falling back to its EXIF or
+/// modification date when the group is not a date.
+fn file_date(group: &str, file: &Path) -> String {
+    if NaiveDate::parse_from_str(group, "%Y-%m-%d").is_ok() {
+        return group.to_string();
+    }
+
+    date_utils::exif_date(file)
+        .or_else(|| date_utils::modified_date(file))
+        .unwrap_or_else(|| "unknown".to_string())
+}
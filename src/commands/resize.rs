@@ -0,0 +1,194 @@
+use anyhow::{Context, Result};
+use console::style;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use image_manager_lib::{ImageManager, ImageManagerConfig};
+
+use super::{NamingPolicy, ResizeArgs};
+use crate::output::display_errors;
+use crate::output::emoji::{emoji, PICTURE};
+use crate::progress::{
+    create_copy_progress, create_scanner_progress, finish_with_message, start_progress_monitoring,
+};
+use crate::status;
+use crate::utils::image_ops::{self, ResizeOutcome};
+use crate::utils::{file_ops, operation_log, validation};
+
+pub fn handle_resize(args: ResizeArgs, max_display: usize) -> Result<()> {
+    validation::validate_resize_args(&args)?;
+
+    let progress = create_scanner_progress();
+    progress.set_message("Initializing image manager...");
+
+    let config = ImageManagerConfig {
+        recursive_scan: args.recursive,
+        parallel_processing: true,
+        ..Default::default()
+    };
+
+    let manager = ImageManager::with_config(config);
+    finish_with_message(&progress, "Image manager initialized");
+
+    let progress_handle = image_manager_lib::ProgressHandle::new();
+    let progress_for_monitoring = progress_handle.clone();
+
+    let monitor_handle = start_progress_monitoring(progress_for_monitoring, "Scanning images...");
+
+    let (organized_images, errors) = manager
+        .organize_by_date_with_progress(&args.directory, &progress_handle)
+        .with_context(|| {
+            format!(
+                "Failed to scan images in directory: {}",
+                args.directory.display()
+            )
+        })?;
+
+    let _ = monitor_handle.join();
+
+    let mut files: Vec<PathBuf> = organized_images.into_values().flatten().collect();
+    files.sort();
+
+    fs::create_dir_all(&args.target_path).with_context(|| {
+        format!(
+            "Failed to create target directory: {}",
+            args.target_path.display()
+        )
+    })?;
+
+    let operation_start = std::time::Instant::now();
+    let (resized, resize_errors) = resize_files(&files, &args);
+
+    status!(
+        "\n{} Resizing completed in {:.1}s",
+        style("✓").green(),
+        operation_start.elapsed().as_secs_f64()
+    );
+
+    display_resize_results(&resized, &args, max_display);
+
+    let error_strings: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    display_errors(&error_strings, "Processing Errors", max_display);
+    display_errors(&resize_errors, "Resize Errors", max_display);
+
+    Ok(())
+}
+
+/// Writes a resized copy of each file into the target directory, keeping its
+/// path relative to the source directory. Failures are collected instead of
+/// stopping the run.
+fn resize_files(
+    files: &[PathBuf],
+    args: &ResizeArgs,
+) -> (Vec<(PathBuf, ResizeOutcome)>, Vec<String>) {
+    let mut resized = Vec::new();
+    let mut errors = Vec::new();
+    let progress = create_copy_progress(files.iter().map(|file| file_ops::file_size(file)).sum());
+    progress.set_message("Resizing images...");
+
+    for file in files {
+        progress.inc(file_ops::file_size(file));
+
+        match resize_file(file, args) {
+            Ok(outcome) => {
+                let action = if outcome.was_resized() {
+                    "resized"
+                } else {
+                    "copied"
+                };
+                operation_log::record(action, file, None);
+                resized.push((file.clone(), outcome));
+            }
+            Err(e) => {
+                let message = format!("{}: {}", e, e.root_cause());
+                operation_log::record("error", file, Some(&message));
+                errors.push(message);
+            }
+        }
+    }
+
+    progress.finish_and_clear();
+    (resized, errors)
+}
+
+fn resize_file(file: &Path, args: &ResizeArgs) -> Result<ResizeOutcome> {
+    let relative_dir = file
+        .parent()
+        .and_then(|parent| parent.strip_prefix(&args.directory).ok())
+        .unwrap_or(Path::new(""));
+    let destination_dir = args.target_path.join(relative_dir);
+    fs::create_dir_all(&destination_dir)?;
+
+    let destination = file_ops::get_unique_filename(
+        &destination_dir.join(file.file_name().unwrap_or_default()),
+        NamingPolicy::Counter,
+        Path::exists,
+    )?;
+
+    image_ops::resize_image(file, &destination, args.max_width, args.max_height)
+}
+
+fn display_resize_results(
+    resized: &[(PathBuf, ResizeOutcome)],
+    args: &ResizeArgs,
+    max_display: usize,
+) {
+    let copied = resized
+        .iter()
+        .filter(|(_, outcome)| !outcome.was_resized())
+        .count();
+
+    status!(
+        "\n{} {}",
+        style(emoji(PICTURE)).cyan(),
+        style("Resize Results").bold().cyan()
+    );
+    status!("{}", style("━".repeat(50)).dim());
+    status!("Target dir: {}", style(args.target_path.display()).cyan());
+    status!(
+        "Max size: {} x {}",
+        style(bound_label(args.max_width)).cyan(),
+        style(bound_label(args.max_height)).cyan()
+    );
+    status!("Resized: {}", style(resized.len() - copied).yellow());
+    status!(
+        "Copied as-is: {} (already within bounds)",
+        style(copied).yellow()
+    );
+
+    if resized.is_empty() {
+        return;
+    }
+
+    status!("\n{}", style("Dimensions:").bold());
+    for (file, outcome) in resized.iter().take(max_display) {
+        let name = file.file_name().unwrap_or_default().to_string_lossy();
+        let (width, height) = outcome.original;
+        if outcome.was_resized() {
+            let (new_width, new_height) = outcome.resized;
+            status!(
+                "  {} {}x{} → {}",
+                name,
+                width,
+                height,
+                style(format!("{}x{}", new_width, new_height)).green()
+            );
+        } else {
+            status!(
+                "  {} {}x{} {}",
+                name,
+                width,
+                height,
+                style("(unchanged)").dim()
+            );
+        }
+    }
+
+    if resized.len() > max_display {
+        status!("  ... and {} more files", resized.len() - max_display);
+    }
+}
+
+fn bound_label(bound: Option<u32>) -> String {
+    bound.map_or_else(|| "any".to_string(), |pixels| pixels.to_string())
+}
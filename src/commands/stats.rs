@@ -0,0 +1,65 @@
+use anyhow::{Context, Result};
+use console::style;
+
+use image_manager_lib::{ImageManager, ImageManagerConfig};
+
+use super::StatsArgs;
+use crate::export::{export_and_report, ExportData};
+use crate::output::{display_errors, is_json_output, print_json_result, print_stats_summary};
+use crate::progress::{create_scanner_progress, finish_with_message, start_progress_monitoring};
+use crate::status;
+use crate::utils::validation;
+
+pub fn handle_stats(args: StatsArgs, max_display: usize) -> Result<()> {
+    validation::validate_stats_args(&args)?;
+
+    let progress = create_scanner_progress();
+    progress.set_message("Initializing image manager...");
+
+    let config = ImageManagerConfig {
+        recursive_scan: args.recursive,
+        parallel_processing: true,
+        ..Default::default()
+    };
+
+    let manager = ImageManager::with_config(config);
+    finish_with_message(&progress, "Image manager initialized");
+
+    let progress_handle = image_manager_lib::ProgressHandle::new();
+    let progress_for_monitoring = progress_handle.clone();
+
+    let monitor_handle = start_progress_monitoring(progress_for_monitoring, "Scanning images...");
+
+    let operation_start = std::time::Instant::now();
+    let (organized_images, errors) = manager
+        .organize_by_date_with_progress(&args.directory, &progress_handle)
+        .with_context(|| {
+            format!(
+                "Failed to scan images in directory: {}",
+                args.directory.display()
+            )
+        })?;
+
+    let _ = monitor_handle.join();
+
+    let elapsed = operation_start.elapsed();
+    status!(
+        "\n{} Scan completed in {:.1}s",
+        style("✓").green(),
+        elapsed.as_secs_f64()
+    );
+
+    let stats = ExportData::stats(&organized_images, args.directory.clone());
+    print_stats_summary(&stats);
+
+    export_and_report(&stats, &args.export)?;
+
+    if is_json_output() {
+        print_json_result(&stats)?;
+    }
+
+    let error_strings: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+    display_errors(&error_strings, "Processing Errors", max_display);
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use anyhow::Result;
+use console::style;
+use std::path::{Path, PathBuf};
+
+use super::VerifyArgs;
+use crate::export::data::ExportDataType;
+use crate::export::verify::{verify_organize_export, VerifyReport};
+use crate::export::ExportData;
+use crate::output::display_errors;
+use crate::output::emoji::{emoji, LOOKING_GLASS};
+use crate::progress::create_scanner_progress;
+use crate::status;
+use crate::utils::size_utils::format_bytes;
+use crate::utils::validation;
+
+pub fn handle_verify(args: VerifyArgs, max_display: usize) -> Result<()> {
+    validation::validate_verify_args(&args)?;
+
+    let plan = ExportData::from_json_file(&args.plan)?;
+
+    if plan.metadata.command != "organize" {
+        return Err(anyhow::anyhow!(
+            "Plan {} was exported by the '{}' command; verify requires an organize export",
+            args.plan.display(),
+            plan.metadata.command
+        ));
+    }
+
+    let target_path = match &args.target_path {
+        Some(target_path) => target_path.clone(),
+        None => recorded_target(&plan).ok_or_else(|| {
+            anyhow::anyhow!("The plan has no target directory; pass --target-path")
+        })?,
+    };
+    validation::validate_directory(&target_path, "Target directory")?;
+
+    let progress = create_scanner_progress();
+    progress.set_message(format!(
+        "Verifying {} files...",
+        plan.metadata.total_processed
+    ));
+    let operation_start = std::time::Instant::now();
+    let report = verify_organize_export(&plan, &target_path, !args.no_hash)?;
+    progress.finish_and_clear();
+
+    status!(
+        "\n{} Verification completed in {:.1}s",
+        style("✓").green(),
+        operation_start.elapsed().as_secs_f64()
+    );
+
+    display_verify_results(&report, &target_path, max_display);
+    display_errors(&report.errors, "Read Errors", max_display);
+
+    if !report.is_clean() {
+        return Err(anyhow::anyhow!(
+            "Verification failed: {} missing, {} size mismatches, {} hash mismatches, {} extra files",
+            report.missing.len(),
+            report.size_mismatches.len(),
+            report.hash_mismatches.len(),
+            report.extra.len()
+        ));
+    }
+
+    Ok(())
+}
+
+fn recorded_target(plan: &ExportData) -> Option<PathBuf> {
+    match &plan.data {
+        ExportDataType::Organize { target_config, .. } => target_config.base_path.clone(),
+        _ => None,
+    }
+}
+
+fn display_verify_results(report: &VerifyReport, target_path: &Path, max_display: usize) {
+    status!(
+        "\n{} {}",
+        style(emoji(LOOKING_GLASS)).cyan(),
+        style("Verification Results").bold().cyan()
+    );
+    status!("{}", style("━".repeat(50)).dim());
+    status!("Target dir: {}", style(target_path.display()).cyan());
+    status!("Verified: {}", style(report.verified).green());
+    if report.hashed > 0 {
+        status!("Hash-checked: {}", style(report.hashed).green());
+    }
+
+    if report.is_clean() {
+        status!(
+            "\n{} {}",
+            style("✓").green(),
+            style("Every file in the plan is in place").green()
+        );
+        return;
+    }
+
+    let missing: Vec<String> = report
+        .missing
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    display_problems(&missing, "Missing Files", max_display);
+
+    let size_mismatches: Vec<String> = report
+        .size_mismatches
+        .iter()
+        .map(|mismatch| {
+            format!(
+                "{} (expected {}, found {})",
+                mismatch.path.display(),
+                format_bytes(mismatch.expected),
+                format_bytes(mismatch.actual)
+            )
+        })
+        .collect();
+    display_problems(&size_mismatches, "Size Mismatches", max_display);
+
+    let hash_mismatches: Vec<String> = report
+        .hash_mismatches
+        .iter()
+        .map(|path| format!("{} (content differs from the original)", path.display()))
+        .collect();
+    display_problems(&hash_mismatches, "Hash Mismatches", max_display);
+
+    let extra: Vec<String> = report
+        .extra
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect();
+    display_problems(&extra, "Extra Files (not in plan)", max_display);
+}
+
+fn display_problems(problems: &[String], heading: &str, max_display: usize) {
+    if problems.is_empty() {
+        return;
+    }
+
+    status!(
+        "\n{} {} ({})",
+        style("✗").red(),
+        style(heading).red(),
+        problems.len()
+    );
+
+    for problem in problems.iter().take(max_display) {
+        status!("  {}", style(format!("• {}", problem)).red());
+    }
+
+    if problems.len() > max_display {
+        status!(
+            "  {} ... and {} more",
+            style("•").red(),
+            problems.len() - max_display
+        );
+    }
+}
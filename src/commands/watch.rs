@@ -0,0 +1,240 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use console::style;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+use super::{copy_to_target, OrganizeArgs, WatchArgs};
+use crate::output::emoji::{emoji, HOURGLASS, INBOX, WARNING};
+use crate::status;
+use crate::utils::{date_utils, duplicate_utils, validation};
+
+/// How often the event loop wakes up to file settled images and check for
+/// Ctrl-C.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// A new file that has not been filed yet. It is filed once its size stays
+/// the same for a full debounce period, so partially written files are left
+/// alone until the writer is done.
+struct PendingFile {
+    last_change: Instant,
+    size: Option<u64>,
+}
+
+#[derive(Default)]
+struct WatchState {
+    pending: HashMap<PathBuf, PendingFile>,
+    filed: HashSet<PathBuf>,
+    filed_count: usize,
+    failed_count: usize,
+}
+
+pub fn handle_watch(args: WatchArgs, max_display: usize) -> Result<()> {
+    validation::validate_watch_args(&args)?;
+
+    fs::create_dir_all(&args.target_path).with_context(|| {
+        format!(
+            "Failed to create target directory: {}",
+            args.target_path.display()
+        )
+    })?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_requested = Arc::clone(&stop);
+    ctrlc::set_handler(move || stop_requested.store(true, Ordering::SeqCst))
+        .context("Failed to install Ctrl-C handler")?;
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher =
+        notify::recommended_watcher(sender).context("Failed to start file watcher")?;
+    let mode = if args.recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    watcher
+        .watch(&args.directory, mode)
+        .with_context(|| format!("Failed to watch directory: {}", args.directory.display()))?;
+
+    status!(
+        "   Target directory: {}",
+        style(args.target_path.display()).cyan()
+    );
+
+    let organize_args = args.organize_args();
+    let debounce = Duration::from_millis(args.debounce_ms);
+    let mut state = WatchState::default();
+
+    while !stop.load(Ordering::SeqCst) {
+        match receiver.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(event)) => queue_event(event, &mut state),
+            Ok(Err(e)) => print_warning(&format!("Watch error: {}", e)),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        for file in take_settled(&mut state.pending, debounce) {
+            file_image(&file, &organize_args, &mut state, max_display);
+        }
+    }
+
+    // Stop listening, then give files that were still arriving two debounce
+    // periods to settle: one to record their size, one to confirm it.
+    drop(watcher);
+    for event in receiver.try_iter().flatten() {
+        queue_event(event, &mut state);
+    }
+
+    if !state.pending.is_empty() {
+        status!(
+            "\n{} Finishing {} pending files...",
+            style(emoji(HOURGLASS)).yellow(),
+            state.pending.len()
+        );
+
+        for _ in 0..2 {
+            std::thread::sleep(debounce);
+            for file in take_settled(&mut state.pending, debounce) {
+                file_image(&file, &organize_args, &mut state, max_display);
+            }
+        }
+    }
+
+    status!(
+        "\n{} Stopped watching. Filed {} files",
+        style("✓").green(),
+        style(state.filed_count).yellow()
+    );
+
+    if state.failed_count > 0 {
+        status!("   Not filed: {}", style(state.failed_count).red());
+    }
+
+    if !state.pending.is_empty() {
+        let mut unfinished: Vec<&PathBuf> = state.pending.keys().collect();
+        unfinished.sort();
+        print_warning("Still being written when stopped; not filed:");
+        for file in unfinished {
+            status!("  {}", style(format!("• {}", file.display())).yellow());
+        }
+    }
+
+    Ok(())
+}
+
+fn queue_event(event: Event, state: &mut WatchState) {
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        return;
+    }
+
+    let now = Instant::now();
+    for path in event.paths {
+        if state.filed.contains(&path) || !path.is_file() || !duplicate_utils::is_image_file(&path)
+        {
+            continue;
+        }
+
+        state
+            .pending
+            .entry(path)
+            .and_modify(|file| file.last_change = now)
+            .or_insert(PendingFile {
+                last_change: now,
+                size: None,
+            });
+    }
+}
+
+/// Removes and returns the pending files whose size did not change over the
+/// last debounce period. Files that are still growing are checked again
+/// after another period; files that disappeared are dropped.
+fn take_settled(pending: &mut HashMap<PathBuf, PendingFile>, debounce: Duration) -> Vec<PathBuf> {
+    let mut settled = Vec::new();
+
+    pending.retain(|path, file| {
+        if file.last_change.elapsed() < debounce {
+            return true;
+        }
+
+        match fs::metadata(path) {
+            Ok(metadata) if file.size == Some(metadata.len()) => {
+                settled.push(path.clone());
+                false
+            }
+            Ok(metadata) => {
+                file.size = Some(metadata.len());
+                file.last_change = Instant::now();
+                true
+            }
+            Err(_) => false,
+        }
+    });
+
+    settled.sort();
+    settled
+}
+
+fn file_image(
+    file: &Path,
+    organize_args: &OrganizeArgs,
+    state: &mut WatchState,
+    max_display: usize,
+) {
+    let date = date_utils::exif_date(file)
+        .or_else(|| date_utils::modified_date(file))
+        .unwrap_or_else(|| organize_args.unknown_dir.clone());
+
+    let mut organized = HashMap::new();
+    organized.insert(date, vec![file.to_path_buf()]);
+
+    let timestamp = Local::now().format("%H:%M:%S");
+    let name = file.file_name().unwrap_or_default().to_string_lossy();
+
+    match copy_to_target(&organized, organize_args, false, max_display) {
+        Ok(copied) => match copied.values().flatten().next() {
+            Some(destination) => {
+                let shown = organize_args
+                    .target_path
+                    .as_deref()
+                    .and_then(|target| destination.strip_prefix(target).ok())
+                    .unwrap_or(destination);
+                status!(
+                    "{} {} {} → {}",
+                    style(timestamp).dim(),
+                    style(emoji(INBOX)).green(),
+                    name,
+                    style(shown.display()).cyan()
+                );
+                state.filed_count += 1;
+            }
+            None => {
+                status!(
+                    "{} {} {} was not filed",
+                    style(timestamp).dim(),
+                    style(emoji(WARNING)).yellow(),
+                    name
+                );
+                state.failed_count += 1;
+            }
+        },
+        Err(e) => {
+            print_warning(&format!("Failed to file {}: {:#}", file.display(), e));
+            state.failed_count += 1;
+        }
+    }
+
+    state.filed.insert(file.to_path_buf());
+}
+
+fn print_warning(message: &str) {
+    status!(
+        "{} {}",
+        style(emoji(WARNING)).yellow(),
+        style(message).yellow()
+    );
+}
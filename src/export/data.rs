@@ -1,7 +1,15 @@
-use chrono::{DateTime, Utc};
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::commands::{ExifTag, KeepStrategy};
+use crate::utils::{duplicate_utils, exif_utils, hash_utils, path_template};
+
+/// Version of the export layout; bump whenever a change would make older
+/// readers misinterpret the data.
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportData {
@@ -11,12 +19,44 @@ pub struct ExportData {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportMetadata {
+    #[serde(default)]
+    pub schema_version: u32,
     pub timestamp: DateTime<Utc>,
     pub command: String,
     pub version: String,
     pub source_directory: PathBuf,
     pub total_processed: usize,
     pub command_metadata: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub summary: ExportSummary,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportSummary {
+    pub total_files: usize,
+    pub total_bytes: u64,
+    pub extension_counts: BTreeMap<String, usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_groups: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reclaimable_bytes: Option<u64>,
+}
+
+impl ExportSummary {
+    fn from_files<'a>(files: impl Iterator<Item = (&'a str, u64)>) -> Self {
+        let mut summary = Self::default();
+
+        for (extension, size) in files {
+            summary.total_files += 1;
+            summary.total_bytes += size;
+            *summary
+                .extension_counts
+                .entry(extension.to_lowercase())
+                .or_insert(0) += 1;
+        }
+
+        summary
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,11 +70,42 @@ pub enum ExportDataType {
         file_records: Vec<DuplicateFileRecord>,
         similarity_threshold: f32,
     },
+    Stats {
+        extension_stats: Vec<ExtensionStats>,
+        date_range: Option<DateRange>,
+    },
+    Rename {
+        file_records: Vec<RenameFileRecord>,
+        dry_run: bool,
+    },
+    Exif {
+        tags: Vec<String>,
+        file_records: Vec<ExifFileRecord>,
+    },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TargetConfig {
     pub base_path: Option<PathBuf>,
+    /// Folder layout the files are placed in. Older exports without it
+    /// record one folder per date.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unknown_dir: Option<String>,
+}
+
+impl TargetConfig {
+    /// Folder, relative to the target root, that a file from the `date`
+    /// group is placed in.
+    pub fn subdir(&self, date: &str, file: &Path) -> PathBuf {
+        let Some(template) = &self.template else {
+            return PathBuf::from(date);
+        };
+
+        path_template::destination_subdir(template, date, file)
+            .unwrap_or_else(|| PathBuf::from(self.unknown_dir.as_deref().unwrap_or(date)))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +116,12 @@ pub struct OrganizeFileRecord {
     pub file_name: String,
     pub file_size_bytes: u64,
     pub file_extension: String,
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub exif_datetime: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +133,43 @@ pub struct DuplicateFileRecord {
     pub similarity: f32,
     pub file_size_bytes: u64,
     pub file_extension: String,
+    #[serde(default)]
+    pub content_hash: String,
+    /// "keep" for the file that survives under the keep strategy, "remove"
+    /// for the rest of its group.
+    #[serde(default)]
+    pub recommended_action: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameFileRecord {
+    pub original_path: PathBuf,
+    pub new_path: PathBuf,
+    pub file_size_bytes: u64,
+    pub file_extension: String,
+}
+
+/// Values of the requested tags, keyed by tag name. Tags the file does not
+/// carry (or all of them, for files without EXIF) are `None`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExifFileRecord {
+    pub file_path: PathBuf,
+    pub file_size_bytes: u64,
+    pub file_extension: String,
+    pub tags: BTreeMap<String, Option<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionStats {
+    pub extension: String,
+    pub count: usize,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    pub earliest: String,
+    pub latest: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -66,6 +180,83 @@ pub struct DuplicateGroup {
 }
 
 impl ExportData {
+    /// Rewrites source file paths to be relative to the scanned directory.
+    /// Returns the paths that lie outside it, which are left absolute.
+    pub fn make_paths_relative(&mut self) -> Vec<PathBuf> {
+        let source_directory = self.metadata.source_directory.clone();
+        let canonical_source = source_directory.canonicalize().ok();
+        let mut outside_source = Vec::new();
+
+        let mut relativize = |path: &mut PathBuf| {
+            let relative = path.strip_prefix(&source_directory).ok().or_else(|| {
+                canonical_source
+                    .as_ref()
+                    .and_then(|canonical| path.strip_prefix(canonical).ok())
+            });
+
+            match relative {
+                Some(relative) => *path = relative.to_path_buf(),
+                None => outside_source.push(path.clone()),
+            }
+        };
+
+        match &mut self.data {
+            ExportDataType::Organize { file_records, .. } => {
+                for record in file_records {
+                    relativize(&mut record.original_path);
+                }
+            }
+            ExportDataType::Duplicates { file_records, .. } => {
+                for record in file_records {
+                    relativize(&mut record.file_path);
+                }
+            }
+            ExportDataType::Stats { .. } => {}
+            ExportDataType::Exif { file_records, .. } => {
+                for record in file_records {
+                    relativize(&mut record.file_path);
+                }
+            }
+            ExportDataType::Rename { file_records, .. } => {
+                for record in file_records {
+                    relativize(&mut record.original_path);
+                    relativize(&mut record.new_path);
+                }
+            }
+        }
+
+        self.metadata
+            .command_metadata
+            .insert("relative_paths".to_string(), serde_json::json!(true));
+
+        outside_source
+    }
+
+    pub fn from_json_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read export file: {}", path.display()))?;
+
+        let value: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse JSON export: {}", path.display()))?;
+
+        let schema_version = value
+            .pointer("/metadata/schema_version")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+
+        if schema_version > u64::from(EXPORT_SCHEMA_VERSION) {
+            return Err(anyhow::anyhow!(
+                "Export {} uses schema version {}, but this version of image-manager-cli only supports up to version {}; please upgrade",
+                path.display(),
+                schema_version,
+                EXPORT_SCHEMA_VERSION
+            ));
+        }
+
+        serde_json::from_value(value)
+            .with_context(|| format!("Export file has an unexpected layout: {}", path.display()))
+    }
+
     pub fn organize(
         organized_files: HashMap<String, Vec<PathBuf>>,
         target_config: TargetConfig,
@@ -90,20 +281,22 @@ impl ExportData {
 
                 let file_size = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
 
-                let target_path = if let Some(base_path) = &target_config.base_path {
-                    let dir_name = base_path
-                        .file_name()
-                        .and_then(|name| name.to_str())
-                        .unwrap_or("untitled");
-                    PathBuf::from(format!("{}/{}/{}", dir_name, date, file_name))
-                } else {
-                    let source_dir_name = source_directory
-                        .file_name()
-                        .and_then(|name| name.to_str())
-                        .unwrap_or("untitled");
-                    PathBuf::from(format!("{}/{}/{}", source_dir_name, date, file_name))
+                let (width, height) = match image::image_dimensions(file_path) {
+                    Ok((width, height)) => (Some(width), Some(height)),
+                    Err(_) => (None, None),
                 };
 
+                let dir_name = target_config
+                    .base_path
+                    .as_deref()
+                    .unwrap_or(&source_directory)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .unwrap_or("untitled");
+                let target_path = PathBuf::from(dir_name)
+                    .join(target_config.subdir(date, file_path))
+                    .join(&file_name);
+
                 file_records.push(OrganizeFileRecord {
                     original_path: file_path.clone(),
                     target_path,
@@ -111,10 +304,19 @@ impl ExportData {
                     file_name,
                     file_extension,
                     file_size_bytes: file_size,
+                    width,
+                    height,
+                    exif_datetime: exif_utils::read_datetime_original(file_path),
                 });
             }
         }
 
+        let summary = ExportSummary::from_files(
+            file_records
+                .iter()
+                .map(|r| (r.file_extension.as_str(), r.file_size_bytes)),
+        );
+
         let mut command_metadata = HashMap::new();
         if let Some(ref base_path) = target_config.base_path {
             command_metadata.insert(
@@ -125,12 +327,14 @@ impl ExportData {
 
         Self {
             metadata: ExportMetadata {
+                schema_version: EXPORT_SCHEMA_VERSION,
                 timestamp: Utc::now(),
                 command: "organize".to_string(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
                 source_directory,
                 total_processed,
                 command_metadata,
+                summary,
             },
             data: ExportDataType::Organize {
                 file_records,
@@ -144,10 +348,16 @@ impl ExportData {
         similarity_threshold: f32,
         source_directory: PathBuf,
         total_processed: usize,
+        include_hashes: bool,
+        keep: KeepStrategy,
     ) -> Self {
         let mut file_records = Vec::new();
+        let mut reclaimable_bytes = 0;
 
         for group in &duplicate_groups {
+            let mut group_sizes = Vec::with_capacity(group.files.len());
+            let survivor = duplicate_utils::select_survivor(&group.files, keep);
+
             for (position, file_path) in group.files.iter().enumerate() {
                 let file_extension = file_path
                     .extension()
@@ -157,6 +367,12 @@ impl ExportData {
 
                 let file_size = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
 
+                let content_hash = if include_hashes {
+                    hash_utils::sha256_file(file_path).unwrap_or_default()
+                } else {
+                    String::new()
+                };
+
                 file_records.push(DuplicateFileRecord {
                     file_path: file_path.clone(),
                     group_id: group.group_id.clone(),
@@ -165,10 +381,29 @@ impl ExportData {
                     similarity: group.similarity,
                     file_size_bytes: file_size,
                     file_extension,
+                    content_hash,
+                    recommended_action: if position == survivor {
+                        "keep"
+                    } else {
+                        "remove"
+                    }
+                    .to_string(),
                 });
+                group_sizes.push(file_size);
             }
+
+            let kept = group_sizes.get(survivor).copied().unwrap_or(0);
+            reclaimable_bytes += group_sizes.iter().sum::<u64>() - kept;
         }
 
+        let mut summary = ExportSummary::from_files(
+            file_records
+                .iter()
+                .map(|r| (r.file_extension.as_str(), r.file_size_bytes)),
+        );
+        summary.duplicate_groups = Some(duplicate_groups.len());
+        summary.reclaimable_bytes = Some(reclaimable_bytes);
+
         let mut command_metadata = HashMap::new();
         command_metadata.insert(
             "similarity_threshold".to_string(),
@@ -181,12 +416,14 @@ impl ExportData {
 
         Self {
             metadata: ExportMetadata {
+                schema_version: EXPORT_SCHEMA_VERSION,
                 timestamp: Utc::now(),
                 command: "duplicates".to_string(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
                 source_directory,
                 total_processed,
                 command_metadata,
+                summary,
             },
             data: ExportDataType::Duplicates {
                 file_records,
@@ -194,4 +431,371 @@ impl ExportData {
             },
         }
     }
+
+    pub fn rename(
+        renames: &[(PathBuf, PathBuf)],
+        source_directory: PathBuf,
+        total_processed: usize,
+        dry_run: bool,
+    ) -> Self {
+        let file_records: Vec<RenameFileRecord> = renames
+            .iter()
+            .map(|(original_path, new_path)| RenameFileRecord {
+                original_path: original_path.clone(),
+                new_path: new_path.clone(),
+                // The file may already carry its new name when exported
+                // after renaming.
+                file_size_bytes: std::fs::metadata(original_path)
+                    .or_else(|_| std::fs::metadata(new_path))
+                    .map(|m| m.len())
+                    .unwrap_or(0),
+                file_extension: original_path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .unwrap_or("")
+                    .to_string(),
+            })
+            .collect();
+
+        let summary = ExportSummary::from_files(
+            file_records
+                .iter()
+                .map(|r| (r.file_extension.as_str(), r.file_size_bytes)),
+        );
+
+        let mut command_metadata = HashMap::new();
+        command_metadata.insert("dry_run".to_string(), serde_json::json!(dry_run));
+
+        Self {
+            metadata: ExportMetadata {
+                schema_version: EXPORT_SCHEMA_VERSION,
+                timestamp: Utc::now(),
+                command: "rename".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                source_directory,
+                total_processed,
+                command_metadata,
+                summary,
+            },
+            data: ExportDataType::Rename {
+                file_records,
+                dry_run,
+            },
+        }
+    }
+
+    pub fn exif(files: &[PathBuf], tags: &[ExifTag], source_directory: PathBuf) -> Self {
+        let file_records: Vec<ExifFileRecord> = files
+            .iter()
+            .map(|file_path| {
+                let exif = exif_utils::read_exif(file_path);
+                ExifFileRecord {
+                    file_path: file_path.clone(),
+                    file_size_bytes: std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0),
+                    file_extension: file_path
+                        .extension()
+                        .and_then(|e| e.to_str())
+                        .unwrap_or("")
+                        .to_string(),
+                    tags: tags
+                        .iter()
+                        .map(|&tag| {
+                            let value = exif
+                                .as_ref()
+                                .and_then(|exif| exif_utils::tag_value(exif, tag));
+                            (tag.name().to_string(), value)
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let summary = ExportSummary::from_files(
+            file_records
+                .iter()
+                .map(|r| (r.file_extension.as_str(), r.file_size_bytes)),
+        );
+
+        let tag_names: Vec<String> = tags.iter().map(|tag| tag.name().to_string()).collect();
+        let mut command_metadata = HashMap::new();
+        command_metadata.insert("tags".to_string(), serde_json::json!(tag_names));
+
+        Self {
+            metadata: ExportMetadata {
+                schema_version: EXPORT_SCHEMA_VERSION,
+                timestamp: Utc::now(),
+                command: "exif".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                source_directory,
+                total_processed: file_records.len(),
+                command_metadata,
+                summary,
+            },
+            data: ExportDataType::Exif {
+                tags: tag_names,
+                file_records,
+            },
+        }
+    }
+
+    /// Profiles the scanned images: per-extension counts and sizes (sorted by
+    /// extension) and the range of dates they were grouped under.
+    pub fn stats(
+        organized_files: &HashMap<String, Vec<PathBuf>>,
+        source_directory: PathBuf,
+    ) -> Self {
+        let mut extensions: BTreeMap<String, ExtensionStats> = BTreeMap::new();
+        let mut sizes = Vec::new();
+
+        for file_path in organized_files.values().flatten() {
+            let extension = file_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            let file_size = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
+
+            let stats = extensions
+                .entry(extension.clone())
+                .or_insert_with(|| ExtensionStats {
+                    extension: extension.clone(),
+                    count: 0,
+                    total_bytes: 0,
+                });
+            stats.count += 1;
+            stats.total_bytes += file_size;
+            sizes.push((extension, file_size));
+        }
+
+        let mut dates: Vec<NaiveDate> = organized_files
+            .iter()
+            .filter(|(_, files)| !files.is_empty())
+            .filter_map(|(date, _)| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .collect();
+        dates.sort();
+        let date_range = match (dates.first(), dates.last()) {
+            (Some(earliest), Some(latest)) => Some(DateRange {
+                earliest: earliest.format("%Y-%m-%d").to_string(),
+                latest: latest.format("%Y-%m-%d").to_string(),
+            }),
+            _ => None,
+        };
+
+        let summary = ExportSummary::from_files(
+            sizes
+                .iter()
+                .map(|(extension, size)| (extension.as_str(), *size)),
+        );
+
+        Self {
+            metadata: ExportMetadata {
+                schema_version: EXPORT_SCHEMA_VERSION,
+                timestamp: Utc::now(),
+                command: "stats".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                source_directory,
+                total_processed: summary.total_files,
+                command_metadata: HashMap::new(),
+                summary,
+            },
+            data: ExportDataType::Stats {
+                extension_stats: extensions.into_values().collect(),
+                date_range,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_sample_export(temp_dir: &TempDir) -> PathBuf {
+        let data = ExportData::organize(
+            HashMap::new(),
+            TargetConfig::default(),
+            temp_dir.path().to_path_buf(),
+            0,
+        );
+        let path = temp_dir.path().join("export.json");
+        fs::write(&path, serde_json::to_string_pretty(&data).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_from_json_file_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_sample_export(&temp_dir);
+
+        let data = ExportData::from_json_file(&path).unwrap();
+        assert_eq!(data.metadata.schema_version, EXPORT_SCHEMA_VERSION);
+        assert_eq!(data.metadata.command, "organize");
+    }
+
+    #[test]
+    fn test_from_json_file_rejects_newer_schema() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_sample_export(&temp_dir);
+
+        let mut value: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        value["metadata"]["schema_version"] = serde_json::json!(EXPORT_SCHEMA_VERSION + 1);
+        fs::write(&path, value.to_string()).unwrap();
+
+        let error = ExportData::from_json_file(&path).unwrap_err();
+        assert!(error.to_string().contains("schema version"));
+        assert!(error.to_string().contains("please upgrade"));
+    }
+
+    #[test]
+    fn test_make_paths_relative() {
+        let mut organized = HashMap::new();
+        organized.insert(
+            "2023-01-15".to_string(),
+            vec![
+                PathBuf::from("/photos/2023/a.jpg"),
+                PathBuf::from("/elsewhere/b.jpg"),
+            ],
+        );
+        let mut data = ExportData::organize(
+            organized,
+            TargetConfig::default(),
+            PathBuf::from("/photos"),
+            2,
+        );
+
+        let outside = data.make_paths_relative();
+        assert_eq!(outside, vec![PathBuf::from("/elsewhere/b.jpg")]);
+
+        match &data.data {
+            ExportDataType::Organize { file_records, .. } => {
+                assert_eq!(file_records[0].original_path, PathBuf::from("2023/a.jpg"));
+                assert_eq!(
+                    file_records[1].original_path,
+                    PathBuf::from("/elsewhere/b.jpg")
+                );
+            }
+            _ => panic!("expected organize export data"),
+        }
+    }
+
+    #[test]
+    fn test_duplicates_summary() {
+        let temp_dir = TempDir::new().unwrap();
+        let small = temp_dir.path().join("small.jpg");
+        let large = temp_dir.path().join("large.JPG");
+        let other = temp_dir.path().join("other.png");
+        fs::write(&small, vec![0u8; 10]).unwrap();
+        fs::write(&large, vec![0u8; 30]).unwrap();
+        fs::write(&other, vec![0u8; 5]).unwrap();
+
+        let data = ExportData::duplicates(
+            vec![DuplicateGroup {
+                group_id: "group_1".to_string(),
+                files: vec![small, large, other],
+                similarity: 0.9,
+            }],
+            0.9,
+            temp_dir.path().to_path_buf(),
+            3,
+            false,
+            KeepStrategy::Largest,
+        );
+
+        let summary = &data.metadata.summary;
+        assert_eq!(summary.total_files, 3);
+        assert_eq!(summary.total_bytes, 45);
+        assert_eq!(summary.extension_counts.get("jpg"), Some(&2));
+        assert_eq!(summary.extension_counts.get("png"), Some(&1));
+        assert_eq!(summary.duplicate_groups, Some(1));
+        assert_eq!(summary.reclaimable_bytes, Some(15));
+
+        match &data.data {
+            ExportDataType::Duplicates { file_records, .. } => {
+                let actions: Vec<&str> = file_records
+                    .iter()
+                    .map(|r| r.recommended_action.as_str())
+                    .collect();
+                assert_eq!(actions, vec!["remove", "keep", "remove"]);
+            }
+            _ => panic!("expected duplicates export data"),
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let temp_dir = TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.jpg");
+        let b = temp_dir.path().join("b.JPG");
+        let c = temp_dir.path().join("c.png");
+        fs::write(&a, vec![0u8; 10]).unwrap();
+        fs::write(&b, vec![0u8; 20]).unwrap();
+        fs::write(&c, vec![0u8; 5]).unwrap();
+
+        let mut organized = HashMap::new();
+        organized.insert("2023-05-01".to_string(), vec![a, c]);
+        organized.insert("2021-12-24".to_string(), vec![b]);
+        organized.insert("Unknown".to_string(), vec![]);
+
+        let data = ExportData::stats(&organized, temp_dir.path().to_path_buf());
+        assert_eq!(data.metadata.summary.total_files, 3);
+        assert_eq!(data.metadata.summary.total_bytes, 35);
+
+        match &data.data {
+            ExportDataType::Stats {
+                extension_stats,
+                date_range,
+            } => {
+                assert_eq!(
+                    extension_stats,
+                    &vec![
+                        ExtensionStats {
+                            extension: "jpg".to_string(),
+                            count: 2,
+                            total_bytes: 30,
+                        },
+                        ExtensionStats {
+                            extension: "png".to_string(),
+                            count: 1,
+                            total_bytes: 5,
+                        },
+                    ]
+                );
+                assert_eq!(
+                    date_range,
+                    &Some(DateRange {
+                        earliest: "2021-12-24".to_string(),
+                        latest: "2023-05-01".to_string(),
+                    })
+                );
+            }
+            _ => panic!("expected stats export data"),
+        }
+    }
+
+    #[test]
+    fn test_exif_lists_files_without_exif() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("plain.jpg");
+        fs::write(&file, "no exif here").unwrap();
+
+        let data = ExportData::exif(
+            std::slice::from_ref(&file),
+            &[ExifTag::Date, ExifTag::Gps],
+            temp_dir.path().to_path_buf(),
+        );
+
+        match &data.data {
+            ExportDataType::Exif { tags, file_records } => {
+                assert_eq!(tags, &vec!["date".to_string(), "gps".to_string()]);
+                assert_eq!(file_records.len(), 1);
+                assert_eq!(file_records[0].file_path, file);
+                assert_eq!(file_records[0].tags.get("date"), Some(&None));
+                assert_eq!(file_records[0].tags.get("gps"), Some(&None));
+            }
+            _ => panic!("expected exif export data"),
+        }
+    }
 }
@@ -1,33 +1,70 @@
 use anyhow::{Context, Result};
-use std::fs::File;
 use std::io::Write;
-use std::path::Path;
 
-use super::data::{ExportData, ExportDataType};
+use super::data::{ExportData, ExportDataType, ExportMetadata, ExportSummary};
 use super::trait_impl::Exporter;
 
-pub struct CsvExporter;
+pub struct CsvExporter {
+    pub delimiter: u8,
+    pub include_header: bool,
+    /// Write a UTF-8 byte order mark before the header, for Excel.
+    pub bom: bool,
+}
+
+impl Default for CsvExporter {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            include_header: true,
+            bom: false,
+        }
+    }
+}
+
+/// Excel only detects UTF-8 in CSV files that start with a byte order mark.
+const UTF8_BOM: &[u8] = "\u{FEFF}".as_bytes();
 
+// CSV has no nested structure, so the metadata summary is intentionally left
+// out of CSV exports; use JSON, YAML or XML when the summary is needed.
 impl Exporter for CsvExporter {
-    fn export(&self, data: &ExportData, path: &Path) -> Result<()> {
-        let mut file = File::create(path)
-            .with_context(|| format!("Failed to create CSV file: {}", path.display()))?;
+    fn export(&self, data: &ExportData, writer: &mut dyn Write) -> Result<()> {
+        // Appends skip the header and must not repeat the mark either.
+        if self.bom && self.include_header {
+            writer.write_all(UTF8_BOM)?;
+        }
+
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(writer);
 
         match &data.data {
             ExportDataType::Organize {
                 file_records,
                 target_config,
             } => {
-                self.export_organize_csv(&mut file, file_records, target_config)?;
+                self.export_organize_csv(&mut writer, file_records, target_config)?;
             }
             ExportDataType::Duplicates {
                 file_records,
                 similarity_threshold,
             } => {
-                self.export_duplicates_csv(&mut file, file_records, *similarity_threshold)?;
+                self.export_duplicates_csv(&mut writer, file_records, *similarity_threshold)?;
+            }
+            ExportDataType::Stats {
+                extension_stats, ..
+            } => {
+                self.export_stats_csv(&mut writer, extension_stats)?;
+            }
+            ExportDataType::Rename { file_records, .. } => {
+                self.export_rename_csv(&mut writer, file_records)?;
+            }
+            ExportDataType::Exif { tags, file_records } => {
+                self.export_exif_csv(&mut writer, tags, file_records)?;
             }
         }
 
+        writer.flush().with_context(|| "Failed to write CSV data")?;
+
         Ok(())
     }
 }
@@ -35,67 +72,1324 @@ impl Exporter for CsvExporter {
 impl CsvExporter {
     fn export_organize_csv(
         &self,
-        file: &mut File,
+        writer: &mut csv::Writer<&mut dyn Write>,
         file_records: &[crate::export::data::OrganizeFileRecord],
         _target_config: &crate::export::data::TargetConfig,
     ) -> Result<()> {
+        if self.include_header {
+            writer.write_record([
+                "Original Path",
+                "Target Path",
+                "Date Directory",
+                "File Name",
+                "File Size (bytes)",
+                "File Extension",
+                "Width",
+                "Height",
+                "EXIF DateTime",
+            ])?;
+        }
+
+        for record in file_records {
+            writer.write_record([
+                record.original_path.display().to_string(),
+                record.target_path.display().to_string(),
+                record.date_directory.clone(),
+                record.file_name.clone(),
+                record.file_size_bytes.to_string(),
+                record.file_extension.clone(),
+                optional_to_string(record.width),
+                optional_to_string(record.height),
+                record.exif_datetime.clone().unwrap_or_default(),
+            ])?;
+        }
+
+        Ok(())
+    }
+
+    fn export_duplicates_csv(
+        &self,
+        writer: &mut csv::Writer<&mut dyn Write>,
+        file_records: &[crate::export::data::DuplicateFileRecord],
+        _similarity_threshold: f32,
+    ) -> Result<()> {
+        if self.include_header {
+            writer.write_record([
+                "Group ID",
+                "File Path",
+                "Position in Group",
+                "Group Size",
+                "Similarity",
+                "File Size (bytes)",
+                "File Extension",
+                "Content Hash",
+                "Recommended Action",
+            ])?;
+        }
+
+        for record in file_records {
+            writer.write_record([
+                record.group_id.clone(),
+                record.file_path.display().to_string(),
+                record.position_in_group.to_string(),
+                record.group_size.to_string(),
+                format!("{:.4}", record.similarity),
+                record.file_size_bytes.to_string(),
+                record.file_extension.clone(),
+                record.content_hash.clone(),
+                record.recommended_action.clone(),
+            ])?;
+        }
+
+        Ok(())
+    }
+
+    fn export_stats_csv(
+        &self,
+        writer: &mut csv::Writer<&mut dyn Write>,
+        extension_stats: &[crate::export::data::ExtensionStats],
+    ) -> Result<()> {
+        if self.include_header {
+            writer.write_record(["File Extension", "Count", "Total Size (bytes)"])?;
+        }
+
+        for stats in extension_stats {
+            writer.write_record([
+                stats.extension.clone(),
+                stats.count.to_string(),
+                stats.total_bytes.to_string(),
+            ])?;
+        }
+
+        Ok(())
+    }
+
+    fn export_rename_csv(
+        &self,
+        writer: &mut csv::Writer<&mut dyn Write>,
+        file_records: &[crate::export::data::RenameFileRecord],
+    ) -> Result<()> {
+        if self.include_header {
+            writer.write_record([
+                "Original Path",
+                "New Path",
+                "File Size (bytes)",
+                "File Extension",
+            ])?;
+        }
+
+        for record in file_records {
+            writer.write_record([
+                record.original_path.display().to_string(),
+                record.new_path.display().to_string(),
+                record.file_size_bytes.to_string(),
+                record.file_extension.clone(),
+            ])?;
+        }
+
+        Ok(())
+    }
+
+    fn export_exif_csv(
+        &self,
+        writer: &mut csv::Writer<&mut dyn Write>,
+        tags: &[String],
+        file_records: &[crate::export::data::ExifFileRecord],
+    ) -> Result<()> {
+        if self.include_header {
+            let mut header = vec!["File Path".to_string()];
+            header.extend(tags.iter().cloned());
+            writer.write_record(&header)?;
+        }
+
+        for record in file_records {
+            let mut row = vec![record.file_path.display().to_string()];
+            row.extend(
+                tags.iter()
+                    .map(|tag| exif_tag_value(record, tag).to_string()),
+            );
+            writer.write_record(&row)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The `index.csv` written by `--split-groups`: one row per duplicate group
+/// with the name of its CSV file.
+pub struct GroupIndexExporter {
+    pub delimiter: u8,
+    pub include_header: bool,
+    pub bom: bool,
+    pub extension: String,
+}
+
+impl Exporter for GroupIndexExporter {
+    fn export(&self, data: &ExportData, writer: &mut dyn Write) -> Result<()> {
+        let ExportDataType::Duplicates { file_records, .. } = &data.data else {
+            return Err(anyhow::anyhow!("A group index needs duplicate data"));
+        };
+        if self.bom && self.include_header {
+            writer.write_all(UTF8_BOM)?;
+        }
+
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .from_writer(writer);
+        if self.include_header {
+            writer.write_record(["Group ID", "File", "File Count", "Similarity"])?;
+        }
+
+        for group in file_records.chunk_by(|a, b| a.group_id == b.group_id) {
+            writer.write_record([
+                group[0].group_id.clone(),
+                format!("{}.{}", group[0].group_id, self.extension),
+                group.len().to_string(),
+                format!("{:.4}", group[0].similarity),
+            ])?;
+        }
+
+        writer.flush().with_context(|| "Failed to write CSV data")?;
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct JsonExporter {
+    pub compact: bool,
+}
+
+impl Exporter for JsonExporter {
+    fn export(&self, data: &ExportData, writer: &mut dyn Write) -> Result<()> {
+        if self.compact {
+            serde_json::to_writer(writer, data)
+        } else {
+            serde_json::to_writer_pretty(writer, data)
+        }
+        .with_context(|| "Failed to serialize data to JSON")?;
+
+        Ok(())
+    }
+}
+
+pub struct NdjsonExporter;
+
+#[derive(serde::Serialize)]
+struct NdjsonMetadataLine<'a> {
+    #[serde(rename = "type")]
+    line_type: &'static str,
+    #[serde(flatten)]
+    metadata: &'a ExportMetadata,
+}
+
+impl Exporter for NdjsonExporter {
+    fn export(&self, data: &ExportData, writer: &mut dyn Write) -> Result<()> {
+        let metadata_line = NdjsonMetadataLine {
+            line_type: "metadata",
+            metadata: &data.metadata,
+        };
+        self.write_line(writer, &metadata_line)?;
+
+        match &data.data {
+            ExportDataType::Organize { file_records, .. } => {
+                for record in file_records {
+                    self.write_line(writer, record)?;
+                }
+            }
+            ExportDataType::Duplicates { file_records, .. } => {
+                for record in file_records {
+                    self.write_line(writer, record)?;
+                }
+            }
+            ExportDataType::Stats {
+                extension_stats, ..
+            } => {
+                for stats in extension_stats {
+                    self.write_line(writer, stats)?;
+                }
+            }
+            ExportDataType::Rename { file_records, .. } => {
+                for record in file_records {
+                    self.write_line(writer, record)?;
+                }
+            }
+            ExportDataType::Exif { file_records, .. } => {
+                for record in file_records {
+                    self.write_line(writer, record)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl NdjsonExporter {
+    fn write_line<T: serde::Serialize>(&self, writer: &mut dyn Write, value: &T) -> Result<()> {
+        serde_json::to_writer(&mut *writer, value)
+            .with_context(|| "Failed to serialize data to NDJSON")?;
+        writeln!(writer)?;
+        Ok(())
+    }
+}
+
+pub struct YamlExporter;
+
+impl Exporter for YamlExporter {
+    fn export(&self, data: &ExportData, writer: &mut dyn Write) -> Result<()> {
+        serde_yaml::to_writer(writer, data).with_context(|| "Failed to serialize data to YAML")?;
+
+        Ok(())
+    }
+}
+
+pub struct XmlExporter;
+
+impl Exporter for XmlExporter {
+    fn export(&self, data: &ExportData, writer: &mut dyn Write) -> Result<()> {
+        writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(writer, "<export>")?;
+        self.write_metadata(writer, &data.metadata)?;
+
+        match &data.data {
+            ExportDataType::Organize {
+                file_records,
+                target_config,
+            } => {
+                match &target_config.base_path {
+                    Some(base_path) => writeln!(
+                        writer,
+                        "  <organize target_base_path=\"{}\">",
+                        escape_markup(&base_path.to_string_lossy())
+                    )?,
+                    None => writeln!(writer, "  <organize>")?,
+                }
+
+                for record in file_records {
+                    writeln!(
+                        writer,
+                        "    <file original_path=\"{}\" target_path=\"{}\" date_directory=\"{}\" file_name=\"{}\" file_size_bytes=\"{}\" file_extension=\"{}\" width=\"{}\" height=\"{}\" exif_datetime=\"{}\"/>",
+                        escape_markup(&record.original_path.to_string_lossy()),
+                        escape_markup(&record.target_path.to_string_lossy()),
+                        escape_markup(&record.date_directory),
+                        escape_markup(&record.file_name),
+                        record.file_size_bytes,
+                        escape_markup(&record.file_extension),
+                        optional_to_string(record.width),
+                        optional_to_string(record.height),
+                        escape_markup(record.exif_datetime.as_deref().unwrap_or_default())
+                    )?;
+                }
+
+                writeln!(writer, "  </organize>")?;
+            }
+            ExportDataType::Duplicates {
+                file_records,
+                similarity_threshold,
+            } => {
+                writeln!(
+                    writer,
+                    "  <duplicates similarity_threshold=\"{:.4}\">",
+                    similarity_threshold
+                )?;
+
+                for record in file_records {
+                    writeln!(
+                        writer,
+                        "    <file group_id=\"{}\" file_path=\"{}\" position_in_group=\"{}\" group_size=\"{}\" similarity=\"{:.4}\" file_size_bytes=\"{}\" file_extension=\"{}\" content_hash=\"{}\" recommended_action=\"{}\"/>",
+                        escape_markup(&record.group_id),
+                        escape_markup(&record.file_path.to_string_lossy()),
+                        record.position_in_group,
+                        record.group_size,
+                        record.similarity,
+                        record.file_size_bytes,
+                        escape_markup(&record.file_extension),
+                        escape_markup(&record.content_hash),
+                        escape_markup(&record.recommended_action)
+                    )?;
+                }
+
+                writeln!(writer, "  </duplicates>")?;
+            }
+            ExportDataType::Stats {
+                extension_stats,
+                date_range,
+            } => {
+                match date_range {
+                    Some(range) => writeln!(
+                        writer,
+                        "  <stats earliest=\"{}\" latest=\"{}\">",
+                        escape_markup(&range.earliest),
+                        escape_markup(&range.latest)
+                    )?,
+                    None => writeln!(writer, "  <stats>")?,
+                }
+
+                for stats in extension_stats {
+                    writeln!(
+                        writer,
+                        "    <extension name=\"{}\" count=\"{}\" total_bytes=\"{}\"/>",
+                        escape_markup(&stats.extension),
+                        stats.count,
+                        stats.total_bytes
+                    )?;
+                }
+
+                writeln!(writer, "  </stats>")?;
+            }
+            ExportDataType::Rename {
+                file_records,
+                dry_run,
+            } => {
+                writeln!(writer, "  <rename dry_run=\"{}\">", dry_run)?;
+
+                for record in file_records {
+                    writeln!(
+                        writer,
+                        "    <file original_path=\"{}\" new_path=\"{}\" file_size_bytes=\"{}\" file_extension=\"{}\"/>",
+                        escape_markup(&record.original_path.to_string_lossy()),
+                        escape_markup(&record.new_path.to_string_lossy()),
+                        record.file_size_bytes,
+                        escape_markup(&record.file_extension)
+                    )?;
+                }
+
+                writeln!(writer, "  </rename>")?;
+            }
+            ExportDataType::Exif { tags, file_records } => {
+                writeln!(writer, "  <exif>")?;
+
+                for record in file_records {
+                    writeln!(
+                        writer,
+                        "    <file file_path=\"{}\">",
+                        escape_markup(&record.file_path.to_string_lossy())
+                    )?;
+                    for tag in tags {
+                        writeln!(
+                            writer,
+                            "      <tag name=\"{}\">{}</tag>",
+                            escape_markup(tag),
+                            escape_markup(exif_tag_value(record, tag))
+                        )?;
+                    }
+                    writeln!(writer, "    </file>")?;
+                }
+
+                writeln!(writer, "  </exif>")?;
+            }
+        }
+
+        writeln!(writer, "</export>")?;
+
+        Ok(())
+    }
+}
+
+impl XmlExporter {
+    fn write_metadata(&self, writer: &mut dyn Write, metadata: &ExportMetadata) -> Result<()> {
+        writeln!(writer, "  <metadata>")?;
+        writeln!(
+            writer,
+            "    <timestamp>{}</timestamp>",
+            metadata.timestamp.to_rfc3339()
+        )?;
+        writeln!(
+            writer,
+            "    <command>{}</command>",
+            escape_markup(&metadata.command)
+        )?;
+        writeln!(
+            writer,
+            "    <version>{}</version>",
+            escape_markup(&metadata.version)
+        )?;
+        writeln!(
+            writer,
+            "    <source_directory>{}</source_directory>",
+            escape_markup(&metadata.source_directory.to_string_lossy())
+        )?;
+        writeln!(
+            writer,
+            "    <total_processed>{}</total_processed>",
+            metadata.total_processed
+        )?;
+
+        let mut keys: Vec<&String> = metadata.command_metadata.keys().collect();
+        keys.sort();
+
+        writeln!(writer, "    <command_metadata>")?;
+        for key in keys {
+            let value = &metadata.command_metadata[key];
+            let value_str = match value.as_str() {
+                Some(s) => s.to_string(),
+                None => value.to_string(),
+            };
+            writeln!(
+                writer,
+                "      <entry key=\"{}\">{}</entry>",
+                escape_markup(key),
+                escape_markup(&value_str)
+            )?;
+        }
+        writeln!(writer, "    </command_metadata>")?;
+
+        let summary = &metadata.summary;
+        writeln!(
+            writer,
+            "    <summary total_files=\"{}\" total_bytes=\"{}\">",
+            summary.total_files, summary.total_bytes
+        )?;
+        if let Some(groups) = summary.duplicate_groups {
+            writeln!(
+                writer,
+                "      <duplicate_groups>{}</duplicate_groups>",
+                groups
+            )?;
+        }
+        if let Some(bytes) = summary.reclaimable_bytes {
+            writeln!(
+                writer,
+                "      <reclaimable_bytes>{}</reclaimable_bytes>",
+                bytes
+            )?;
+        }
+        for (extension, count) in &summary.extension_counts {
+            writeln!(
+                writer,
+                "      <extension name=\"{}\" count=\"{}\"/>",
+                escape_markup(extension),
+                count
+            )?;
+        }
+        writeln!(writer, "    </summary>")?;
+        writeln!(writer, "  </metadata>")?;
+
+        Ok(())
+    }
+}
+
+fn format_extension_counts(summary: &ExportSummary) -> String {
+    summary
+        .extension_counts
+        .iter()
+        .map(|(extension, count)| format!("{}: {}", extension, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn exif_tag_value<'a>(record: &'a crate::export::data::ExifFileRecord, tag: &str) -> &'a str {
+    record
+        .tags
+        .get(tag)
+        .and_then(|value| value.as_deref())
+        .unwrap_or_default()
+}
+
+fn optional_to_string<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn escape_markup(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub struct HtmlExporter;
+
+const HTML_STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.4em; }
+dl { display: grid; grid-template-columns: max-content auto; gap: 0.25em 1em; }
+dt { font-weight: bold; }
+table { border-collapse: collapse; width: 100%; font-size: 0.9em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }
+th { background: #eee; cursor: pointer; user-select: none; }
+tr.group-even td { background: #f7f9fc; }
+tr.group-odd td { background: #fff; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }";
+
+const HTML_SORT_SCRIPT: &str = "document.querySelectorAll('th').forEach(function (th, index) {
+  th.addEventListener('click', function () {
+    var tbody = th.closest('table').querySelector('tbody');
+    var ascending = th.dataset.order !== 'asc';
+    th.dataset.order = ascending ? 'asc' : 'desc';
+    var rows = Array.prototype.slice.call(tbody.rows);
+    rows.sort(function (a, b) {
+      var x = a.cells[index].textContent;
+      var y = b.cells[index].textContent;
+      var cmp = (!isNaN(x) && !isNaN(y)) ? x - y : x.localeCompare(y);
+      return ascending ? cmp : -cmp;
+    });
+    rows.forEach(function (row) { tbody.appendChild(row); });
+  });
+});";
+
+impl Exporter for HtmlExporter {
+    fn export(&self, data: &ExportData, writer: &mut dyn Write) -> Result<()> {
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html lang=\"en\">")?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, "<meta charset=\"utf-8\">")?;
+        writeln!(
+            writer,
+            "<title>image-manager-cli {} report</title>",
+            escape_markup(&data.metadata.command)
+        )?;
+        writeln!(writer, "<style>{}</style>", HTML_STYLE)?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+
+        self.write_metadata(writer, &data.metadata)?;
+
+        match &data.data {
+            ExportDataType::Organize { file_records, .. } => {
+                self.write_organize_table(writer, file_records)?;
+            }
+            ExportDataType::Duplicates { file_records, .. } => {
+                self.write_duplicates_table(writer, file_records)?;
+            }
+            ExportDataType::Stats {
+                extension_stats,
+                date_range,
+            } => {
+                self.write_stats_table(writer, extension_stats, date_range.as_ref())?;
+            }
+            ExportDataType::Rename { file_records, .. } => {
+                self.write_rename_table(writer, file_records)?;
+            }
+            ExportDataType::Exif { tags, file_records } => {
+                self.write_exif_table(writer, tags, file_records)?;
+            }
+        }
+
+        writeln!(writer, "<script>{}</script>", HTML_SORT_SCRIPT)?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+
+        Ok(())
+    }
+}
+
+impl HtmlExporter {
+    fn write_metadata(&self, writer: &mut dyn Write, metadata: &ExportMetadata) -> Result<()> {
+        writeln!(
+            writer,
+            "<h1>{} report</h1>",
+            escape_markup(&metadata.command)
+        )?;
+        writeln!(writer, "<dl>")?;
+        writeln!(
+            writer,
+            "<dt>Command</dt><dd>{}</dd>",
+            escape_markup(&metadata.command)
+        )?;
+        writeln!(
+            writer,
+            "<dt>Timestamp</dt><dd>{}</dd>",
+            metadata.timestamp.to_rfc3339()
+        )?;
+        writeln!(
+            writer,
+            "<dt>Source directory</dt><dd>{}</dd>",
+            escape_markup(&metadata.source_directory.to_string_lossy())
+        )?;
+        writeln!(
+            writer,
+            "<dt>Total processed</dt><dd>{}</dd>",
+            metadata.total_processed
+        )?;
+        writeln!(
+            writer,
+            "<dt>Total size (bytes)</dt><dd>{}</dd>",
+            metadata.summary.total_bytes
+        )?;
+        writeln!(
+            writer,
+            "<dt>Extensions</dt><dd>{}</dd>",
+            escape_markup(&format_extension_counts(&metadata.summary))
+        )?;
+        if let Some(groups) = metadata.summary.duplicate_groups {
+            writeln!(writer, "<dt>Duplicate groups</dt><dd>{}</dd>", groups)?;
+        }
+        if let Some(bytes) = metadata.summary.reclaimable_bytes {
+            writeln!(writer, "<dt>Reclaimable (bytes)</dt><dd>{}</dd>", bytes)?;
+        }
+        writeln!(writer, "</dl>")?;
+
+        Ok(())
+    }
+
+    fn write_organize_table(
+        &self,
+        writer: &mut dyn Write,
+        file_records: &[crate::export::data::OrganizeFileRecord],
+    ) -> Result<()> {
+        writeln!(writer, "<table>")?;
         writeln!(
-            file,
-            "Original Path,Target Path,Date Directory,File Name,File Size (bytes),File Extension"
+            writer,
+            "<thead><tr><th>Original Path</th><th>Target Path</th><th>Date Directory</th><th>File Name</th><th>File Size (bytes)</th><th>File Extension</th><th>Width</th><th>Height</th><th>EXIF DateTime</th></tr></thead>"
         )?;
+        writeln!(writer, "<tbody>")?;
 
         for record in file_records {
             writeln!(
-                file,
-                "\"{}\",\"{}\",\"{}\",\"{}\",{},\"{}\"",
-                record.original_path.display(),
-                record.target_path.display(),
-                record.date_directory,
-                record.file_name,
+                writer,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+                escape_markup(&record.original_path.to_string_lossy()),
+                escape_markup(&record.target_path.to_string_lossy()),
+                escape_markup(&record.date_directory),
+                escape_markup(&record.file_name),
                 record.file_size_bytes,
-                record.file_extension
+                escape_markup(&record.file_extension),
+                optional_to_string(record.width),
+                optional_to_string(record.height),
+                escape_markup(record.exif_datetime.as_deref().unwrap_or_default())
             )?;
         }
 
+        writeln!(writer, "</tbody>")?;
+        writeln!(writer, "</table>")?;
+
         Ok(())
     }
 
-    fn export_duplicates_csv(
+    fn write_duplicates_table(
         &self,
-        file: &mut File,
+        writer: &mut dyn Write,
         file_records: &[crate::export::data::DuplicateFileRecord],
-        _similarity_threshold: f32,
     ) -> Result<()> {
-        writeln!(file, "Group ID,File Path,Position in Group,Group Size,Similarity,File Size (bytes),File Extension")?;
+        writeln!(writer, "<table>")?;
+        writeln!(
+            writer,
+            "<thead><tr><th>Group ID</th><th>File Path</th><th>Position in Group</th><th>Group Size</th><th>Similarity</th><th>File Size (bytes)</th><th>File Extension</th><th>Content Hash</th><th>Recommended Action</th></tr></thead>"
+        )?;
+        writeln!(writer, "<tbody>")?;
+
+        let mut group_index = 0;
+        let mut current_group: Option<&str> = None;
 
         for record in file_records {
+            if current_group != Some(record.group_id.as_str()) {
+                if current_group.is_some() {
+                    group_index += 1;
+                }
+                current_group = Some(record.group_id.as_str());
+            }
+            let row_class = if group_index % 2 == 0 {
+                "group-even"
+            } else {
+                "group-odd"
+            };
+
             writeln!(
-                file,
-                "\"{}\",\"{}\",{},{},{:.4},{},\"{}\"",
-                record.group_id,
-                record.file_path.display(),
+                writer,
+                "<tr class=\"{}\"><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{:.4}</td><td class=\"num\">{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                row_class,
+                escape_markup(&record.group_id),
+                escape_markup(&record.file_path.to_string_lossy()),
                 record.position_in_group,
                 record.group_size,
                 record.similarity,
                 record.file_size_bytes,
-                record.file_extension
+                escape_markup(&record.file_extension),
+                escape_markup(&record.content_hash),
+                escape_markup(&record.recommended_action)
+            )?;
+        }
+
+        writeln!(writer, "</tbody>")?;
+        writeln!(writer, "</table>")?;
+
+        Ok(())
+    }
+
+    fn write_stats_table(
+        &self,
+        writer: &mut dyn Write,
+        extension_stats: &[crate::export::data::ExtensionStats],
+        date_range: Option<&crate::export::data::DateRange>,
+    ) -> Result<()> {
+        if let Some(range) = date_range {
+            writeln!(
+                writer,
+                "<p>Dates: {} to {}</p>",
+                escape_markup(&range.earliest),
+                escape_markup(&range.latest)
+            )?;
+        }
+
+        writeln!(writer, "<table>")?;
+        writeln!(
+            writer,
+            "<thead><tr><th>File Extension</th><th>Count</th><th>Total Size (bytes)</th></tr></thead>"
+        )?;
+        writeln!(writer, "<tbody>")?;
+
+        for stats in extension_stats {
+            writeln!(
+                writer,
+                "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+                escape_markup(&stats.extension),
+                stats.count,
+                stats.total_bytes
+            )?;
+        }
+
+        writeln!(writer, "</tbody>")?;
+        writeln!(writer, "</table>")?;
+
+        Ok(())
+    }
+
+    fn write_rename_table(
+        &self,
+        writer: &mut dyn Write,
+        file_records: &[crate::export::data::RenameFileRecord],
+    ) -> Result<()> {
+        writeln!(writer, "<table>")?;
+        writeln!(
+            writer,
+            "<thead><tr><th>Original Path</th><th>New Path</th><th>File Size (bytes)</th><th>File Extension</th></tr></thead>"
+        )?;
+        writeln!(writer, "<tbody>")?;
+
+        for record in file_records {
+            writeln!(
+                writer,
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+                escape_markup(&record.original_path.to_string_lossy()),
+                escape_markup(&record.new_path.to_string_lossy()),
+                record.file_size_bytes,
+                escape_markup(&record.file_extension)
+            )?;
+        }
+
+        writeln!(writer, "</tbody>")?;
+        writeln!(writer, "</table>")?;
+
+        Ok(())
+    }
+
+    fn write_exif_table(
+        &self,
+        writer: &mut dyn Write,
+        tags: &[String],
+        file_records: &[crate::export::data::ExifFileRecord],
+    ) -> Result<()> {
+        let header: String = tags
+            .iter()
+            .map(|tag| format!("<th>{}</th>", escape_markup(tag)))
+            .collect();
+
+        writeln!(writer, "<table>")?;
+        writeln!(
+            writer,
+            "<thead><tr><th>File Path</th>{}</tr></thead>",
+            header
+        )?;
+        writeln!(writer, "<tbody>")?;
+
+        for record in file_records {
+            let cells: String = tags
+                .iter()
+                .map(|tag| format!("<td>{}</td>", escape_markup(exif_tag_value(record, tag))))
+                .collect();
+            writeln!(
+                writer,
+                "<tr><td>{}</td>{}</tr>",
+                escape_markup(&record.file_path.to_string_lossy()),
+                cells
             )?;
         }
 
+        writeln!(writer, "</tbody>")?;
+        writeln!(writer, "</table>")?;
+
         Ok(())
     }
 }
 
-pub struct JsonExporter;
+pub struct MarkdownExporter;
 
-impl Exporter for JsonExporter {
-    fn export(&self, data: &ExportData, path: &Path) -> Result<()> {
-        let json_string = serde_json::to_string_pretty(data)
-            .with_context(|| "Failed to serialize data to JSON")?;
+impl Exporter for MarkdownExporter {
+    fn export(&self, data: &ExportData, writer: &mut dyn Write) -> Result<()> {
+        self.write_metadata(writer, &data.metadata)?;
+
+        match &data.data {
+            ExportDataType::Organize { file_records, .. } => {
+                writeln!(
+                    writer,
+                    "| Original Path | Target Path | Date Directory | File Name | File Size (bytes) | File Extension | Width | Height | EXIF DateTime |"
+                )?;
+                writeln!(
+                    writer,
+                    "| --- | --- | --- | --- | ---: | --- | ---: | ---: | --- |"
+                )?;
+
+                for record in file_records {
+                    writeln!(
+                        writer,
+                        "| {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+                        escape_markdown_cell(&record.original_path.to_string_lossy()),
+                        escape_markdown_cell(&record.target_path.to_string_lossy()),
+                        escape_markdown_cell(&record.date_directory),
+                        escape_markdown_cell(&record.file_name),
+                        record.file_size_bytes,
+                        escape_markdown_cell(&record.file_extension),
+                        optional_to_string(record.width),
+                        optional_to_string(record.height),
+                        escape_markdown_cell(record.exif_datetime.as_deref().unwrap_or_default())
+                    )?;
+                }
+            }
+            ExportDataType::Duplicates { file_records, .. } => {
+                writeln!(
+                    writer,
+                    "| Group ID | File Path | Position in Group | Group Size | Similarity | File Size (bytes) | File Extension | Content Hash | Recommended Action |"
+                )?;
+                writeln!(
+                    writer,
+                    "| --- | --- | ---: | ---: | ---: | ---: | --- | --- | --- |"
+                )?;
+
+                for record in file_records {
+                    writeln!(
+                        writer,
+                        "| {} | {} | {} | {} | {:.4} | {} | {} | {} | {} |",
+                        escape_markdown_cell(&record.group_id),
+                        escape_markdown_cell(&record.file_path.to_string_lossy()),
+                        record.position_in_group,
+                        record.group_size,
+                        record.similarity,
+                        record.file_size_bytes,
+                        escape_markdown_cell(&record.file_extension),
+                        escape_markdown_cell(&record.content_hash),
+                        escape_markdown_cell(&record.recommended_action)
+                    )?;
+                }
+            }
+            ExportDataType::Stats {
+                extension_stats,
+                date_range,
+            } => {
+                if let Some(range) = date_range {
+                    writeln!(writer, "Dates: {} to {}", range.earliest, range.latest)?;
+                    writeln!(writer)?;
+                }
+
+                writeln!(writer, "| File Extension | Count | Total Size (bytes) |")?;
+                writeln!(writer, "| --- | ---: | ---: |")?;
 
-        std::fs::write(path, json_string)
-            .with_context(|| format!("Failed to write JSON file: {}", path.display()))?;
+                for stats in extension_stats {
+                    writeln!(
+                        writer,
+                        "| {} | {} | {} |",
+                        escape_markdown_cell(&stats.extension),
+                        stats.count,
+                        stats.total_bytes
+                    )?;
+                }
+            }
+            ExportDataType::Rename { file_records, .. } => {
+                writeln!(
+                    writer,
+                    "| Original Path | New Path | File Size (bytes) | File Extension |"
+                )?;
+                writeln!(writer, "| --- | --- | ---: | --- |")?;
+
+                for record in file_records {
+                    writeln!(
+                        writer,
+                        "| {} | {} | {} | {} |",
+                        escape_markdown_cell(&record.original_path.to_string_lossy()),
+                        escape_markdown_cell(&record.new_path.to_string_lossy()),
+                        record.file_size_bytes,
+                        escape_markdown_cell(&record.file_extension)
+                    )?;
+                }
+            }
+            ExportDataType::Exif { tags, file_records } => {
+                let header: Vec<String> =
+                    tags.iter().map(|tag| escape_markdown_cell(tag)).collect();
+                writeln!(writer, "| File Path | {} |", header.join(" | "))?;
+                writeln!(writer, "| --- |{}", " --- |".repeat(tags.len()))?;
+
+                for record in file_records {
+                    let cells: Vec<String> = tags
+                        .iter()
+                        .map(|tag| escape_markdown_cell(exif_tag_value(record, tag)))
+                        .collect();
+                    writeln!(
+                        writer,
+                        "| {} | {} |",
+                        escape_markdown_cell(&record.file_path.to_string_lossy()),
+                        cells.join(" | ")
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl MarkdownExporter {
+    fn write_metadata(&self, writer: &mut dyn Write, metadata: &ExportMetadata) -> Result<()> {
+        writeln!(writer, "## image-manager-cli {} report", metadata.command)?;
+        writeln!(writer)?;
+        writeln!(writer, "- **Command:** {}", metadata.command)?;
+        writeln!(
+            writer,
+            "- **Timestamp:** {}",
+            metadata.timestamp.to_rfc3339()
+        )?;
+        writeln!(
+            writer,
+            "- **Source directory:** `{}`",
+            metadata.source_directory.display()
+        )?;
+        writeln!(
+            writer,
+            "- **Total processed:** {}",
+            metadata.total_processed
+        )?;
+        writeln!(
+            writer,
+            "- **Total size (bytes):** {}",
+            metadata.summary.total_bytes
+        )?;
+        writeln!(
+            writer,
+            "- **Extensions:** {}",
+            format_extension_counts(&metadata.summary)
+        )?;
+        if let Some(groups) = metadata.summary.duplicate_groups {
+            writeln!(writer, "- **Duplicate groups:** {}", groups)?;
+        }
+        if let Some(bytes) = metadata.summary.reclaimable_bytes {
+            writeln!(writer, "- **Reclaimable (bytes):** {}", bytes)?;
+        }
+        writeln!(writer)?;
 
         Ok(())
     }
 }
+
+fn escape_markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{ExifTag, KeepStrategy};
+    use crate::export::data::{DuplicateGroup, TargetConfig};
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    fn organize_data(files: Vec<PathBuf>) -> ExportData {
+        let mut organized = HashMap::new();
+        organized.insert("2023-01-15".to_string(), files);
+        ExportData::organize(
+            organized,
+            TargetConfig::default(),
+            PathBuf::from("/photos"),
+            1,
+        )
+    }
+
+    #[test]
+    fn test_csv_export_escapes_quotes_and_commas() {
+        let tricky_path = PathBuf::from("/photos/my \"best\", photo.jpg");
+        let data = organize_data(vec![tricky_path.clone()]);
+
+        let mut buffer = Vec::new();
+        CsvExporter::default().export(&data, &mut buffer).unwrap();
+
+        let mut reader = csv::Reader::from_reader(buffer.as_slice());
+        let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+        assert_eq!(records.len(), 1);
+        assert_eq!(&records[0][0], tricky_path.to_str().unwrap());
+        assert_eq!(&records[0][3], "my \"best\", photo.jpg");
+    }
+
+    #[test]
+    fn test_csv_export_duplicates_round_trip() {
+        let files = vec![
+            PathBuf::from("/photos/a,\"1\".jpg"),
+            PathBuf::from("/photos/b.jpg"),
+        ];
+
+        let data = ExportData::duplicates(
+            vec![DuplicateGroup {
+                group_id: "group_1".to_string(),
+                files: files.clone(),
+                similarity: 0.9,
+            }],
+            0.9,
+            PathBuf::from("/photos"),
+            2,
+            false,
+            KeepStrategy::First,
+        );
+
+        let mut buffer = Vec::new();
+        CsvExporter::default().export(&data, &mut buffer).unwrap();
+
+        let mut reader = csv::Reader::from_reader(buffer.as_slice());
+        let paths: Vec<String> = reader
+            .records()
+            .map(|r| r.unwrap()[1].to_string())
+            .collect();
+        assert_eq!(paths, vec!["/photos/a,\"1\".jpg", "/photos/b.jpg"]);
+
+        let mut reader = csv::Reader::from_reader(buffer.as_slice());
+        let actions: Vec<String> = reader
+            .records()
+            .map(|r| r.unwrap()[8].to_string())
+            .collect();
+        assert_eq!(actions, vec!["keep", "remove"]);
+    }
+
+    #[test]
+    fn test_json_export_large_dataset_is_valid() {
+        let groups: Vec<DuplicateGroup> = (0..2500)
+            .map(|index| DuplicateGroup {
+                group_id: format!("group_{}", index + 1),
+                files: vec![
+                    PathBuf::from(format!("/photos/{}_a.jpg", index)),
+                    PathBuf::from(format!("/photos/{}_b.jpg", index)),
+                ],
+                similarity: 0.95,
+            })
+            .collect();
+        let data = ExportData::duplicates(
+            groups,
+            0.95,
+            PathBuf::from("/photos"),
+            5000,
+            false,
+            KeepStrategy::First,
+        );
+
+        let mut buffer = Vec::new();
+        JsonExporter::default().export(&data, &mut buffer).unwrap();
+
+        let content = String::from_utf8(buffer).unwrap();
+        assert_eq!(content, serde_json::to_string_pretty(&data).unwrap());
+
+        let parsed: ExportData = serde_json::from_str(&content).unwrap();
+        match parsed.data {
+            ExportDataType::Duplicates { file_records, .. } => {
+                assert_eq!(file_records.len(), 5000)
+            }
+            _ => panic!("expected duplicates export data"),
+        }
+    }
+
+    #[test]
+    fn test_json_export_compact() {
+        let data = organize_data(vec![PathBuf::from("/photos/a.jpg")]);
+
+        let mut buffer = Vec::new();
+        JsonExporter { compact: true }
+            .export(&data, &mut buffer)
+            .unwrap();
+
+        let content = String::from_utf8(buffer).unwrap();
+        assert!(!content.contains('\n'));
+        assert_eq!(content, serde_json::to_string(&data).unwrap());
+    }
+
+    #[test]
+    fn test_ndjson_export_one_record_per_line() {
+        let data = organize_data(vec![
+            PathBuf::from("/photos/a.jpg"),
+            PathBuf::from("/photos/b.jpg"),
+        ]);
+
+        let mut buffer = Vec::new();
+        NdjsonExporter.export(&data, &mut buffer).unwrap();
+
+        let content = String::from_utf8(buffer).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["type"], "metadata");
+        assert_eq!(lines[0]["command"], "organize");
+        assert_eq!(lines[1]["original_path"], "/photos/a.jpg");
+    }
+
+    #[test]
+    fn test_csv_export_custom_delimiter() {
+        let tricky_path = PathBuf::from("/photos/a;b.jpg");
+        let data = organize_data(vec![tricky_path.clone()]);
+
+        let mut buffer = Vec::new();
+        CsvExporter {
+            delimiter: b';',
+            ..Default::default()
+        }
+        .export(&data, &mut buffer)
+        .unwrap();
+
+        let content = String::from_utf8(buffer.clone()).unwrap();
+        assert!(content.starts_with("Original Path;Target Path;"));
+
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(b';')
+            .from_reader(buffer.as_slice());
+        let record = reader.records().next().unwrap().unwrap();
+        assert_eq!(&record[0], tricky_path.to_str().unwrap());
+    }
+
+    #[test]
+    fn test_csv_export_exif_columns_follow_tags() {
+        let data = ExportData::exif(
+            &[PathBuf::from("/photos/missing.jpg")],
+            &[ExifTag::Model, ExifTag::Date],
+            PathBuf::from("/photos"),
+        );
+
+        let mut buffer = Vec::new();
+        CsvExporter::default().export(&data, &mut buffer).unwrap();
+
+        let content = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines, vec!["File Path,model,date", "/photos/missing.jpg,,"]);
+    }
+
+    /// Checks that every element is closed in order and that attribute
+    /// values contain no raw markup characters.
+    fn assert_well_formed_xml(xml: &str) {
+        let mut open = Vec::new();
+        let mut rest = xml;
+        while let Some(start) = rest.find('<') {
+            let end = rest[start..].find('>').unwrap() + start;
+            let tag = &rest[start + 1..end];
+            assert!(!tag.contains('<'), "unescaped '<' in {}", tag);
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop(), Some(name.to_string()));
+            } else if !tag.starts_with('?') && !tag.ends_with('/') {
+                open.push(tag.split_whitespace().next().unwrap().to_string());
+            }
+            assert_eq!(
+                tag.matches('"').count() % 2,
+                0,
+                "unbalanced quotes in {}",
+                tag
+            );
+            for (i, _) in tag.match_indices('&') {
+                let entity = &tag[i..tag[i..].find(';').unwrap() + i + 1];
+                assert!(
+                    ["&amp;", "&lt;", "&gt;", "&quot;", "&apos;"].contains(&entity),
+                    "unknown entity {}",
+                    entity
+                );
+            }
+            rest = &rest[end + 1..];
+        }
+        assert!(open.is_empty(), "unclosed elements: {:?}", open);
+    }
+
+    fn xml_attribute(xml: &str, name: &str) -> String {
+        let start = xml.find(&format!(" {}=\"", name)).unwrap() + name.len() + 3;
+        let end = xml[start..].find('"').unwrap() + start;
+        xml[start..end]
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+    }
+
+    #[test]
+    fn test_xml_export_escapes_markup() {
+        let tricky_path = PathBuf::from("/photos/Tom & \"Jerry\" <1>.jpg");
+        let data = organize_data(vec![tricky_path.clone()]);
+
+        let mut buffer = Vec::new();
+        XmlExporter.export(&data, &mut buffer).unwrap();
+        let xml = String::from_utf8(buffer).unwrap();
+
+        assert_well_formed_xml(&xml);
+        assert_eq!(
+            xml_attribute(&xml, "original_path"),
+            tricky_path.to_str().unwrap()
+        );
+        assert_eq!(xml_attribute(&xml, "file_name"), "Tom & \"Jerry\" <1>.jpg");
+    }
+
+    #[test]
+    fn test_markdown_export_escapes_cells() {
+        let tricky_path = PathBuf::from("/photos/left|right\nnext.jpg");
+        let data = organize_data(vec![tricky_path]);
+
+        let mut buffer = Vec::new();
+        MarkdownExporter.export(&data, &mut buffer).unwrap();
+        let markdown = String::from_utf8(buffer).unwrap();
+
+        let table: Vec<&str> = markdown
+            .lines()
+            .skip_while(|line| !line.starts_with('|'))
+            .collect();
+        assert_eq!(
+            table[0],
+            "| Original Path | Target Path | Date Directory | File Name | File Size (bytes) | File Extension | Width | Height | EXIF DateTime |"
+        );
+        assert_eq!(
+            table[1],
+            "| --- | --- | --- | --- | ---: | --- | ---: | ---: | --- |"
+        );
+        assert_eq!(table.len(), 3, "the newline must not split the row");
+
+        let row = table[2];
+        assert!(row.contains("/photos/left\\|right next.jpg"));
+        let unescaped_pipes = row.replace("\\|", "").matches('|').count();
+        assert_eq!(unescaped_pipes, 10);
+    }
+
+    #[test]
+    fn test_table_exports_show_duplicate_columns() {
+        let data = ExportData::duplicates(
+            vec![DuplicateGroup {
+                group_id: "group_1".to_string(),
+                files: vec![
+                    PathBuf::from("/photos/a.jpg"),
+                    PathBuf::from("/photos/b.jpg"),
+                ],
+                similarity: 1.0,
+            }],
+            1.0,
+            PathBuf::from("/photos"),
+            2,
+            false,
+            KeepStrategy::First,
+        );
+
+        let mut buffer = Vec::new();
+        HtmlExporter.export(&data, &mut buffer).unwrap();
+        let html = String::from_utf8(buffer).unwrap();
+        assert!(html.contains("<th>Content Hash</th><th>Recommended Action</th>"));
+        assert!(html.contains("<td>keep</td>") && html.contains("<td>remove</td>"));
+
+        let mut buffer = Vec::new();
+        MarkdownExporter.export(&data, &mut buffer).unwrap();
+        let markdown = String::from_utf8(buffer).unwrap();
+        assert!(markdown.contains("| Content Hash | Recommended Action |"));
+        assert!(markdown.contains("| keep |") && markdown.contains("| remove |"));
+    }
+}
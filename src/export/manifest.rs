@@ -0,0 +1,51 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::utils::hash_utils;
+
+/// Writes `<export>.sha256` next to the export in the `<hash>  <filename>`
+/// layout understood by `sha256sum -c`.
+pub fn write_checksum_manifest(export_path: &Path) -> Result<PathBuf> {
+    let hash = hash_utils::sha256_file(export_path)
+        .with_context(|| format!("Failed to hash export file: {}", export_path.display()))?;
+
+    let file_name = export_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .with_context(|| format!("Export path has no file name: {}", export_path.display()))?;
+
+    let mut manifest_path = export_path.as_os_str().to_owned();
+    manifest_path.push(".sha256");
+    let manifest_path = PathBuf::from(manifest_path);
+
+    std::fs::write(&manifest_path, format!("{}  {}\n", hash, file_name)).with_context(|| {
+        format!(
+            "Failed to write checksum manifest: {}",
+            manifest_path.display()
+        )
+    })?;
+
+    Ok(manifest_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_checksum_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let export_path = temp_dir.path().join("results.json");
+        fs::write(&export_path, "abc").unwrap();
+
+        let manifest_path = write_checksum_manifest(&export_path).unwrap();
+
+        assert_eq!(manifest_path, temp_dir.path().join("results.json.sha256"));
+        assert_eq!(
+            fs::read_to_string(&manifest_path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  results.json\n"
+        );
+    }
+}
@@ -1,5 +1,6 @@
 pub mod data;
 pub mod formats;
+pub mod manifest;
 /// File export module for saving results to files
 ///
 /// This module handles all file export functionality, separating it from
@@ -8,7 +9,13 @@ pub mod formats;
 /// - `trait_impl`: Export trait and format enum for unified export interface
 /// - `formats`: Concrete implementations for CSV and JSON exporters
 /// - `data`: Data structures specifically for serialization and export
+/// - `manifest`: SHA-256 checksum manifests written beside exports
+/// - `report`: Writes `--export` and reports where it went
+/// - `verify`: Checks that an organize export landed in its target tree
+pub mod report;
 pub mod trait_impl;
+pub mod verify;
 
 pub use data::ExportData;
-pub use trait_impl::{export_data, ExportFormat};
+pub use report::{export_and_report, report_export};
+pub use trait_impl::{export_data, is_stdout_path, ExportFormat, ExportOptions};
@@ -0,0 +1,41 @@
+use anyhow::Result;
+use console::style;
+use std::path::Path;
+
+use super::manifest::write_checksum_manifest;
+use super::{export_data, is_stdout_path, ExportData};
+use crate::commands::ExportArgs;
+use crate::output::emoji::{emoji, DOCUMENT};
+use crate::status;
+
+/// Writes `data` to `--export` when one was given, reports where it went and
+/// adds the `--manifest` checksum file.
+pub fn export_and_report(data: &ExportData, export: &ExportArgs) -> Result<()> {
+    let Some(path) = &export.path else {
+        return Ok(());
+    };
+
+    let written_path = export_data(data, path, export.format, &export.options())?;
+    report_export(&written_path, export)
+}
+
+/// Reports an export that was written to `written_path` and writes its
+/// manifest when `--manifest` is set.
+pub fn report_export(written_path: &Path, export: &ExportArgs) -> Result<()> {
+    if !is_stdout_path(written_path) {
+        status!(
+            "\n{} {}",
+            style(emoji(DOCUMENT)).green(),
+            style("Export completed").green()
+        );
+        status!("   Format: {}", style(export.format.name()).cyan());
+        status!("   Location: {}", style(written_path.display()).cyan());
+    }
+
+    if export.manifest {
+        let manifest_path = write_checksum_manifest(written_path)?;
+        status!("   Manifest: {}", style(manifest_path.display()).cyan());
+    }
+
+    Ok(())
+}
@@ -1,24 +1,93 @@
 use anyhow::{Context, Result};
-use std::path::Path;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 
-use super::data::ExportData;
-use super::formats::{CsvExporter, JsonExporter};
+use super::data::{ExportData, ExportDataType};
+use super::formats::{
+    CsvExporter, GroupIndexExporter, HtmlExporter, JsonExporter, MarkdownExporter, NdjsonExporter,
+    XmlExporter, YamlExporter,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct ExportOptions {
+    pub csv_delimiter: u8,
+    pub csv_bom: bool,
+    pub compress: bool,
+    pub append: bool,
+    pub force_extension: bool,
+    pub json_compact: bool,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            csv_delimiter: b',',
+            csv_bom: false,
+            compress: false,
+            append: false,
+            force_extension: false,
+            json_compact: false,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Returns the path the export is actually written to, which gains a
+    /// `.gz` suffix when compression is enabled (stdout is left untouched).
+    pub fn output_path(&self, path: &Path) -> PathBuf {
+        if self.compress && !is_stdout_path(path) {
+            let mut compressed = path.as_os_str().to_owned();
+            compressed.push(".gz");
+            PathBuf::from(compressed)
+        } else {
+            path.to_path_buf()
+        }
+    }
+}
+
+/// Returns true when the export path is `-`, meaning the export goes to stdout.
+pub fn is_stdout_path(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
 
 pub trait Exporter {
-    fn export(&self, data: &ExportData, path: &Path) -> Result<()>;
+    fn export(&self, data: &ExportData, writer: &mut dyn Write) -> Result<()>;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum ExportFormat {
     Csv,
     Json,
+    Ndjson,
+    Yaml,
+    Xml,
+    Html,
+    Markdown,
 }
 
 impl ExportFormat {
-    pub fn create_exporter(self) -> Box<dyn Exporter> {
+    pub fn create_exporter(
+        self,
+        options: &ExportOptions,
+        include_header: bool,
+    ) -> Box<dyn Exporter> {
         match self {
-            ExportFormat::Csv => Box::new(CsvExporter),
-            ExportFormat::Json => Box::new(JsonExporter),
+            ExportFormat::Csv => Box::new(CsvExporter {
+                delimiter: options.csv_delimiter,
+                include_header,
+                bom: options.csv_bom,
+            }),
+            ExportFormat::Json => Box::new(JsonExporter {
+                compact: options.json_compact,
+            }),
+            ExportFormat::Ndjson => Box::new(NdjsonExporter),
+            ExportFormat::Yaml => Box::new(YamlExporter),
+            ExportFormat::Xml => Box::new(XmlExporter),
+            ExportFormat::Html => Box::new(HtmlExporter),
+            ExportFormat::Markdown => Box::new(MarkdownExporter),
         }
     }
 
@@ -26,18 +95,429 @@ impl ExportFormat {
         match self {
             ExportFormat::Csv => "CSV",
             ExportFormat::Json => "JSON",
+            ExportFormat::Ndjson => "NDJSON",
+            ExportFormat::Yaml => "YAML",
+            ExportFormat::Xml => "XML",
+            ExportFormat::Html => "HTML",
+            ExportFormat::Markdown => "Markdown",
+        }
+    }
+
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            ExportFormat::Csv => &["csv", "tsv", "txt"],
+            ExportFormat::Json => &["json"],
+            ExportFormat::Ndjson => &["ndjson", "jsonl"],
+            ExportFormat::Yaml => &["yaml", "yml"],
+            ExportFormat::Xml => &["xml"],
+            ExportFormat::Html => &["html", "htm"],
+            ExportFormat::Markdown => &["md", "markdown"],
         }
     }
 }
 
-pub fn export_data(data: &ExportData, path: &Path, format: ExportFormat) -> Result<()> {
-    let exporter = format.create_exporter();
+pub fn validate_export_extension(path: &Path, format: ExportFormat) -> Result<()> {
+    if is_stdout_path(path) {
+        return Ok(());
+    }
+
+    let mut checked_path = path;
+    if checked_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+    {
+        checked_path = Path::new(checked_path.file_stem().unwrap_or_default());
+    }
+
+    let Some(extension) = checked_path.extension().and_then(|ext| ext.to_str()) else {
+        return Ok(());
+    };
+
+    if format
+        .extensions()
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(extension))
+    {
+        return Ok(());
+    }
+
+    Err(anyhow::anyhow!(
+        "Export path {} has a .{} extension but the export format is {}; use a .{} file name or pass --force-extension",
+        path.display(),
+        extension,
+        format.name(),
+        format.extensions()[0]
+    ))
+}
 
-    exporter.export(data, path).with_context(|| {
+pub fn validate_export_options(
+    path: &Path,
+    format: ExportFormat,
+    options: &ExportOptions,
+) -> Result<()> {
+    if !options.force_extension {
+        validate_export_extension(path, format)?;
+    }
+
+    if options.append && format != ExportFormat::Csv {
+        return Err(anyhow::anyhow!(
+            "--export-append is only supported for CSV exports, not {}",
+            format.name()
+        ));
+    }
+
+    if options.append && options.compress {
+        return Err(anyhow::anyhow!(
+            "--export-append cannot be combined with --compress"
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn export_data(
+    data: &ExportData,
+    path: &Path,
+    format: ExportFormat,
+    options: &ExportOptions,
+) -> Result<PathBuf> {
+    validate_export_options(path, format, options)?;
+
+    let output_path = options.output_path(path);
+    let exporter = format.create_exporter(options, !appending_to_existing(&output_path, options));
+
+    write_export(exporter.as_ref(), data, &output_path, options).with_context(|| {
         format!(
             "Failed to export data to {} format: {}",
             format.name(),
-            path.display()
+            output_path.display()
+        )
+    })?;
+
+    Ok(output_path)
+}
+
+/// Whether `--export-append` adds rows to a non-empty file, which then
+/// already has its header.
+fn appending_to_existing(path: &Path, options: &ExportOptions) -> bool {
+    options.append
+        && !is_stdout_path(path)
+        && fs::metadata(path)
+            .map(|metadata| metadata.len() > 0)
+            .unwrap_or(false)
+}
+
+/// Directory `--split-groups` writes into: the export path without its
+/// extension (`dups.csv` becomes `dups/`).
+pub fn split_groups_directory(path: &Path) -> PathBuf {
+    path.with_extension("")
+}
+
+/// Writes each duplicate group to its own CSV file (`group_1.csv`, ...) plus
+/// an `index.csv` listing the groups, and returns the directory.
+pub fn export_split_groups(
+    data: &ExportData,
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<PathBuf> {
+    let ExportDataType::Duplicates {
+        file_records,
+        similarity_threshold,
+    } = &data.data
+    else {
+        return Err(anyhow::anyhow!(
+            "--split-groups only applies to duplicate exports"
+        ));
+    };
+
+    let directory = split_groups_directory(path);
+    fs::create_dir_all(&directory)
+        .with_context(|| format!("Failed to create export directory: {}", directory.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("csv");
+
+    // Records of a group are contiguous, in group order.
+    for group in file_records.chunk_by(|a, b| a.group_id == b.group_id) {
+        let group_data = ExportData {
+            metadata: data.metadata.clone(),
+            data: ExportDataType::Duplicates {
+                file_records: group.to_vec(),
+                similarity_threshold: *similarity_threshold,
+            },
+        };
+        let group_path =
+            options.output_path(&directory.join(format!("{}.{}", group[0].group_id, extension)));
+        let exporter = ExportFormat::Csv
+            .create_exporter(options, !appending_to_existing(&group_path, options));
+        write_export(exporter.as_ref(), &group_data, &group_path, options).with_context(|| {
+            format!(
+                "Failed to export duplicate group to {}",
+                group_path.display()
+            )
+        })?;
+    }
+
+    let index_path = options.output_path(&directory.join(format!("index.{}", extension)));
+    let index_exporter = GroupIndexExporter {
+        delimiter: options.csv_delimiter,
+        include_header: !appending_to_existing(&index_path, options),
+        bom: options.csv_bom,
+        extension: if options.compress {
+            format!("{}.gz", extension)
+        } else {
+            extension.to_string()
+        },
+    };
+    write_export(&index_exporter, data, &index_path, options)
+        .with_context(|| format!("Failed to write group index: {}", index_path.display()))?;
+
+    Ok(directory)
+}
+
+fn write_export(
+    exporter: &dyn Exporter,
+    data: &ExportData,
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<()> {
+    let destination: Box<dyn Write> = if is_stdout_path(path) {
+        Box::new(std::io::stdout().lock())
+    } else if options.append {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open export file: {}", path.display()))?;
+        Box::new(file)
+    } else {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create export file: {}", path.display()))?;
+        Box::new(file)
+    };
+    let mut writer = BufWriter::new(destination);
+
+    if options.compress {
+        let mut encoder = GzEncoder::new(&mut writer, Compression::default());
+        exporter.export(data, &mut encoder)?;
+        encoder.finish()?;
+    } else {
+        exporter.export(data, &mut writer)?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::data::TargetConfig;
+    use flate2::read::GzDecoder;
+    use std::collections::HashMap;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_stdout_path() {
+        assert!(is_stdout_path(Path::new("-")));
+        assert!(!is_stdout_path(Path::new("results.json")));
+        assert!(!is_stdout_path(Path::new("./-")));
+
+        let options = ExportOptions {
+            compress: true,
+            ..Default::default()
+        };
+        assert_eq!(options.output_path(Path::new("-")), PathBuf::from("-"));
+    }
+
+    fn sample_organize_data(file_name: &str) -> ExportData {
+        let mut organized = HashMap::new();
+        organized.insert(
+            "2023-01-15".to_string(),
+            vec![PathBuf::from("/photos").join(file_name)],
+        );
+        ExportData::organize(
+            organized,
+            TargetConfig::default(),
+            PathBuf::from("/photos"),
+            1,
         )
-    })
+    }
+
+    #[test]
+    fn test_append_csv_writes_header_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let export_path = temp_dir.path().join("master.csv");
+        let options = ExportOptions {
+            append: true,
+            ..Default::default()
+        };
+
+        export_data(
+            &sample_organize_data("a.jpg"),
+            &export_path,
+            ExportFormat::Csv,
+            &options,
+        )
+        .unwrap();
+        export_data(
+            &sample_organize_data("b.jpg"),
+            &export_path,
+            ExportFormat::Csv,
+            &options,
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(&export_path).unwrap();
+        assert_eq!(content.matches("Original Path").count(), 1);
+        assert_eq!(content.lines().count(), 3);
+    }
+
+    #[test]
+    fn test_append_rejected_for_non_csv() {
+        let options = ExportOptions {
+            append: true,
+            ..Default::default()
+        };
+
+        let path = Path::new("results.csv");
+        assert!(validate_export_options(path, ExportFormat::Csv, &options).is_ok());
+        assert!(validate_export_options(path, ExportFormat::Json, &options).is_err());
+    }
+
+    #[test]
+    fn test_validate_export_extension() {
+        assert!(validate_export_extension(Path::new("out.json"), ExportFormat::Csv).is_err());
+        assert!(validate_export_extension(Path::new("out.csv"), ExportFormat::Json).is_err());
+        assert!(validate_export_extension(Path::new("out.JSON"), ExportFormat::Json).is_ok());
+        assert!(validate_export_extension(Path::new("out.json.gz"), ExportFormat::Json).is_ok());
+        assert!(validate_export_extension(Path::new("out"), ExportFormat::Csv).is_ok());
+        assert!(validate_export_extension(Path::new("-"), ExportFormat::Csv).is_ok());
+
+        let forced = ExportOptions {
+            force_extension: true,
+            ..Default::default()
+        };
+        assert!(validate_export_options(Path::new("out.json"), ExportFormat::Csv, &forced).is_ok());
+    }
+
+    #[test]
+    fn test_export_split_groups() {
+        use crate::commands::KeepStrategy;
+        use crate::export::data::DuplicateGroup;
+
+        let temp_dir = TempDir::new().unwrap();
+        let group = |id: &str, files: &[&str]| DuplicateGroup {
+            group_id: id.to_string(),
+            files: files.iter().map(|f| temp_dir.path().join(f)).collect(),
+            similarity: 1.0,
+        };
+        let data = ExportData::duplicates(
+            vec![
+                group("group_1", &["a.jpg", "b.jpg"]),
+                group("group_2", &["c.jpg", "d.jpg", "e.jpg"]),
+            ],
+            1.0,
+            temp_dir.path().to_path_buf(),
+            5,
+            false,
+            KeepStrategy::First,
+        );
+
+        let export_path = temp_dir.path().join("dups.csv");
+        let directory =
+            export_split_groups(&data, &export_path, &ExportOptions::default()).unwrap();
+        assert_eq!(directory, temp_dir.path().join("dups"));
+
+        let first = std::fs::read_to_string(directory.join("group_1.csv")).unwrap();
+        let second = std::fs::read_to_string(directory.join("group_2.csv")).unwrap();
+        assert_eq!(first.lines().count(), 3);
+        assert_eq!(second.lines().count(), 4);
+        assert!(first.contains("a.jpg") && !first.contains("c.jpg"));
+
+        let index = std::fs::read_to_string(directory.join("index.csv")).unwrap();
+        let lines: Vec<&str> = index.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("group_1,group_1.csv,2,"));
+        assert!(lines[2].starts_with("group_2,group_2.csv,3,"));
+
+        let append = ExportOptions {
+            append: true,
+            ..Default::default()
+        };
+        export_split_groups(&data, &export_path, &append).unwrap();
+
+        let first = std::fs::read_to_string(directory.join("group_1.csv")).unwrap();
+        assert_eq!(first.lines().count(), 5);
+        assert_eq!(first.matches("Group ID").count(), 1);
+
+        let index = std::fs::read_to_string(directory.join("index.csv")).unwrap();
+        assert_eq!(index.lines().count(), 5);
+        assert_eq!(index.matches("Group ID").count(), 1);
+    }
+
+    #[test]
+    fn test_csv_bom() {
+        let temp_dir = TempDir::new().unwrap();
+        let export_path = temp_dir.path().join("results.csv");
+        let data = sample_organize_data("Über.jpg");
+
+        export_data(
+            &data,
+            &export_path,
+            ExportFormat::Csv,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+        let plain = std::fs::read(&export_path).unwrap();
+        assert!(plain.starts_with(b"Original Path"));
+
+        let options = ExportOptions {
+            csv_bom: true,
+            append: true,
+            ..Default::default()
+        };
+        std::fs::remove_file(&export_path).unwrap();
+        export_data(&data, &export_path, ExportFormat::Csv, &options).unwrap();
+        export_data(&data, &export_path, ExportFormat::Csv, &options).unwrap();
+
+        let content = std::fs::read_to_string(&export_path).unwrap();
+        assert!(content.starts_with("\u{FEFF}Original Path"));
+        assert_eq!(content.matches('\u{FEFF}').count(), 1);
+        assert!(content.contains("Über.jpg"));
+    }
+
+    #[test]
+    fn test_compressed_export_matches_uncompressed() {
+        let temp_dir = TempDir::new().unwrap();
+        let export_path = temp_dir.path().join("results.json");
+
+        let data = sample_organize_data("a.jpg");
+
+        let plain_path = export_data(
+            &data,
+            &export_path,
+            ExportFormat::Json,
+            &ExportOptions::default(),
+        )
+        .unwrap();
+        let compressed_options = ExportOptions {
+            compress: true,
+            ..Default::default()
+        };
+        let compressed_path =
+            export_data(&data, &export_path, ExportFormat::Json, &compressed_options).unwrap();
+
+        assert_eq!(plain_path, export_path);
+        assert_eq!(compressed_path, temp_dir.path().join("results.json.gz"));
+
+        let mut decompressed = Vec::new();
+        GzDecoder::new(File::open(&compressed_path).unwrap())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, std::fs::read(&plain_path).unwrap());
+    }
 }
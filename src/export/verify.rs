@@ -0,0 +1,189 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::data::{ExportData, ExportDataType};
+use crate::utils::scan_filter::ScanFilter;
+use crate::utils::{duplicate_utils, hash_utils};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeMismatch {
+    pub path: PathBuf,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct VerifyReport {
+    pub verified: usize,
+    pub hashed: usize,
+    pub missing: Vec<PathBuf>,
+    pub size_mismatches: Vec<SizeMismatch>,
+    pub hash_mismatches: Vec<PathBuf>,
+    pub extra: Vec<PathBuf>,
+    pub errors: Vec<String>,
+}
+
+impl VerifyReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty()
+            && self.size_mismatches.is_empty()
+            && self.hash_mismatches.is_empty()
+            && self.extra.is_empty()
+    }
+}
+
+/// Checks that every record of an organize export exists under `target_root`
+/// with the recorded size. With `check_hashes`, files whose original is still
+/// in place are also compared by SHA-256. Images in the target tree that no
+/// record accounts for are reported as extra.
+pub fn verify_organize_export(
+    data: &ExportData,
+    target_root: &Path,
+    check_hashes: bool,
+) -> Result<VerifyReport> {
+    let ExportDataType::Organize { file_records, .. } = &data.data else {
+        return Err(anyhow::anyhow!(
+            "Export was written by the '{}' command; only organize exports can be verified",
+            data.metadata.command
+        ));
+    };
+
+    let relative_paths = data
+        .metadata
+        .command_metadata
+        .get("relative_paths")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let mut report = VerifyReport::default();
+    let mut expected = HashSet::new();
+
+    for record in file_records {
+        let target = resolve_target_path(&record.target_path, target_root);
+        expected.insert(target.clone());
+
+        let actual = match std::fs::metadata(&target) {
+            Ok(metadata) => metadata.len(),
+            Err(_) => {
+                report.missing.push(target);
+                continue;
+            }
+        };
+
+        if actual != record.file_size_bytes {
+            report.size_mismatches.push(SizeMismatch {
+                path: target,
+                expected: record.file_size_bytes,
+                actual,
+            });
+            continue;
+        }
+
+        let original = if relative_paths && record.original_path.is_relative() {
+            data.metadata.source_directory.join(&record.original_path)
+        } else {
+            record.original_path.clone()
+        };
+
+        if check_hashes && original.is_file() {
+            match (
+                hash_utils::sha256_file(&original),
+                hash_utils::sha256_file(&target),
+            ) {
+                (Ok(original_hash), Ok(target_hash)) => {
+                    report.hashed += 1;
+                    if original_hash != target_hash {
+                        report.hash_mismatches.push(target);
+                        continue;
+                    }
+                }
+                (Err(e), _) => report.errors.push(format!("{}: {}", original.display(), e)),
+                (_, Err(e)) => report.errors.push(format!("{}: {}", target.display(), e)),
+            }
+        }
+
+        report.verified += 1;
+    }
+
+    let scanned =
+        duplicate_utils::scan_image_files(&[target_root], true, &ScanFilter::new(&[target_root]));
+    report.errors.extend(scanned.errors);
+    report.extra = scanned
+        .files
+        .into_iter()
+        .filter(|file| !expected.contains(file))
+        .collect();
+    report.extra.sort();
+
+    Ok(report)
+}
+
+/// Recorded target paths start with the name of the target directory; the
+/// rest is placed under `target_root`. Absolute paths are used as they are.
+fn resolve_target_path(target_path: &Path, target_root: &Path) -> PathBuf {
+    if target_path.is_absolute() {
+        return target_path.to_path_buf();
+    }
+
+    target_root.join(target_path.components().skip(1).collect::<PathBuf>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::data::TargetConfig;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_verify_organize_export() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("inbox");
+        let target = temp_dir.path().join("library");
+        fs::create_dir_all(&source).unwrap();
+
+        let files: Vec<PathBuf> = ["a.jpg", "b.jpg", "c.jpg", "d.jpg"]
+            .iter()
+            .map(|name| {
+                let path = source.join(name);
+                fs::write(&path, format!("image {}", name)).unwrap();
+                path
+            })
+            .collect();
+
+        let mut organized = HashMap::new();
+        organized.insert("2023-01-15".to_string(), files);
+        let data = ExportData::organize(
+            organized,
+            TargetConfig {
+                base_path: Some(target.clone()),
+                template: Some("{year}/{month}".to_string()),
+                unknown_dir: None,
+            },
+            source.clone(),
+            4,
+        );
+
+        let day_dir = target.join("2023").join("01");
+        fs::create_dir_all(&day_dir).unwrap();
+        fs::copy(source.join("a.jpg"), day_dir.join("a.jpg")).unwrap();
+        fs::write(day_dir.join("b.jpg"), "truncated").unwrap();
+        fs::write(day_dir.join("c.jpg"), "image x.jpg").unwrap();
+        fs::write(day_dir.join("stray.png"), "stray").unwrap();
+
+        let report = verify_organize_export(&data, &target, true).unwrap();
+        assert_eq!(report.verified, 1);
+        assert_eq!(report.missing, vec![day_dir.join("d.jpg")]);
+        assert_eq!(report.size_mismatches.len(), 1);
+        assert_eq!(report.size_mismatches[0].path, day_dir.join("b.jpg"));
+        assert_eq!(report.hash_mismatches, vec![day_dir.join("c.jpg")]);
+        assert_eq!(report.extra, vec![day_dir.join("stray.png")]);
+        assert!(!report.is_clean());
+
+        let report = verify_organize_export(&data, &target, false).unwrap();
+        assert_eq!(report.verified, 2);
+        assert_eq!(report.hashed, 0);
+    }
+}
@@ -1,6 +1,7 @@
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-use console::{style, Emoji};
+use anyhow::{Context, Result};
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::Shell;
+use console::style;
 
 mod commands;
 mod export;
@@ -8,12 +9,18 @@ mod output;
 mod progress;
 mod utils;
 
-use commands::{handle_duplicates, handle_organize, DuplicatesArgs, OrganizeArgs};
+use commands::{
+    handle_convert, handle_dedupe, handle_duplicates, handle_exif, handle_organize, handle_rename,
+    handle_resize, handle_stats, handle_verify, handle_watch, ConvertArgs, DedupeArgs,
+    DuplicatesArgs, ExifArgs, OrganizeArgs, RenameArgs, ResizeArgs, StatsArgs, VerifyArgs,
+    WatchArgs,
+};
 
-static LOOKING_GLASS: Emoji = Emoji("🔍 ", "");
-static FILES: Emoji = Emoji("📁 ", "");
-static DUPLICATE: Emoji = Emoji("🔄 ", "");
-static WARNING: Emoji = Emoji("⚠️ ", "");
+use output::emoji::{emoji, LOOKING_GLASS, WARNING};
+use output::OutputMode;
+use progress::{ProgressFormat, ProgressMode};
+use utils::config_file::ConfigFile;
+use utils::operation_log;
 
 #[derive(Parser)]
 #[command(name = "image-manager-cli")]
@@ -23,6 +30,106 @@ static WARNING: Emoji = Emoji("⚠️ ", "");
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Read default options from this TOML file (default: ./image-manager.toml if present)"
+    )]
+    config: Option<std::path::PathBuf>,
+
+    #[arg(
+        short = 'q',
+        long,
+        global = true,
+        help = "Only print errors; no status lines, previews or progress bars"
+    )]
+    quiet: bool,
+
+    #[arg(
+        short = 'v',
+        long,
+        global = true,
+        action = clap::ArgAction::Count,
+        conflicts_with = "quiet",
+        help = "Log each file as it is scanned, skipped, copied or fails (-v; -vv adds scanned files)"
+    )]
+    verbose: u8,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Disable colored output (also set by the NO_COLOR environment variable)"
+    )]
+    no_color: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Print ASCII markers instead of emoji, for terminals without emoji fonts"
+    )]
+    no_emoji: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value = "human",
+        help = "Print results for people (human), as aligned preview tables (table) or as one JSON document on stdout (json; organize, duplicates, dedupe, stats, rename and exif)"
+    )]
+    output: OutputMode,
+
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "output",
+        help = "Render the organize and duplicates previews as tables (same as --output table)"
+    )]
+    table: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        default_value_t = progress::config::MAX_DISPLAY_ITEMS,
+        help = "Show at most N files, groups or errors per listing (0 = unlimited)"
+    )]
+    max_display: usize,
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value = "human",
+        help = "Show progress bars (human) or write progress to stderr as one JSON object per line (json)"
+    )]
+    progress_format: ProgressFormat,
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value = "auto",
+        help = "Draw progress bars only on a terminal (auto), always, or never"
+    )]
+    progress: ProgressMode,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Append a timestamped JSON line for every scanned, copied, skipped, deleted or failed file to PATH"
+    )]
+    log_file: Option<std::path::PathBuf>,
+}
+
+impl Cli {
+    /// Whether colors are turned off by `--no-color` or a non-empty
+    /// `NO_COLOR` (see https://no-color.org).
+    fn colors_disabled(&self) -> bool {
+        self.no_color || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+    }
 }
 
 #[derive(Subcommand)]
@@ -31,39 +138,237 @@ enum Commands {
     Organize(OrganizeArgs),
     /// Find duplicate images in a directory
     Duplicates(DuplicatesArgs),
+    /// Remove duplicates and organize the remaining images in one pass
+    Dedupe(DedupeArgs),
+    /// Summarize the images in a directory
+    Stats(StatsArgs),
+    /// Rename images after their date
+    Rename(RenameArgs),
+    /// Convert images to another format
+    Convert(ConvertArgs),
+    /// Show selected EXIF tags for each image
+    Exif(ExifArgs),
+    /// Copy new images into a dated target tree as they arrive
+    Watch(WatchArgs),
+    /// Check that the files of an organize export landed in the target tree
+    Verify(VerifyArgs),
+    /// Write copies of images scaled down to fit within a maximum size
+    Resize(ResizeArgs),
+    /// Print a shell completion script to stdout
+    ///
+    /// Redirect the output into your shell's completion directory, for example:
+    ///
+    ///   image-manager-cli completions bash > ~/.local/share/bash-completion/completions/image-manager-cli
+    ///
+    ///   image-manager-cli completions zsh > ~/.zfunc/_image-manager-cli
+    ///
+    ///   image-manager-cli completions fish > ~/.config/fish/completions/image-manager-cli.fish
+    ///
+    ///   image-manager-cli completions powershell >> $PROFILE
+    Completions {
+        #[arg(value_enum, help = "Shell to generate completions for")]
+        shell: Shell,
+    },
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    // The script goes to stdout as-is, without the usual status output.
+    if let Commands::Completions { shell } = cli.command {
+        let mut command = Cli::command();
+        let bin_name = command.get_name().to_string();
+        clap_complete::generate(shell, &mut command, bin_name, &mut std::io::stdout());
+        return;
+    }
 
-    match run(cli) {
+    output::set_quiet(cli.quiet);
+    output::set_output_mode(if cli.table {
+        OutputMode::Table
+    } else {
+        cli.output
+    });
+    progress::set_progress_format(cli.progress_format);
+    progress::set_progress_mode(cli.progress);
+    output::emoji::set_emoji_enabled(!cli.no_emoji);
+    init_logging(cli.quiet, cli.verbose, cli.colors_disabled());
+
+    let result = run(cli, &matches);
+    operation_log::finish(&result);
+
+    match result {
         Ok(_) => {
-            println!("\n{}", style("✓ Operation completed successfully").green());
+            status!("\n{}", style("✓ Operation completed successfully").green());
         }
         Err(e) => {
-            eprintln!("\n{} {}", WARNING, style(format!("Error: {}", e)).red());
+            eprintln!(
+                "\n{} {}",
+                emoji(WARNING),
+                style(format!("Error: {}", e)).red()
+            );
             std::process::exit(1);
         }
     }
 }
 
-fn run(cli: Cli) -> Result<()> {
+/// Logs go to stderr. `-v` shows per-file events, `-vv` also every scanned
+/// file and `-vvv` everything; `RUST_LOG` overrides the level.
+fn init_logging(quiet: bool, verbosity: u8, no_color: bool) {
+    let level = match (quiet, verbosity) {
+        (true, _) => log::LevelFilter::Error,
+        (false, 0) => log::LevelFilter::Warn,
+        (false, 1) => log::LevelFilter::Info,
+        (false, 2) => log::LevelFilter::Debug,
+        (false, _) => log::LevelFilter::Trace,
+    };
+
+    env_logger::Builder::new()
+        .filter_level(log::LevelFilter::Warn)
+        .filter_module("image_manager_cli", level)
+        .format_timestamp(None)
+        .format_target(false)
+        .write_style(if no_color {
+            env_logger::WriteStyle::Never
+        } else {
+            env_logger::WriteStyle::Auto
+        })
+        .parse_default_env()
+        .init();
+}
+
+fn run(mut cli: Cli, matches: &ArgMatches) -> Result<()> {
+    // These commands build no result document to print.
+    let without_json = match &cli.command {
+        Commands::Convert(_) => Some("convert"),
+        Commands::Watch(_) => Some("watch"),
+        Commands::Verify(_) => Some("verify"),
+        Commands::Resize(_) => Some("resize"),
+        _ => None,
+    };
+    if let Some(command) = without_json.filter(|_| output::is_json_output()) {
+        return Err(anyhow::anyhow!(
+            "--output json is not supported by the {} command",
+            command
+        ));
+    }
+
+    if cli.colors_disabled() {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+
+    if let Some(log_file) = &cli.log_file {
+        operation_log::open(log_file)?;
+        let command_line: Vec<String> = std::env::args().collect();
+        operation_log::record_run("started", Some(&command_line.join(" ")));
+
+        // Watch handles Ctrl-C itself and ends the run normally.
+        if !matches!(cli.command, Commands::Watch(_)) {
+            ctrlc::set_handler(|| {
+                operation_log::record_run("interrupted", None);
+                operation_log::sync();
+                std::process::exit(130);
+            })
+            .context("Failed to install Ctrl-C handler")?;
+        }
+    }
+
+    let config = ConfigFile::load(cli.config.as_deref())?;
+    if let Some((_, command_matches)) = matches.subcommand() {
+        match &mut cli.command {
+            Commands::Organize(args) => config.apply_to_organize(args, command_matches),
+            Commands::Duplicates(args) => config.apply_to_duplicates(args, command_matches),
+            _ => {}
+        }
+    }
+
+    let max_display = progress::config::display_limit(cli.max_display);
     match cli.command {
         Commands::Organize(args) => {
-            println!(
-                "{} {} Scanning directory for organization preview...",
-                LOOKING_GLASS,
-                style("Organize").cyan()
-            );
-            handle_organize(args)
+            if args.action.is_none() {
+                status!(
+                    "{} {} Scanning directory for organization preview...",
+                    emoji(LOOKING_GLASS),
+                    style("Organize").cyan()
+                );
+            }
+            handle_organize(args, max_display)
         }
         Commands::Duplicates(args) => {
-            println!(
+            status!(
                 "{} {} Scanning directory for duplicates...",
-                LOOKING_GLASS,
+                emoji(LOOKING_GLASS),
                 style("Duplicates").cyan()
             );
-            handle_duplicates(args)
+            handle_duplicates(args, max_display)
+        }
+        Commands::Dedupe(args) => {
+            status!(
+                "{} {} Scanning directory for duplicates to remove...",
+                emoji(LOOKING_GLASS),
+                style("Dedupe").cyan()
+            );
+            handle_dedupe(args, max_display)
+        }
+        Commands::Stats(args) => {
+            status!(
+                "{} {} Scanning directory for statistics...",
+                emoji(LOOKING_GLASS),
+                style("Stats").cyan()
+            );
+            handle_stats(args, max_display)
+        }
+        Commands::Rename(args) => {
+            status!(
+                "{} {} Scanning directory for files to rename...",
+                emoji(LOOKING_GLASS),
+                style("Rename").cyan()
+            );
+            handle_rename(args, max_display)
+        }
+        Commands::Convert(args) => {
+            status!(
+                "{} {} Scanning directory for images to convert...",
+                emoji(LOOKING_GLASS),
+                style("Convert").cyan()
+            );
+            handle_convert(args, max_display)
+        }
+        Commands::Exif(args) => {
+            status!(
+                "{} {} Scanning directory for EXIF metadata...",
+                emoji(LOOKING_GLASS),
+                style("Exif").cyan()
+            );
+            handle_exif(args, max_display)
+        }
+        Commands::Watch(args) => {
+            status!(
+                "{} {} Watching {} for new images (Ctrl-C to stop)...",
+                emoji(LOOKING_GLASS),
+                style("Watch").cyan(),
+                args.directory.display()
+            );
+            handle_watch(args, max_display)
+        }
+        Commands::Verify(args) => {
+            status!(
+                "{} {} Checking organize plan {}...",
+                emoji(LOOKING_GLASS),
+                style("Verify").cyan(),
+                args.plan.display()
+            );
+            handle_verify(args, max_display)
+        }
+        Commands::Resize(args) => {
+            status!(
+                "{} {} Scanning directory for images to resize...",
+                emoji(LOOKING_GLASS),
+                style("Resize").cyan()
+            );
+            handle_resize(args, max_display)
         }
+        Commands::Completions { .. } => unreachable!("completions are generated in main"),
     }
 }
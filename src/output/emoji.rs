@@ -0,0 +1,39 @@
+use console::Emoji;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub static LOOKING_GLASS: Emoji = Emoji("🔍", ">");
+pub static FILES: Emoji = Emoji("📁", "*");
+pub static DUPLICATE: Emoji = Emoji("🔄", "=");
+pub static WARNING: Emoji = Emoji("⚠️", "!");
+pub static INFO: Emoji = Emoji("ℹ️", "i");
+pub static EMPTY: Emoji = Emoji("📭", "-");
+pub static DOCUMENT: Emoji = Emoji("📄", "*");
+pub static TRASH: Emoji = Emoji("🗑️", "x");
+pub static PICTURE: Emoji = Emoji("🖼️", "*");
+pub static DISK: Emoji = Emoji("💾", "*");
+pub static PENCIL: Emoji = Emoji("✏️", "*");
+pub static DIVIDERS: Emoji = Emoji("🗂️", "*");
+pub static LINK: Emoji = Emoji("🔗", "*");
+pub static CAMERA: Emoji = Emoji("📷", "*");
+pub static INBOX: Emoji = Emoji("📥", "*");
+pub static CHART: Emoji = Emoji("📊", "*");
+pub static CALENDAR: Emoji = Emoji("📅", "*");
+pub static HOURGLASS: Emoji = Emoji("⏳", "...");
+
+static EMOJI_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Makes every emoji print its ASCII fallback for the rest of the run.
+pub fn set_emoji_enabled(enabled: bool) {
+    EMOJI_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// All emoji output goes through here so `--no-emoji` reaches every call
+/// site. Without the flag, `Emoji` still falls back on terminals that do not
+/// want emoji.
+pub fn emoji(symbol: Emoji<'static, 'static>) -> Emoji<'static, 'static> {
+    if EMOJI_ENABLED.load(Ordering::Relaxed) {
+        symbol
+    } else {
+        Emoji(symbol.1, symbol.1)
+    }
+}
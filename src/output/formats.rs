@@ -1,150 +1,838 @@
+use comfy_table::presets::UTF8_FULL_CONDENSED;
+use comfy_table::{Cell, CellAlignment, ContentArrangement, Row, Table};
 use console::style;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 use image_manager_lib::ProcessingError;
 
+use crate::commands::{DuplicatesArgs, SortOrder};
+use crate::export::data::{ExportData, ExportDataType};
+use crate::output::emoji::{
+    emoji, CALENDAR, CAMERA, CHART, DIVIDERS, DUPLICATE, EMPTY, FILES, INFO, PENCIL, WARNING,
+};
+use crate::output::is_table_output;
+use crate::status;
+use crate::utils::scan_filter::SkippedFile;
+use crate::utils::size_utils::format_bytes;
+use crate::utils::{duplicate_utils, path_template};
+
 pub fn print_organize_preview(
     organized_images: &HashMap<String, Vec<PathBuf>>,
     errors: &[ProcessingError],
     target_path: Option<&PathBuf>,
+    template: &str,
+    unknown_dir: &str,
+    sort: SortOrder,
+    max_display: usize,
 ) {
     if organized_images.is_empty() && errors.is_empty() {
-        println!(
+        status!(
             "\n{} {}",
-            style("📭").yellow(),
+            style(emoji(EMPTY)).yellow(),
             style("No supported images found in directory").bold()
         );
         return;
     }
 
-    println!(
+    status!(
         "\n{} {}",
-        style("📁").cyan(),
+        style(emoji(FILES)).cyan(),
         style("Organization Preview").bold().cyan()
     );
-    println!("{}", style("━".repeat(50)).dim());
+    status!("{}", style("━".repeat(50)).dim());
 
+    let mut folders: BTreeMap<String, Vec<&PathBuf>> = BTreeMap::new();
+    let mut undated: Vec<&PathBuf> = Vec::new();
     for (date, files) in organized_images {
-        println!("\n{} {}", style("📅").blue(), style(date).bold());
+        for file in files {
+            match path_template::destination_subdir(template, date, file) {
+                Some(subdir) => folders
+                    .entry(
+                        subdir
+                            .to_string_lossy()
+                            .replace(std::path::MAIN_SEPARATOR, "/"),
+                    )
+                    .or_default()
+                    .push(file),
+                None => undated.push(file),
+            }
+        }
+    }
+
+    for files in folders.values_mut() {
+        files.sort();
+    }
+    undated.sort();
+
+    let mut folders: Vec<_> = folders.iter().collect();
+    if sort == SortOrder::Desc {
+        folders.reverse();
+    }
+
+    // Undated files always come last, whichever way the dates are sorted.
+    let sections = folders
+        .into_iter()
+        .map(|(folder, files)| {
+            let heading = if folder.is_empty() {
+                "All files (flat)"
+            } else {
+                folder.as_str()
+            };
+            (heading, folder.as_str(), files)
+        })
+        .chain((!undated.is_empty()).then_some(("Unknown date", unknown_dir, &undated)));
+
+    if is_table_output() {
+        let mut table = preview_table(&["Date", "File", "Size"]);
+        for (heading, _, files) in sections {
+            for (i, file) in files.iter().enumerate().take(max_display) {
+                let date = if i == 0 { heading } else { "" };
+                table.add_row(preview_row(vec![
+                    Cell::new(date),
+                    Cell::new(file.display()),
+                    size_cell(file),
+                ]));
+            }
+            if files.len() > max_display {
+                table.add_row(more_files_row(files.len() - max_display, 3));
+            }
+        }
+        status!("{table}");
+        display_errors(errors, "Processing Errors", max_display);
+        return;
+    }
+
+    for (heading, folder, files) in sections {
+        status!(
+            "\n{} {}",
+            style(emoji(CALENDAR)).blue(),
+            style(heading).bold()
+        );
 
         if let Some(target_path) = target_path {
             let target_dir_name = target_path
                 .file_name()
                 .and_then(|name| name.to_str())
                 .unwrap_or("untitled");
-            println!(
-                "   Target: {}/{}/{}",
-                style(target_dir_name).green(),
-                style(date).cyan(),
-                style(files.len()).yellow()
-            );
+            if folder.is_empty() {
+                status!(
+                    "   Target: {}/{}",
+                    style(target_dir_name).green(),
+                    style(files.len()).yellow()
+                );
+            } else {
+                status!(
+                    "   Target: {}/{}/{}",
+                    style(target_dir_name).green(),
+                    style(folder).cyan(),
+                    style(files.len()).yellow()
+                );
+            }
         } else {
-            println!("   Files: {}", style(files.len()).yellow());
+            status!("   Files: {}", style(files.len()).yellow());
         }
 
-        for (i, file) in files.iter().enumerate() {
-            println!(
+        for (i, file) in files.iter().enumerate().take(max_display) {
+            status!(
                 "   {}. {}",
                 style(i + 1).dim(),
                 style(file.file_name().unwrap_or_default().to_string_lossy()).cyan()
             );
         }
+
+        if files.len() > max_display {
+            status!("   ... and {} more files", files.len() - max_display);
+        }
+    }
+
+    display_errors(errors, "Processing Errors", max_display);
+}
+
+/// A folder in the tree preview: its subfolders by name and the files
+/// filed directly in it.
+#[derive(Default)]
+struct TreeNode<'a> {
+    children: BTreeMap<String, TreeNode<'a>>,
+    files: Vec<&'a PathBuf>,
+}
+
+impl TreeNode<'_> {
+    fn file_count(&self) -> usize {
+        self.files.len()
+            + self
+                .children
+                .values()
+                .map(TreeNode::file_count)
+                .sum::<usize>()
     }
+}
 
-    print_errors(errors);
+/// Alternative to `print_organize_preview` for `--view tree`: the target
+/// layout as a tree with one level per folder of the template.
+pub fn print_organize_tree(
+    organized_images: &HashMap<String, Vec<PathBuf>>,
+    errors: &[ProcessingError],
+    target_path: Option<&PathBuf>,
+    template: &str,
+    unknown_dir: &str,
+    sort: SortOrder,
+    max_display: usize,
+) {
+    if organized_images.is_empty() && errors.is_empty() {
+        status!(
+            "\n{} {}",
+            style(emoji(EMPTY)).yellow(),
+            style("No supported images found in directory").bold()
+        );
+        return;
+    }
+
+    let (root, undated) = build_tree(organized_images, template);
+
+    let root_name = target_path
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| ".".to_string());
+    status!(
+        "\n{} {}",
+        style(root_name).green().bold(),
+        style(format!(
+            "({} files)",
+            root.file_count() + undated.file_count()
+        ))
+        .dim()
+    );
+
+    let mut lines = Vec::new();
+    // Undated files always come last, whichever way the dates are sorted.
+    let extra = (!undated.files.is_empty()).then_some((unknown_dir, &undated));
+    render_tree(&root, extra, "", sort, max_display, &mut lines);
+    for line in lines {
+        status!("{}", line);
+    }
+
+    display_errors(errors, "Processing Errors", max_display);
+}
+
+/// Files each image under the folders `template` resolves to for its date;
+/// files whose date the template cannot use go into the second node.
+fn build_tree<'a>(
+    organized_images: &'a HashMap<String, Vec<PathBuf>>,
+    template: &str,
+) -> (TreeNode<'a>, TreeNode<'a>) {
+    let mut root = TreeNode::default();
+    let mut undated = TreeNode::default();
+    for (date, files) in organized_images {
+        for file in files {
+            let node = match path_template::destination_subdir(template, date, file) {
+                Some(subdir) => subdir
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy().into_owned())
+                    .fold(&mut root, |node, part| {
+                        node.children.entry(part).or_default()
+                    }),
+                None => &mut undated,
+            };
+            node.files.push(file);
+        }
+    }
+    (root, undated)
+}
+
+enum TreeEntry<'a> {
+    Folder(&'a str, &'a TreeNode<'a>),
+    File(&'a PathBuf),
+}
+
+fn render_tree<'a>(
+    node: &'a TreeNode<'a>,
+    extra: Option<(&'a str, &'a TreeNode<'a>)>,
+    prefix: &str,
+    sort: SortOrder,
+    max_display: usize,
+    lines: &mut Vec<String>,
+) {
+    let mut folders: Vec<(&str, &TreeNode)> = node
+        .children
+        .iter()
+        .map(|(name, child)| (name.as_str(), child))
+        .collect();
+    if sort == SortOrder::Desc {
+        folders.reverse();
+    }
+    folders.extend(extra);
+
+    let mut files = node.files.clone();
+    files.sort();
+
+    let entries: Vec<TreeEntry> = folders
+        .into_iter()
+        .map(|(name, child)| TreeEntry::Folder(name, child))
+        .chain(files.into_iter().map(TreeEntry::File))
+        .collect();
+
+    for (index, entry) in entries.iter().enumerate().take(max_display) {
+        let (branch, indent) = if index + 1 == entries.len() {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        match entry {
+            TreeEntry::Folder(name, child) => {
+                lines.push(format!(
+                    "{}{}{} {}",
+                    style(prefix).dim(),
+                    style(branch).dim(),
+                    style(name).cyan().bold(),
+                    style(format!("({})", child.file_count())).dim()
+                ));
+                render_tree(
+                    child,
+                    None,
+                    &format!("{}{}", prefix, indent),
+                    sort,
+                    max_display,
+                    lines,
+                );
+            }
+            TreeEntry::File(file) => lines.push(format!(
+                "{}{}{}",
+                style(prefix).dim(),
+                style(branch).dim(),
+                file.file_name().unwrap_or_default().to_string_lossy()
+            )),
+        }
+    }
+
+    if entries.len() > max_display {
+        lines.push(format!(
+            "{}{}... {} more",
+            style(prefix).dim(),
+            style("└── ").dim(),
+            entries.len() - max_display
+        ));
+    }
 }
 
 pub fn print_duplicates_preview(
     duplicate_groups: &image_manager_lib::duplicates::DuplicateGroups,
     errors: &[ProcessingError],
     similarity_threshold: &image_manager_lib::SimilarityThreshold,
+    args: &DuplicatesArgs,
+    max_display: usize,
 ) {
+    let keep = args.keep;
+    let min_group_size = args.min_group_size;
+    let compare_directories = args
+        .compare_directory
+        .as_deref()
+        .map(|compare| (args.directory.as_path(), compare));
+    let sort = args.sort_groups;
+
     if duplicate_groups.is_empty() && errors.is_empty() {
-        println!(
+        status!(
             "\n{} {}",
-            style("📭").yellow(),
+            style(emoji(EMPTY)).yellow(),
             style("No duplicate images found").bold()
         );
         return;
     }
 
-    println!(
+    status!(
         "\n{} {}",
-        style("🔄").cyan(),
+        style(emoji(DUPLICATE)).cyan(),
         style("Duplicate Detection Preview").bold().cyan()
     );
-    println!("{}", style("━".repeat(50)).dim());
-    println!(
+    status!("{}", style("━".repeat(50)).dim());
+    status!(
         "Similarity threshold: {}",
         style(format!("{:.2}%", similarity_threshold.value() * 100.0)).green()
     );
 
-    for (group_index, group) in duplicate_groups.iter().enumerate() {
-        if group.len() > 1 {
-            println!(
-                "\n{} {}",
-                style("Group").blue(),
-                style(group_index + 1).bold()
-            );
-            println!("   Files: {}", style(group.len()).yellow());
+    // Groups keep their detection number so they match the export's group IDs.
+    let shown_groups: Vec<usize> = duplicate_utils::group_order(duplicate_groups, sort, keep)
+        .into_iter()
+        .filter(|&group_index| duplicate_groups[group_index].len() >= min_group_size)
+        .collect();
 
+    if is_table_output() {
+        let similarity = format!("{:.2}%", similarity_threshold.value() * 100.0);
+        let mut table = preview_table(&["Group", "File", "Size", "Similarity", "Keep"]);
+        for &group_index in shown_groups.iter().take(max_display) {
+            let group = &duplicate_groups[group_index];
+            let survivor = duplicate_utils::select_survivor(group, keep);
             for (file_index, file) in group.iter().enumerate() {
-                let size_str = if let Ok(metadata) = std::fs::metadata(file) {
-                    format!(" ({})", style(format_bytes(metadata.len())).dim())
-                } else {
-                    String::new()
+                let file_label = match compare_directories {
+                    Some((source, _)) if file.starts_with(source) => {
+                        format!("{} [source]", file.display())
+                    }
+                    Some(_) => format!("{} [compare]", file.display()),
+                    None => file.display().to_string(),
                 };
+                let first = file_index == 0;
+                table.add_row(preview_row(vec![
+                    Cell::new(if first {
+                        (group_index + 1).to_string()
+                    } else {
+                        String::new()
+                    })
+                    .set_alignment(CellAlignment::Right),
+                    Cell::new(file_label),
+                    size_cell(file),
+                    Cell::new(if first { similarity.as_str() } else { "" })
+                        .set_alignment(CellAlignment::Right),
+                    Cell::new(if file_index == survivor { "KEEP" } else { "" }),
+                ]));
+            }
+        }
+        status!("{table}");
+        if shown_groups.len() > max_display {
+            status!(
+                "... and {} more groups (use --max-display 0 to list all)",
+                shown_groups.len() - max_display
+            );
+        }
+        display_errors(errors, "Processing Errors", max_display);
+        return;
+    }
 
-                println!(
-                    "   {}. {}{}",
-                    style(file_index + 1).dim(),
-                    style(file.display()).cyan(),
-                    size_str
-                );
+    for &group_index in shown_groups.iter().take(max_display) {
+        let group = &duplicate_groups[group_index];
+        status!(
+            "\n{} {}",
+            style("Group").blue(),
+            style(group_index + 1).bold()
+        );
+        status!("   Files: {}", style(group.len()).yellow());
+
+        let survivor = duplicate_utils::select_survivor(group, keep);
+        for (file_index, file) in group.iter().enumerate() {
+            let size_str = if let Ok(metadata) = std::fs::metadata(file) {
+                format!(" ({})", style(format_bytes(metadata.len())).dim())
+            } else {
+                String::new()
+            };
+
+            let origin_tag = match compare_directories {
+                Some((source, _)) if file.starts_with(source) => {
+                    format!(" {}", style("[source]").dim())
+                }
+                Some(_) => format!(" {}", style("[compare]").dim()),
+                None => String::new(),
+            };
+
+            let keep_tag = if file_index == survivor {
+                format!(" {}", style("KEEP").green().bold())
+            } else {
+                String::new()
+            };
+
+            status!(
+                "   {}. {}{}{}{}",
+                style(file_index + 1).dim(),
+                style(file.display()).cyan(),
+                size_str,
+                origin_tag,
+                keep_tag
+            );
+        }
+    }
+
+    if shown_groups.len() > max_display {
+        status!(
+            "\n... and {} more groups (use --max-display 0 to list all)",
+            shown_groups.len() - max_display
+        );
+    }
+
+    display_errors(errors, "Processing Errors", max_display);
+}
+
+/// A preview table that fits the terminal width. Cells that would wrap are
+/// cut to one line with an ellipsis instead.
+fn preview_table(header: &[&str]) -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL_CONDENSED)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_truncation_indicator("…")
+        .set_header(header.to_vec());
+    table
+}
+
+fn preview_row(cells: Vec<Cell>) -> Row {
+    let mut row = Row::from(cells);
+    row.max_height(1);
+    row
+}
+
+fn size_cell(file: &Path) -> Cell {
+    let size = std::fs::metadata(file)
+        .map(|metadata| format_bytes(metadata.len()))
+        .unwrap_or_else(|_| "-".to_string());
+    Cell::new(size).set_alignment(CellAlignment::Right)
+}
+
+fn more_files_row(remaining: usize, columns: usize) -> Row {
+    let mut cells = vec![Cell::new(""); columns];
+    cells[1] = Cell::new(format!("... and {} more files", remaining));
+    preview_row(cells)
+}
+
+pub fn print_exif_table(data: &ExportData, max_display: usize) {
+    let ExportDataType::Exif { tags, file_records } = &data.data else {
+        return;
+    };
+
+    if file_records.is_empty() {
+        status!(
+            "\n{} {}",
+            style(emoji(EMPTY)).yellow(),
+            style("No supported images found in directory").bold()
+        );
+        return;
+    }
+
+    status!(
+        "\n{} {}",
+        style(emoji(CAMERA)).cyan(),
+        style("EXIF Metadata").bold().cyan()
+    );
+    status!("{}", style("━".repeat(50)).dim());
+
+    let name_width = tags.iter().map(String::len).max().unwrap_or(0);
+    for record in file_records.iter().take(max_display) {
+        status!("\n{}", style(record.file_path.display()).bold());
+        for tag in tags {
+            match record.tags.get(tag).and_then(|value| value.as_deref()) {
+                Some(value) => status!("   {:<width$}  {}", tag, value, width = name_width),
+                None => status!(
+                    "   {:<width$}  {}",
+                    tag,
+                    style("-").dim(),
+                    width = name_width
+                ),
             }
         }
     }
 
-    print_errors(errors);
+    if file_records.len() > max_display {
+        status!(
+            "\n{} ... and {} more files",
+            style("•").dim(),
+            file_records.len() - max_display
+        );
+    }
+}
+
+pub fn print_stats_summary(data: &ExportData) {
+    let summary = &data.metadata.summary;
+    if summary.total_files == 0 {
+        status!(
+            "\n{} {}",
+            style(emoji(EMPTY)).yellow(),
+            style("No supported images found in directory").bold()
+        );
+        return;
+    }
+
+    status!(
+        "\n{} {}",
+        style(emoji(CHART)).cyan(),
+        style("Directory Statistics").bold().cyan()
+    );
+    status!("{}", style("━".repeat(50)).dim());
+    status!("Images: {}", style(summary.total_files).yellow());
+    status!(
+        "Total size: {}",
+        style(format_bytes(summary.total_bytes)).cyan()
+    );
+
+    if let ExportDataType::Stats {
+        extension_stats,
+        date_range,
+    } = &data.data
+    {
+        match date_range {
+            Some(range) => status!(
+                "Date range: {} to {}",
+                style(&range.earliest).green(),
+                style(&range.latest).green()
+            ),
+            None => status!("Date range: {}", style("unknown").dim()),
+        }
+
+        status!(
+            "\n{} {}",
+            style(emoji(DIVIDERS)).blue(),
+            style("By extension").bold()
+        );
+        for stats in extension_stats {
+            let extension = if stats.extension.is_empty() {
+                "(none)"
+            } else {
+                stats.extension.as_str()
+            };
+            status!(
+                "   {:<8} {:>6} files  {}",
+                style(extension).cyan(),
+                style(stats.count).yellow(),
+                style(format_bytes(stats.total_bytes)).dim()
+            );
+        }
+    }
+}
+
+pub fn print_rename_preview(renames: &[(PathBuf, PathBuf)], unchanged: usize, dry_run: bool) {
+    status!(
+        "\n{} {}",
+        style(emoji(PENCIL)).cyan(),
+        style(if dry_run {
+            "Rename Preview (dry run)"
+        } else {
+            "Rename Plan"
+        })
+        .bold()
+        .cyan()
+    );
+    status!("{}", style("━".repeat(50)).dim());
+
+    if renames.is_empty() {
+        status!("No files need renaming");
+    }
+
+    for (original, new_path) in renames {
+        status!(
+            "   {} → {}",
+            style(original.file_name().unwrap_or_default().to_string_lossy()).dim(),
+            style(new_path.file_name().unwrap_or_default().to_string_lossy()).cyan()
+        );
+    }
+
+    if unchanged > 0 {
+        status!(
+            "\n{} {} files already match the template",
+            style(emoji(INFO)).cyan(),
+            style(unchanged).yellow()
+        );
+    }
 }
 
-pub fn print_errors(errors: &[ProcessingError]) {
+/// One-line recap at the end of an organize run, e.g. "142 files across 12
+/// dates, 3 errors, 1.2 GB".
+pub fn print_organize_summary(organized_images: &HashMap<String, Vec<PathBuf>>, errors: usize) {
+    let files: Vec<&PathBuf> = organized_images.values().flatten().collect();
+
+    status!(
+        "\n{} {} across {}, {}, {}",
+        style("Σ").cyan(),
+        counted(files.len(), "file", "files"),
+        counted(organized_images.len(), "date", "dates"),
+        counted(errors, "error", "errors"),
+        format_bytes(total_bytes(files))
+    );
+}
+
+/// One-line recap at the end of a duplicates run, including the space the
+/// chosen `--keep` strategy would reclaim.
+pub fn print_duplicates_summary(
+    duplicate_groups: &image_manager_lib::duplicates::DuplicateGroups,
+    errors: usize,
+    reclaimable: u64,
+) {
+    let files: Vec<&PathBuf> = duplicate_groups.iter().flatten().collect();
+
+    status!(
+        "\n{} {} in {}, {}, {} ({} reclaimable)",
+        style("Σ").cyan(),
+        counted(files.len(), "file", "files"),
+        counted(duplicate_groups.len(), "group", "groups"),
+        counted(errors, "error", "errors"),
+        format_bytes(total_bytes(files)),
+        format_bytes(reclaimable)
+    );
+}
+
+fn counted(count: usize, singular: &str, plural: &str) -> String {
+    format!("{} {}", count, if count == 1 { singular } else { plural })
+}
+
+fn total_bytes<'a>(files: impl IntoIterator<Item = &'a PathBuf>) -> u64 {
+    files
+        .into_iter()
+        .filter_map(|file| std::fs::metadata(file).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+pub fn print_limit_notice(limit: usize, skipped: usize) {
+    if skipped == 0 {
+        return;
+    }
+
+    status!(
+        "\n{} Limited to the first {} files; skipped {} (--limit)",
+        style(emoji(INFO)).cyan(),
+        style(limit).cyan(),
+        style(skipped).yellow()
+    );
+}
+
+/// Reports how many files the scan filters skipped; the individual files
+/// are logged with `-v`.
+pub fn print_skipped_files(skipped: &[SkippedFile]) {
+    if skipped.is_empty() {
+        return;
+    }
+
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for file in skipped {
+        let source = file.reason.source();
+        match counts.iter_mut().find(|(name, _)| *name == source) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((source, 1)),
+        }
+    }
+    let breakdown: Vec<String> = counts
+        .iter()
+        .map(|(source, count)| format!("{} {}", count, source))
+        .collect();
+
+    status!(
+        "\n{} Skipped {} files matching the scan filters ({})",
+        style(emoji(INFO)).cyan(),
+        style(skipped.len()).yellow(),
+        breakdown.join(", ")
+    );
+}
+
+pub fn print_paths_outside_source(paths: &[PathBuf], max_display: usize) {
+    if paths.is_empty() {
+        return;
+    }
+
+    status!(
+        "\n{} {}",
+        style(emoji(WARNING)).yellow(),
+        style(format!(
+            "{} exported paths are outside the source directory and were kept absolute",
+            paths.len()
+        ))
+        .yellow()
+    );
+
+    for path in paths.iter().take(max_display) {
+        status!("  {}", style(format!("• {}", path.display())).yellow());
+    }
+
+    if paths.len() > max_display {
+        status!(
+            "  {} ... and {} more paths",
+            style("•").yellow(),
+            paths.len() - max_display
+        );
+    }
+}
+
+/// Prints `errors` to stderr under an `error_type` heading, up to the
+/// display cap.
+pub fn display_errors(errors: &[impl std::fmt::Display], error_type: &str, max_display: usize) {
     if !errors.is_empty() {
-        println!(
+        eprintln!(
             "\n{} {}",
-            style("⚠️").yellow(),
-            style("Processing Errors").yellow()
+            style(emoji(WARNING)).yellow(),
+            style(error_type).yellow()
         );
-        println!("{}", style("━".repeat(30)).dim());
+        eprintln!("{}", style("━".repeat(30)).dim());
 
-        for error in errors.iter().take(10) {
-            println!("  {}", style(format!("• {}", error)).red());
+        for error in errors.iter().take(max_display) {
+            eprintln!("  {}", style(format!("• {}", error)).red());
         }
 
-        if errors.len() > 10 {
-            println!(
+        if errors.len() > max_display {
+            eprintln!(
                 "  {} ... and {} more errors",
                 style("•").red(),
-                errors.len() - 10
+                errors.len() - max_display
             );
         }
     }
 }
 
-fn format_bytes(bytes: u64) -> String {
-    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
-    let mut size = bytes as f64;
-    let mut unit_index = 0;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn organized(entries: &[(&str, &[&str])]) -> HashMap<String, Vec<PathBuf>> {
+        entries
+            .iter()
+            .map(|(date, files)| {
+                let files = files.iter().map(PathBuf::from).collect();
+                (date.to_string(), files)
+            })
+            .collect()
+    }
+
+    fn tree_lines(
+        organized_images: &HashMap<String, Vec<PathBuf>>,
+        template: &str,
+        max_display: usize,
+    ) -> Vec<String> {
+        console::set_colors_enabled(false);
+        let (root, undated) = build_tree(organized_images, template);
+        let extra = (!undated.files.is_empty()).then_some(("Unknown", &undated));
+        let mut lines = Vec::new();
+        render_tree(&root, extra, "", SortOrder::Asc, max_display, &mut lines);
+        lines
+    }
+
+    #[test]
+    fn test_tree_nests_template_folders() {
+        let images = organized(&[
+            ("2023-01-15", &["a.jpg", "b.png"]),
+            ("2023-02-01", &["c.jpg"]),
+            ("unknown", &["d.jpg"]),
+        ]);
 
-    while size >= 1024.0 && unit_index < UNITS.len() - 1 {
-        size /= 1024.0;
-        unit_index += 1;
+        assert_eq!(
+            tree_lines(&images, "{year}/{month}", 10),
+            [
+                "├── 2023 (3)",
+                "│   ├── 01 (2)",
+                "│   │   ├── a.jpg",
+                "│   │   └── b.png",
+                "│   └── 02 (1)",
+                "│       └── c.jpg",
+                "└── Unknown (1)",
+                "    └── d.jpg",
+            ]
+        );
+
+        assert_eq!(
+            tree_lines(&images, "{ext}", 10),
+            [
+                "├── jpg (3)",
+                "│   ├── a.jpg",
+                "│   ├── c.jpg",
+                "│   └── d.jpg",
+                "└── png (1)",
+                "    └── b.png",
+            ]
+        );
     }
 
-    if unit_index == 0 {
-        format!("{} {}", bytes, UNITS[unit_index])
-    } else {
-        format!("{:.1} {}", size, UNITS[unit_index])
+    #[test]
+    fn test_tree_collapses_past_max_display() {
+        let images = organized(&[("2023-01-15", &["a.jpg", "b.jpg", "c.jpg"])]);
+
+        assert_eq!(
+            tree_lines(&images, path_template::FLAT_TEMPLATE, 3),
+            ["├── a.jpg", "├── b.jpg", "└── c.jpg"]
+        );
+        assert_eq!(
+            tree_lines(&images, path_template::FLAT_TEMPLATE, 2),
+            ["├── a.jpg", "├── b.jpg", "└── ... 1 more"]
+        );
     }
 }
@@ -1,3 +1,80 @@
+pub mod emoji;
 pub mod formats;
 
-pub use formats::{print_duplicates_preview, print_organize_preview};
+use anyhow::Result;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::export::{export_data, ExportData, ExportFormat, ExportOptions};
+
+pub use formats::{
+    display_errors, print_duplicates_preview, print_duplicates_summary, print_exif_table,
+    print_limit_notice, print_organize_preview, print_organize_summary, print_organize_tree,
+    print_paths_outside_source, print_rename_preview, print_skipped_files, print_stats_summary,
+};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputMode {
+    #[value(name = "human")]
+    Human,
+    #[value(name = "json")]
+    Json,
+    #[value(name = "table")]
+    Table,
+}
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+static TABLE_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// In JSON mode the result is written to stdout as a single JSON document
+/// and everything decorative is suppressed.
+pub fn set_output_mode(mode: OutputMode) {
+    let json = mode == OutputMode::Json;
+    JSON_OUTPUT.store(json, Ordering::Relaxed);
+    TABLE_OUTPUT.store(mode == OutputMode::Table, Ordering::Relaxed);
+    if json {
+        set_quiet(true);
+    }
+}
+
+pub fn is_json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// In table mode the organize and duplicates previews are rendered as
+/// aligned tables instead of bullet lists.
+pub fn is_table_output() -> bool {
+    TABLE_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Writes `data` to stdout the way `--export - --export-format json` would.
+pub fn print_json_result(data: &ExportData) -> Result<()> {
+    export_data(
+        data,
+        Path::new("-"),
+        ExportFormat::Json,
+        &ExportOptions::default(),
+    )?;
+    Ok(())
+}
+
+/// Suppresses status lines, previews and progress bars for the rest of the
+/// run. Errors are still reported.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// `println!` for status output that `--quiet` suppresses.
+#[macro_export]
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
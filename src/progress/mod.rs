@@ -1,5 +1,9 @@
+use console::Term;
 use image_manager_lib::ProgressHandle;
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
 
 pub mod config {
     use std::time::Duration;
@@ -7,10 +11,87 @@ pub mod config {
     pub const DEFAULT_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
     pub const SPINNER_UPDATE_INTERVAL: Duration = Duration::from_millis(120);
     pub const MAX_DISPLAY_ITEMS: usize = 10;
+
+    /// Turns `--max-display` into the number of files, groups or errors a
+    /// listing shows before summarizing the rest; 0 shows everything.
+    pub fn display_limit(max_display: usize) -> usize {
+        if max_display == 0 {
+            usize::MAX
+        } else {
+            max_display
+        }
+    }
+}
+
+use crate::output::is_quiet;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgressFormat {
+    #[value(name = "human")]
+    Human,
+    #[value(name = "json")]
+    Json,
+}
+
+static JSON_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+/// In JSON mode the library progress is written to stderr as one JSON object
+/// per line and the progress bars are hidden.
+pub fn set_progress_format(format: ProgressFormat) {
+    JSON_PROGRESS.store(format == ProgressFormat::Json, Ordering::Relaxed);
+}
+
+fn is_json_progress() -> bool {
+    JSON_PROGRESS.load(Ordering::Relaxed)
+}
+
+fn bars_hidden() -> bool {
+    is_quiet() || is_json_progress()
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgressMode {
+    #[value(name = "auto")]
+    Auto,
+    #[value(name = "always")]
+    Always,
+    #[value(name = "never")]
+    Never,
+}
+
+static PROGRESS_MODE: OnceLock<ProgressMode> = OnceLock::new();
+
+/// With `auto`, progress bars are only drawn when stderr is a terminal, so
+/// redirected output gets no animation.
+pub fn set_progress_mode(mode: ProgressMode) {
+    let _ = PROGRESS_MODE.set(mode);
+}
+
+fn draw_target() -> ProgressDrawTarget {
+    match PROGRESS_MODE.get().copied().unwrap_or(ProgressMode::Auto) {
+        ProgressMode::Always => ProgressDrawTarget::term_like(Box::new(Term::stderr())),
+        ProgressMode::Auto if Term::stderr().is_term() => ProgressDrawTarget::stderr(),
+        _ => ProgressDrawTarget::hidden(),
+    }
+}
+
+/// Finishes `progress` with `message`. When the bar is not drawn (no
+/// terminal or `--progress never`) the message is printed once as a plain
+/// line instead, unless progress output is off altogether.
+pub fn finish_with_message(progress: &ProgressBar, message: impl Into<Cow<'static, str>>) {
+    let message = message.into();
+    if progress.is_hidden() && !bars_hidden() {
+        eprintln!("{}", message);
+    }
+    progress.finish_with_message(message);
 }
 
 pub fn create_scanner_progress() -> ProgressBar {
-    let progress = ProgressBar::new_spinner();
+    if bars_hidden() {
+        return ProgressBar::hidden();
+    }
+
+    let progress = ProgressBar::with_draw_target(None, draw_target());
     progress.set_style(
         ProgressStyle::default_spinner()
             .template("{spinner:.green} {msg}")
@@ -21,24 +102,49 @@ pub fn create_scanner_progress() -> ProgressBar {
 }
 
 pub fn create_processor_progress() -> ProgressBar {
-    let progress = ProgressBar::new_spinner();
-    progress.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.green} {msg:.cyan}")
-            .unwrap(),
-    );
+    if bars_hidden() {
+        return ProgressBar::hidden();
+    }
+
+    let progress = ProgressBar::with_draw_target(None, draw_target());
+    progress.set_style(processor_spinner_style());
     progress.enable_steady_tick(config::SPINNER_UPDATE_INTERVAL);
     progress
 }
 
-pub fn create_copy_progress(total: u64) -> ProgressBar {
-    let progress = ProgressBar::new(total);
+fn processor_spinner_style() -> ProgressStyle {
+    ProgressStyle::default_spinner()
+        .template("{spinner:.green} [{elapsed}] {msg:.cyan}")
+        .unwrap()
+}
+
+/// Determinate style used once the library reports a completion percentage;
+/// the bar length is 100 so `pos` is the percentage.
+fn processor_bar_style() -> ProgressStyle {
+    ProgressStyle::default_bar()
+        .template("{spinner:.green} [{elapsed}] [{bar:40.cyan/blue}] {pos:>3}% {msg:.cyan}")
+        .unwrap()
+        .progress_chars("#>-")
+}
+
+/// Byte-based bar for copies and conversions; advance it by each file's size.
+pub fn create_copy_progress(total_bytes: u64) -> ProgressBar {
+    if bars_hidden() {
+        return ProgressBar::hidden();
+    }
+
+    let progress = ProgressBar::with_draw_target(Some(total_bytes), draw_target());
     progress.set_style(
         ProgressStyle::default_bar()
-            .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+            .template(
+                "{spinner:.green} [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({binary_bytes_per_sec}, {eta} left) {msg}",
+            )
             .unwrap()
             .progress_chars("#>-"),
     );
+    // Redraw between increments so the rate and ETA keep moving while a
+    // large file is being copied.
+    progress.enable_steady_tick(config::DEFAULT_PROGRESS_INTERVAL);
     progress
 }
 
@@ -46,24 +152,99 @@ pub fn start_progress_monitoring(
     progress_handle: ProgressHandle,
     initial_message: &str,
 ) -> std::thread::JoinHandle<()> {
-    let spinner = create_processor_progress();
+    if is_json_progress() {
+        return std::thread::spawn(move || emit_json_progress(progress_handle));
+    }
+
+    if is_quiet() {
+        return std::thread::spawn(|| {});
+    }
+
+    let mut spinner = create_processor_progress();
     spinner.set_message(initial_message.to_string());
-    let spinner_clone = spinner.clone();
+    let started = std::time::Instant::now();
 
     std::thread::spawn(move || {
+        let mut determinate = false;
+        let mut current_phase = None;
+
         while !progress_handle.is_complete() {
             let info = progress_handle.get_progress();
+            let phase = info.phase.name().to_string();
             let current_file = info.current_file.as_deref().unwrap_or("processing...");
 
-            spinner_clone.set_message(format!(
-                "{}: {:.1}% - {}",
-                info.phase.name(),
-                info.percentage.unwrap_or(0.0),
-                current_file
-            ));
+            // Each phase gets its own bar, so finished phases stay on screen
+            // with their duration.
+            if let Some(previous) = current_phase.take().filter(|previous| *previous != phase) {
+                let elapsed = spinner.elapsed();
+                finish_with_message(
+                    &spinner,
+                    format!("{} done in {:.1}s", previous, elapsed.as_secs_f64()),
+                );
+                spinner = create_processor_progress();
+                determinate = false;
+            }
+
+            match info.percentage {
+                Some(percentage) => {
+                    if !determinate {
+                        spinner.set_length(100);
+                        spinner.set_style(processor_bar_style());
+                        determinate = true;
+                    }
+                    spinner.set_position(percentage.clamp(0.0, 100.0).round() as u64);
+                }
+                None if determinate => {
+                    spinner.set_style(processor_spinner_style());
+                    determinate = false;
+                }
+                None => {}
+            }
+
+            spinner.set_message(format!("{} - {}", phase, current_file));
+            current_phase = Some(phase);
 
             std::thread::sleep(config::DEFAULT_PROGRESS_INTERVAL);
         }
-        spinner_clone.finish_with_message("Operation completed");
+        finish_with_message(
+            &spinner,
+            format!(
+                "Operation completed in {:.1}s",
+                started.elapsed().as_secs_f64()
+            ),
+        );
     })
 }
+
+/// Writes a `progress` event to stderr whenever the reported progress
+/// changes, then a single `complete` event once the operation is done.
+fn emit_json_progress(progress_handle: ProgressHandle) {
+    let mut last_event = None;
+
+    while !progress_handle.is_complete() {
+        let info = progress_handle.get_progress();
+        let event = serde_json::json!({
+            "event": "progress",
+            "phase": info.phase.name(),
+            "percentage": info.percentage,
+            "current_file": info.current_file,
+        });
+
+        if last_event.as_ref() != Some(&event) {
+            eprintln!("{}", event);
+            last_event = Some(event);
+        }
+
+        std::thread::sleep(config::DEFAULT_PROGRESS_INTERVAL);
+    }
+
+    eprintln!(
+        "{}",
+        serde_json::json!({
+            "event": "complete",
+            "phase": "complete",
+            "percentage": 100.0,
+            "current_file": null,
+        })
+    );
+}
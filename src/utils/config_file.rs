@@ -0,0 +1,177 @@
+use anyhow::{Context, Result};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, ValueEnum};
+use serde::{de, Deserialize, Deserializer};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commands::{DuplicateScanMode, DuplicatesArgs, OrganizeArgs};
+use crate::export::ExportFormat;
+
+pub const DEFAULT_CONFIG_FILE: &str = "image-manager.toml";
+
+/// Default options read from `image-manager.toml`. Flags given on the command
+/// line take precedence over these values.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    pub recursive: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_export_format")]
+    pub export_format: Option<ExportFormat>,
+    pub threshold: Option<f32>,
+    pub target_path: Option<PathBuf>,
+}
+
+impl ConfigFile {
+    /// Loads `path`, or `image-manager.toml` in the working directory when no
+    /// path is given. Only a missing default file is treated as empty.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let default_path = PathBuf::from(DEFAULT_CONFIG_FILE);
+                if !default_path.is_file() {
+                    return Ok(Self::default());
+                }
+                default_path
+            }
+        };
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+        Self::parse(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid config file {}: {}", path.display(), e))
+    }
+
+    fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(content)
+    }
+
+    pub fn apply_to_organize(&self, args: &mut OrganizeArgs, matches: &ArgMatches) {
+        if let Some(recursive) = self.recursive {
+            if !from_command_line(matches, "recursive") {
+                args.recursive = recursive;
+            }
+        }
+
+        if let Some(export_format) = self.export_format {
+            if !from_command_line(matches, "export_format") {
+                args.export.format = export_format;
+            }
+        }
+
+        if let Some(target_path) = &self.target_path {
+            if !from_command_line(matches, "target_path") {
+                args.target_path = Some(target_path.clone());
+            }
+        }
+    }
+
+    pub fn apply_to_duplicates(&self, args: &mut DuplicatesArgs, matches: &ArgMatches) {
+        if let Some(recursive) = self.recursive {
+            if !from_command_line(matches, "recursive") {
+                args.recursive = recursive;
+            }
+        }
+
+        if let Some(export_format) = self.export_format {
+            if !from_command_line(matches, "export_format") {
+                args.export.format = export_format;
+            }
+        }
+
+        // A config threshold must neither clash with a --sensitivity given on
+        // the command line nor warn about being ignored in exact mode.
+        if let Some(threshold) = self.threshold {
+            if !from_command_line(matches, "threshold")
+                && !from_command_line(matches, "sensitivity")
+                && args.mode != DuplicateScanMode::Exact
+            {
+                args.threshold = Some(threshold);
+            }
+        }
+    }
+}
+
+fn from_command_line(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+fn deserialize_export_format<'de, D>(deserializer: D) -> Result<Option<ExportFormat>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    ExportFormat::from_str(&value, true)
+        .map(Some)
+        .map_err(|_| de::Error::custom(format!("unknown export format {:?}", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_config() {
+        let config = ConfigFile::parse(
+            "recursive = true\nexport_format = \"json\"\nthreshold = 0.9\ntarget_path = \"/archive\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.recursive, Some(true));
+        assert_eq!(config.export_format, Some(ExportFormat::Json));
+        assert_eq!(config.threshold, Some(0.9));
+        assert_eq!(config.target_path, Some(PathBuf::from("/archive")));
+    }
+
+    #[test]
+    fn test_unknown_key_is_named_in_error() {
+        let error = ConfigFile::parse("recusive = true\n").unwrap_err();
+        assert!(error.to_string().contains("recusive"));
+    }
+
+    #[test]
+    fn test_invalid_value_is_rejected() {
+        assert!(ConfigFile::parse("recursive = \"yes\"\n").is_err());
+        assert!(ConfigFile::parse("export_format = \"pdf\"\n").is_err());
+    }
+
+    fn parse_duplicates(cli: &[&str]) -> (DuplicatesArgs, ArgMatches) {
+        use clap::{Args, FromArgMatches};
+
+        let matches = DuplicatesArgs::augment_args(clap::Command::new("duplicates"))
+            .try_get_matches_from(std::iter::once("duplicates").chain(cli.iter().copied()))
+            .unwrap();
+        let args = DuplicatesArgs::from_arg_matches(&matches).unwrap();
+        (args, matches)
+    }
+
+    #[test]
+    fn test_config_threshold_precedence() {
+        let config = ConfigFile::parse("threshold = 0.9\n").unwrap();
+
+        let (mut args, matches) = parse_duplicates(&["."]);
+        config.apply_to_duplicates(&mut args, &matches);
+        assert_eq!(args.threshold, Some(0.9));
+
+        let (mut args, matches) = parse_duplicates(&[".", "--threshold", "0.7"]);
+        config.apply_to_duplicates(&mut args, &matches);
+        assert_eq!(args.threshold, Some(0.7));
+
+        let (mut args, matches) = parse_duplicates(&[".", "--sensitivity", "high"]);
+        config.apply_to_duplicates(&mut args, &matches);
+        assert_eq!(args.threshold, None);
+
+        let (mut args, matches) = parse_duplicates(&[".", "--mode", "exact"]);
+        config.apply_to_duplicates(&mut args, &matches);
+        assert_eq!(args.threshold, None);
+    }
+
+    #[test]
+    fn test_missing_explicit_config_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(ConfigFile::load(Some(&temp_dir.path().join("missing.toml"))).is_err());
+    }
+}
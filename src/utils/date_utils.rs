@@ -1,12 +1,218 @@
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate};
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use super::exif_utils;
+use crate::commands::DateSource;
+
+const YEAR_RANGE: RangeInclusive<u32> = 1900..=2100;
+const MONTH_RANGE: RangeInclusive<u32> = 1..=12;
+const DAY_RANGE: RangeInclusive<u32> = 1..=31;
+
+/// Splits a `year-month-day` date (`-`, `/` or `.` separated) into its parts.
+/// A time after `T` or a space, as in ISO 8601 timestamps, is ignored.
+/// Parts that are not numbers in a plausible range make the whole date
+/// invalid, so they never end up as folder names. A `year-month` date with
+/// only month precision gets an empty day, which templates leave out.
 pub fn parse_date_string(date_str: &str) -> Option<(String, String, String)> {
-    let parts: Vec<&str> = date_str.split(['-', '/']).collect();
-    if parts.len() == 3 {
-        Some((
-            parts[0].to_string(),
-            parts[1].to_string(),
-            parts[2].to_string(),
-        ))
+    let date = date_str.trim().split(['T', ' ']).next()?;
+    let parts: Vec<&str> = date.split(['-', '/', '.']).collect();
+
+    match parts.as_slice() {
+        [year, month, day]
+            if is_in_range(year, YEAR_RANGE)
+                && is_in_range(month, MONTH_RANGE)
+                && is_in_range(day, DAY_RANGE) =>
+        {
+            Some((year.to_string(), month.to_string(), day.to_string()))
+        }
+        [year, month] if is_in_range(year, YEAR_RANGE) && is_in_range(month, MONTH_RANGE) => {
+            Some((year.to_string(), month.to_string(), String::new()))
+        }
+        _ => None,
+    }
+}
+
+fn is_in_range(part: &str, range: RangeInclusive<u32>) -> bool {
+    !part.is_empty()
+        && part.bytes().all(|byte| byte.is_ascii_digit())
+        && part.parse().is_ok_and(|value| range.contains(&value))
+}
+
+/// Returns the EXIF capture date of `path` as `YYYY-MM-DD`.
+pub fn exif_date(path: &Path) -> Option<String> {
+    let datetime = exif_utils::read_datetime_original(path)?;
+    datetime.get(..10).map(str::to_string)
+}
+
+/// Returns the local modification date of `path` as `YYYY-MM-DD`.
+pub fn modified_date(path: &Path) -> Option<String> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(
+        DateTime::<Local>::from(modified)
+            .format("%Y-%m-%d")
+            .to_string(),
+    )
+}
+
+/// Returns the date of `path` read from `source` as `YYYY-MM-DD`.
+pub fn source_date(path: &Path, source: DateSource) -> Option<String> {
+    match source {
+        DateSource::Exif => exif_date(path),
+        DateSource::Filename => path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(parse_filename_date),
+        DateSource::Mtime => modified_date(path),
+    }
+}
+
+/// Parses a `YYYY-MM-DD` date given on the command line.
+pub fn parse_cli_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map_err(|_| {
+        anyhow::anyhow!(
+            "Invalid date {:?} (expected YYYY-MM-DD, e.g. 2023-01-15)",
+            value
+        )
+    })
+}
+
+/// Extracts a date embedded in a file name, such as `IMG_20230115_0932.jpg`,
+/// `PXL_20230115123456.jpg` or `Screenshot 2023-01-15 at 09.32.png`, and
+/// returns it as `YYYY-MM-DD`.
+pub fn parse_filename_date(file_name: &str) -> Option<String> {
+    let bytes = file_name.as_bytes();
+    (0..bytes.len())
+        .filter(|&start| {
+            bytes[start].is_ascii_digit() && (start == 0 || !bytes[start - 1].is_ascii_digit())
+        })
+        .find_map(|start| date_at(&file_name[start..]))
+}
+
+fn date_at(text: &str) -> Option<String> {
+    let number = |start: usize, end: usize| -> Option<u32> {
+        text.get(start..end)
+            .filter(|part| part.bytes().all(|b| b.is_ascii_digit()))?
+            .parse()
+            .ok()
+    };
+
+    let bytes = text.as_bytes();
+    let separated =
+        bytes.len() >= 10 && matches!(bytes[4], b'-' | b'_' | b'.') && bytes[7] == bytes[4];
+
+    let (year, month, day) = if separated {
+        (number(0, 4)?, number(5, 7)?, number(8, 10)?)
     } else {
-        None
+        (number(0, 4)?, number(4, 6)?, number(6, 8)?)
+    };
+
+    if !(1900..=2100).contains(&year) {
+        return None;
+    }
+    let date = NaiveDate::from_ymd_opt(year as i32, month, day)?;
+    Some(date.format("%Y-%m-%d").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date_parts(year: &str, month: &str, day: &str) -> Option<(String, String, String)> {
+        Some((year.to_string(), month.to_string(), day.to_string()))
+    }
+
+    #[test]
+    fn test_parse_date_string() {
+        assert_eq!(
+            parse_date_string("2023-01-15"),
+            date_parts("2023", "01", "15")
+        );
+        assert_eq!(
+            parse_date_string("2023/01/15"),
+            date_parts("2023", "01", "15")
+        );
+        assert_eq!(
+            parse_date_string("2023.01.15"),
+            date_parts("2023", "01", "15")
+        );
+        assert_eq!(
+            parse_date_string("2023-01-15T10:30:00"),
+            date_parts("2023", "01", "15")
+        );
+        assert_eq!(
+            parse_date_string("2023-01-15 10:30:00"),
+            date_parts("2023", "01", "15")
+        );
+    }
+
+    #[test]
+    fn test_parse_date_string_month_precision() {
+        assert_eq!(parse_date_string("2023-01"), date_parts("2023", "01", ""));
+        assert_eq!(parse_date_string("2023/01"), date_parts("2023", "01", ""));
+        assert_eq!(parse_date_string("2023.01"), date_parts("2023", "01", ""));
+        assert_eq!(parse_date_string("2023-13"), None);
+        assert_eq!(parse_date_string("2023"), None);
+    }
+
+    #[test]
+    fn test_parse_date_string_rejects_malformed_input() {
+        assert_eq!(parse_date_string(""), None);
+        assert_eq!(parse_date_string("unknown"), None);
+        assert_eq!(parse_date_string("2023-01-15-16"), None);
+        assert_eq!(parse_date_string("2023--15"), None);
+        assert_eq!(parse_date_string("T10:30:00"), None);
+    }
+
+    #[test]
+    fn test_parse_date_string_rejects_implausible_parts() {
+        assert_eq!(parse_date_string("abc-def-ghi"), None);
+        assert_eq!(parse_date_string("2023-1a-15"), None);
+        assert_eq!(parse_date_string("2023-+1-15"), None);
+        assert_eq!(parse_date_string("1899-12-31"), None);
+        assert_eq!(parse_date_string("2101-01-01"), None);
+        assert_eq!(parse_date_string("2023-00-15"), None);
+        assert_eq!(parse_date_string("2023-13-15"), None);
+        assert_eq!(parse_date_string("2023-01-00"), None);
+        assert_eq!(parse_date_string("2023-01-32"), None);
+        assert_eq!(
+            parse_date_string("1900-12-31"),
+            date_parts("1900", "12", "31")
+        );
+    }
+
+    #[test]
+    fn test_parse_cli_date() {
+        assert_eq!(
+            parse_cli_date("2023-01-15").unwrap(),
+            NaiveDate::from_ymd_opt(2023, 1, 15).unwrap()
+        );
+        assert!(parse_cli_date("2023-02-30").is_err());
+        assert!(parse_cli_date("15.01.2023").is_err());
+        assert!(parse_cli_date("").is_err());
+    }
+
+    #[test]
+    fn test_parse_filename_date() {
+        assert_eq!(
+            parse_filename_date("IMG_20230115.jpg"),
+            Some("2023-01-15".to_string())
+        );
+        assert_eq!(
+            parse_filename_date("PXL_20230115_093212345.jpg"),
+            Some("2023-01-15".to_string())
+        );
+        assert_eq!(
+            parse_filename_date("Screenshot 2023-01-15 at 09.32.png"),
+            Some("2023-01-15".to_string())
+        );
+        assert_eq!(
+            parse_filename_date("DSC_0001_2022_12_31.jpg"),
+            Some("2022-12-31".to_string())
+        );
+        assert_eq!(parse_filename_date("DSC_0001.jpg"), None);
+        assert_eq!(parse_filename_date("IMG_20231345.jpg"), None);
+        assert_eq!(parse_filename_date("1673740800.jpg"), None);
     }
 }
@@ -0,0 +1,370 @@
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::commands::{GroupSort, KeepStrategy};
+use crate::utils::file_ops::file_size;
+use crate::utils::hash_utils;
+use crate::utils::scan_filter::{ScanFilter, SkippedFile};
+
+/// File extensions (lowercase) considered images when the CLI scans a
+/// directory itself rather than through the library.
+pub const IMAGE_EXTENSIONS: [&str; 9] = [
+    "jpg", "jpeg", "png", "gif", "tif", "tiff", "webp", "bmp", "ico",
+];
+
+/// The image files found by a scan, along with the files the scan filter
+/// skipped and the directories that could not be read.
+#[derive(Debug, Default)]
+pub struct ScannedFiles {
+    pub files: Vec<PathBuf>,
+    pub skipped: Vec<SkippedFile>,
+    pub errors: Vec<String>,
+}
+
+/// Collects the image files in `directories` that pass `filter`, sorted by
+/// path. A file reached through more than one path (overlapping directories
+/// or symlinks) is only listed under its first path, so it can never be
+/// reported as a duplicate of itself.
+pub fn scan_image_files(
+    directories: &[&Path],
+    recursive: bool,
+    filter: &ScanFilter,
+) -> ScannedFiles {
+    let mut scanned = ScannedFiles::default();
+    for directory in directories {
+        collect_image_files(directory, recursive, filter, &mut scanned);
+    }
+    scanned.files.sort();
+
+    let mut seen = HashSet::new();
+    scanned
+        .files
+        .retain(|file| seen.insert(fs::canonicalize(file).unwrap_or_else(|_| file.clone())));
+    scanned
+}
+
+/// Groups byte-identical `files` by SHA-256, only hashing files that share
+/// their size with another file. Returns the groups (files and groups sorted
+/// by path) and the files that could not be read.
+pub fn find_exact_duplicates(files: Vec<PathBuf>) -> (Vec<Vec<PathBuf>>, Vec<String>) {
+    let mut errors = Vec::new();
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    for file in files {
+        match fs::metadata(&file) {
+            Ok(metadata) => by_size.entry(metadata.len()).or_default().push(file),
+            Err(e) => errors.push(format!("{}: {}", file.display(), e)),
+        }
+    }
+
+    let candidates: Vec<PathBuf> = by_size
+        .into_values()
+        .filter(|files| files.len() > 1)
+        .flatten()
+        .collect();
+
+    let hashes: Vec<(PathBuf, std::io::Result<String>)> = candidates
+        .into_par_iter()
+        .map(|file| {
+            let hash = hash_utils::sha256_file(&file);
+            (file, hash)
+        })
+        .collect();
+
+    let mut by_hash: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for (file, hash) in hashes {
+        match hash {
+            Ok(hash) => by_hash.entry(hash).or_default().push(file),
+            Err(e) => errors.push(format!("{}: {}", file.display(), e)),
+        }
+    }
+
+    let mut groups: Vec<Vec<PathBuf>> = by_hash
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|mut group| {
+            group.sort();
+            group
+        })
+        .collect();
+    groups.sort();
+    errors.sort();
+
+    (groups, errors)
+}
+
+/// Adds the image files under `directory` to `scanned`, leaving out those
+/// `filter` skips and recording unreadable directories.
+pub fn collect_image_files(
+    directory: &Path,
+    recursive: bool,
+    filter: &ScanFilter,
+    scanned: &mut ScannedFiles,
+) {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(e) => {
+            scanned
+                .errors
+                .push(format!("{}: {}", directory.display(), e));
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            if recursive && filter.admit_directory(&path) {
+                collect_image_files(&path, recursive, filter, scanned);
+            }
+        } else if file_type.is_file()
+            && is_image_file(&path)
+            && filter.admit(&path, &mut scanned.skipped)
+        {
+            scanned.files.push(path);
+        }
+    }
+}
+
+/// Whether `group` holds at least one file from each of the two directories.
+pub fn spans_directories(group: &[PathBuf], source: &Path, compare: &Path) -> bool {
+    group.iter().any(|file| file.starts_with(source))
+        && group.iter().any(|file| file.starts_with(compare))
+}
+
+pub fn is_image_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Returns the index of the file in `group` that survives duplicate removal
+/// under `strategy`. Ties go to the earliest file in the group.
+pub fn select_survivor(group: &[PathBuf], strategy: KeepStrategy) -> usize {
+    let scores = group.iter().map(|file| match strategy {
+        KeepStrategy::First => 0,
+        KeepStrategy::Largest => u128::from(u64::MAX - file_size(file)),
+        KeepStrategy::Newest => u128::MAX - modified_nanos(file),
+        KeepStrategy::ShortestPath => file.components().count() as u128,
+    });
+
+    scores
+        .enumerate()
+        .min_by_key(|&(index, score)| (score, index))
+        .map_or(0, |(index, _)| index)
+}
+
+/// Bytes freed by removing every file in `group` except its survivor.
+pub fn reclaimable_bytes(group: &[PathBuf], strategy: KeepStrategy) -> u64 {
+    let survivor = select_survivor(group, strategy);
+    group
+        .iter()
+        .enumerate()
+        .filter(|&(index, _)| index != survivor)
+        .map(|(_, file)| file_size(file))
+        .sum()
+}
+
+/// Indices of `groups` in display order: most reclaimable bytes first for
+/// `Size` and most files first for `Count`. Ties keep the detection order.
+pub fn group_order(groups: &[Vec<PathBuf>], sort: GroupSort, keep: KeepStrategy) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..groups.len()).collect();
+    match sort {
+        GroupSort::Size => {
+            let savings: Vec<u64> = groups
+                .iter()
+                .map(|group| reclaimable_bytes(group, keep))
+                .collect();
+            order.sort_by_key(|&index| std::cmp::Reverse(savings[index]));
+        }
+        GroupSort::Count => order.sort_by_key(|&index| std::cmp::Reverse(groups[index].len())),
+    }
+    order
+}
+
+fn modified_nanos(path: &Path) -> u128 {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_nanos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filetime::{set_file_mtime, FileTime};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_select_survivor() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("nested");
+        fs::create_dir(&nested).unwrap();
+
+        let small_new = nested.join("small.jpg");
+        let large_old = nested.join("large.jpg");
+        let shallow = temp_dir.path().join("shallow.jpg");
+        fs::write(&small_new, vec![0u8; 10]).unwrap();
+        fs::write(&large_old, vec![0u8; 30]).unwrap();
+        fs::write(&shallow, vec![0u8; 30]).unwrap();
+        set_file_mtime(&small_new, FileTime::from_unix_time(2_000_000, 0)).unwrap();
+        set_file_mtime(&large_old, FileTime::from_unix_time(1_000_000, 0)).unwrap();
+        set_file_mtime(&shallow, FileTime::from_unix_time(1_500_000, 0)).unwrap();
+
+        let group = vec![small_new, large_old, shallow];
+        assert_eq!(select_survivor(&group, KeepStrategy::First), 0);
+        assert_eq!(select_survivor(&group, KeepStrategy::Largest), 1);
+        assert_eq!(select_survivor(&group, KeepStrategy::Newest), 0);
+        assert_eq!(select_survivor(&group, KeepStrategy::ShortestPath), 2);
+        assert_eq!(select_survivor(&[], KeepStrategy::Largest), 0);
+
+        assert_eq!(reclaimable_bytes(&group, KeepStrategy::First), 60);
+        assert_eq!(reclaimable_bytes(&group, KeepStrategy::Largest), 40);
+    }
+
+    #[test]
+    fn test_group_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let write = |name: &str, size: usize| {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, vec![0u8; size]).unwrap();
+            path
+        };
+
+        let groups = vec![
+            vec![
+                write("a1.jpg", 10),
+                write("a2.jpg", 10),
+                write("a3.jpg", 10),
+            ],
+            vec![write("b1.jpg", 50), write("b2.jpg", 50)],
+        ];
+
+        assert_eq!(
+            group_order(&groups, GroupSort::Size, KeepStrategy::First),
+            vec![1, 0]
+        );
+        assert_eq!(
+            group_order(&groups, GroupSort::Count, KeepStrategy::First),
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn test_find_exact_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("nested");
+        fs::create_dir(&nested).unwrap();
+
+        fs::write(temp_dir.path().join("a.jpg"), "same").unwrap();
+        fs::write(temp_dir.path().join("b.PNG"), "same").unwrap();
+        fs::write(temp_dir.path().join("c.jpg"), "diff").unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "same").unwrap();
+        fs::write(nested.join("d.jpg"), "same").unwrap();
+
+        let filter = ScanFilter::new(&[temp_dir.path()]);
+        let scanned = scan_image_files(&[temp_dir.path()], false, &filter);
+        assert!(scanned.errors.is_empty());
+        let (groups, errors) = find_exact_duplicates(scanned.files);
+        assert!(errors.is_empty());
+        assert_eq!(
+            groups,
+            vec![vec![
+                temp_dir.path().join("a.jpg"),
+                temp_dir.path().join("b.PNG")
+            ]]
+        );
+
+        let scanned = scan_image_files(&[temp_dir.path()], true, &filter);
+        let (groups, _) = find_exact_duplicates(scanned.files);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].len(), 3);
+        assert!(groups[0].contains(&nested.join("d.jpg")));
+    }
+
+    #[test]
+    fn test_scan_skips_filtered_files_before_hashing() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.jpg"), "same").unwrap();
+        fs::write(temp_dir.path().join("b.jpg"), "same").unwrap();
+        fs::write(temp_dir.path().join("large.jpg"), "much larger").unwrap();
+
+        let filter = ScanFilter::new(&[temp_dir.path()])
+            .with_exclude(&["b.jpg".to_string()])
+            .unwrap()
+            .with_size_range(None, Some(8));
+        let scanned = scan_image_files(&[temp_dir.path()], false, &filter);
+
+        assert_eq!(scanned.files, vec![temp_dir.path().join("a.jpg")]);
+        assert_eq!(scanned.skipped.len(), 2);
+        assert!(find_exact_duplicates(scanned.files).0.is_empty());
+    }
+
+    #[test]
+    fn test_scan_does_not_descend_into_pruned_folders() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for folder in ["2023/trip", "exports"] {
+            fs::create_dir_all(root.join(folder)).unwrap();
+            fs::write(root.join(folder).join("a.jpg"), "same").unwrap();
+        }
+        fs::write(root.join("2023").join("b.jpg"), "same").unwrap();
+
+        let filter = ScanFilter::new(&[root])
+            .with_exclude(&["exports".to_string()])
+            .unwrap()
+            .with_max_depth(Some(1));
+        let scanned = scan_image_files(&[root], true, &filter);
+
+        assert_eq!(scanned.files, vec![root.join("2023").join("b.jpg")]);
+        assert!(scanned.skipped.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_overlapping_symlinks_are_not_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let library = temp_dir.path().join("library");
+        let external = temp_dir.path().join("external");
+        fs::create_dir_all(&library).unwrap();
+        fs::create_dir_all(external.join("sub")).unwrap();
+        fs::write(external.join("sub").join("only.jpg"), "only copy").unwrap();
+        std::os::unix::fs::symlink(external.join("sub"), library.join("a_sub")).unwrap();
+        std::os::unix::fs::symlink(&external, library.join("b_ext")).unwrap();
+
+        let linked = crate::utils::symlinks::find_linked_directories(&[&library]);
+        let mut roots = vec![library.as_path()];
+        roots.extend(linked.directories.iter().map(PathBuf::as_path));
+        let scanned = scan_image_files(&roots, true, &ScanFilter::new(&roots));
+
+        assert_eq!(scanned.files.len(), 1);
+        assert!(find_exact_duplicates(scanned.files).0.is_empty());
+
+        // Overlapping roots are deduplicated even without the link check.
+        let external_sub = external.join("sub");
+        let overlapping = [external.as_path(), external_sub.as_path()];
+        let scanned = scan_image_files(&overlapping, true, &ScanFilter::new(&overlapping));
+        assert_eq!(scanned.files, vec![external.join("sub").join("only.jpg")]);
+    }
+
+    #[test]
+    fn test_spans_directories() {
+        let source = Path::new("/phone");
+        let compare = Path::new("/archive");
+        let both = vec![
+            PathBuf::from("/phone/a.jpg"),
+            PathBuf::from("/archive/a.jpg"),
+        ];
+        let one = vec![PathBuf::from("/phone/a.jpg"), PathBuf::from("/phone/b.jpg")];
+
+        assert!(spans_directories(&both, source, compare));
+        assert!(!spans_directories(&one, source, compare));
+    }
+}
@@ -0,0 +1,98 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::commands::ExifTag;
+
+pub fn read_exif(path: &Path) -> Option<exif::Exif> {
+    let file = File::open(path).ok()?;
+    let mut reader = BufReader::new(file);
+    exif::Reader::new().read_from_container(&mut reader).ok()
+}
+
+/// Reads `DateTimeOriginal` and formats it as `YYYY-MM-DDTHH:MM:SS`.
+pub fn read_datetime_original(path: &Path) -> Option<String> {
+    datetime_original(&read_exif(path)?)
+}
+
+/// Reads the camera `Model` tag, trimmed of padding.
+pub fn read_camera_model(path: &Path) -> Option<String> {
+    ascii_value(&read_exif(path)?, exif::Tag::Model)
+}
+
+/// Formats `tag` for display, or `None` if the image does not carry it.
+pub fn tag_value(exif: &exif::Exif, tag: ExifTag) -> Option<String> {
+    match tag {
+        ExifTag::Date => datetime_original(exif),
+        ExifTag::Make => ascii_value(exif, exif::Tag::Make),
+        ExifTag::Model => ascii_value(exif, exif::Tag::Model),
+        ExifTag::Lens => ascii_value(exif, exif::Tag::LensModel),
+        ExifTag::Iso => display_value(exif, exif::Tag::PhotographicSensitivity),
+        ExifTag::Exposure => display_value(exif, exif::Tag::ExposureTime),
+        ExifTag::Aperture => display_value(exif, exif::Tag::FNumber),
+        ExifTag::FocalLength => display_value(exif, exif::Tag::FocalLength),
+        ExifTag::Orientation => display_value(exif, exif::Tag::Orientation),
+        ExifTag::Gps => {
+            let latitude = gps_coordinate(exif, exif::Tag::GPSLatitude, exif::Tag::GPSLatitudeRef)?;
+            let longitude =
+                gps_coordinate(exif, exif::Tag::GPSLongitude, exif::Tag::GPSLongitudeRef)?;
+            Some(format!("{:.6}, {:.6}", latitude, longitude))
+        }
+    }
+}
+
+fn datetime_original(exif: &exif::Exif) -> Option<String> {
+    let field = exif.get_field(exif::Tag::DateTimeOriginal, exif::In::PRIMARY)?;
+
+    match &field.value {
+        exif::Value::Ascii(values) => {
+            let datetime = exif::DateTime::from_ascii(values.first()?).ok()?;
+            Some(format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                datetime.year,
+                datetime.month,
+                datetime.day,
+                datetime.hour,
+                datetime.minute,
+                datetime.second
+            ))
+        }
+        _ => None,
+    }
+}
+
+fn ascii_value(exif: &exif::Exif, tag: exif::Tag) -> Option<String> {
+    let field = exif.get_field(tag, exif::In::PRIMARY)?;
+
+    match &field.value {
+        exif::Value::Ascii(values) => {
+            let value = String::from_utf8_lossy(values.first()?);
+            let value = value.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+            (!value.is_empty()).then(|| value.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn display_value(exif: &exif::Exif, tag: exif::Tag) -> Option<String> {
+    let field = exif.get_field(tag, exif::In::PRIMARY)?;
+    Some(field.display_value().with_unit(exif).to_string())
+}
+
+/// Converts a degrees/minutes/seconds GPS tag to signed decimal degrees.
+fn gps_coordinate(exif: &exif::Exif, tag: exif::Tag, reference: exif::Tag) -> Option<f64> {
+    let field = exif.get_field(tag, exif::In::PRIMARY)?;
+    let exif::Value::Rational(parts) = &field.value else {
+        return None;
+    };
+
+    let degrees = parts
+        .iter()
+        .take(3)
+        .zip([1.0, 60.0, 3600.0])
+        .map(|(part, divisor)| part.to_f64() / divisor)
+        .sum::<f64>();
+
+    let negative = ascii_value(exif, reference).is_some_and(|r| r == "S" || r == "W");
+    Some(if negative { -degrees } else { degrees })
+}
@@ -1,37 +1,233 @@
 use anyhow::Result;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{self, ErrorKind};
 use std::path::{Path, PathBuf};
 
+use crate::commands::NamingPolicy;
+use crate::utils::hash_utils;
+
 pub mod config {
     pub const MAX_FILENAME_ATTEMPTS: usize = 1000;
+    /// Number of transfers between free-space checks for `--min-free-space`.
+    pub const FREE_SPACE_CHECK_INTERVAL: usize = 16;
 }
 
-pub fn get_unique_filename(target_path: &Path) -> Result<PathBuf> {
-    let stem = target_path
-        .file_stem()
-        .and_then(|s| s.to_str())
-        .unwrap_or("file");
-    let extension = target_path.extension().and_then(|ext| ext.to_str());
+/// Returns `target_path` if `is_taken` (usually `Path::exists`) reports it
+/// as free, otherwise a suffixed variant chosen by `naming`.
+///
+/// The timestamp policy only falls back to counting when the stamped name
+/// is itself taken, e.g. after two conflicts within the same second. The
+/// hash policy needs the source file (see `content_hashed_filename`) and
+/// counts like `Counter` here.
+pub fn get_unique_filename(
+    target_path: &Path,
+    naming: NamingPolicy,
+    is_taken: impl Fn(&Path) -> bool,
+) -> Result<PathBuf> {
+    if !is_taken(target_path) {
+        return Ok(target_path.to_path_buf());
+    }
+
+    match naming {
+        NamingPolicy::Counter | NamingPolicy::Hash => {
+            first_free(target_path, |n| format!("_{}", n), &is_taken)
+        }
+        NamingPolicy::Parenthetical => first_free(target_path, |n| format!(" ({})", n), &is_taken),
+        NamingPolicy::Timestamp => {
+            let stamped = with_stem_suffix(target_path, &format!("_{}", Utc::now().timestamp()));
+            if !is_taken(&stamped) {
+                return Ok(stamped);
+            }
+            first_free(&stamped, |n| format!("_{}", n), &is_taken)
+        }
+    }
+}
 
-    let mut counter = 1;
-    let mut new_path = target_path.to_path_buf();
+/// Appends the first 8 hex digits of the SHA-256 of `source` to the file
+/// stem, so identical files always map to the same name.
+pub fn content_hashed_filename(target_path: &Path, source: &Path) -> Result<PathBuf> {
+    let digest = hash_utils::sha256_file(source)?;
+    Ok(with_stem_suffix(target_path, &format!("_{}", &digest[..8])))
+}
+
+/// Race-safe counterpart of `get_unique_filename`: reserves the name by
+/// creating the file with `create_new`, so a concurrent writer can never
+/// pick the same path between the check and the write.
+pub fn create_unique_file(target_path: &Path, naming: NamingPolicy) -> Result<(PathBuf, File)> {
+    let mut taken = HashSet::new();
+    loop {
+        let candidate = get_unique_filename(target_path, naming, |path| taken.contains(path))?;
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&candidate)
+        {
+            Ok(file) => return Ok((candidate, file)),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                taken.insert(candidate);
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+}
+
+/// Copies `source` into a name reserved with `create_unique_file` and
+/// returns where it landed. The reserved file is removed if the copy fails.
+pub fn copy_to_unique_file(
+    source: &Path,
+    target_path: &Path,
+    naming: NamingPolicy,
+) -> Result<PathBuf> {
+    let (destination, mut file) = create_unique_file(target_path, naming)?;
+    let copied = File::open(source)
+        .and_then(|mut reader| io::copy(&mut reader, &mut file))
+        .and_then(|_| file.set_permissions(std::fs::metadata(source)?.permissions()));
+    if let Err(e) = copied {
+        drop(file);
+        let _ = std::fs::remove_file(&destination);
+        return Err(e.into());
+    }
+    Ok(destination)
+}
 
-    while new_path.exists() {
-        let new_stem = format!("{}_{}", stem, counter);
-        new_path = target_path.with_file_name(new_stem);
-        if let Some(ext) = extension {
-            new_path = new_path.with_extension(ext);
+fn first_free(
+    target_path: &Path,
+    suffix: impl Fn(usize) -> String,
+    is_taken: &dyn Fn(&Path) -> bool,
+) -> Result<PathBuf> {
+    for counter in 1..=config::MAX_FILENAME_ATTEMPTS {
+        let new_path = with_stem_suffix(target_path, &suffix(counter));
+        if !is_taken(&new_path) {
+            return Ok(new_path);
         }
-        counter += 1;
+    }
+
+    Err(anyhow::anyhow!(
+        "Too many files with similar names exist (limit: {})",
+        config::MAX_FILENAME_ATTEMPTS
+    ))
+}
+
+fn with_stem_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("file");
+    let mut new_path = path.with_file_name(format!("{}{}", stem, suffix));
+    if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
+        new_path = new_path.with_extension(ext);
+    }
+    new_path
+}
+
+/// Moves a file, falling back to copy-then-remove when a plain rename is not
+/// possible (e.g. when source and destination are on different filesystems).
+pub fn move_file(source: &Path, destination: &Path) -> Result<()> {
+    if std::fs::rename(source, destination).is_ok() {
+        return Ok(());
+    }
+
+    std::fs::copy(source, destination)?;
+    if let Err(e) = std::fs::remove_file(source) {
+        return Err(anyhow::anyhow!(
+            "copied to {} but failed to remove original: {}",
+            destination.display(),
+            e
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn symlink_file(source: &Path, destination: &Path) -> Result<()> {
+    let source = source.canonicalize()?;
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&source, destination)?;
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_file(&source, destination)?;
 
-        if counter > config::MAX_FILENAME_ATTEMPTS {
+    Ok(())
+}
+
+pub fn hard_link_file(source: &Path, destination: &Path) -> Result<()> {
+    match std::fs::hard_link(source, destination) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => Err(anyhow::anyhow!(
+            "source and target are on different filesystems; hardlinks cannot cross filesystems (use --link-mode copy or symlink)"
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Replaces `duplicate` with a hardlink to `original`. The link is created
+/// under a temporary name first and renamed over the duplicate, so the
+/// duplicate is never lost if linking fails. Returns false if both paths
+/// already point to the same file.
+pub fn replace_with_hard_link(original: &Path, duplicate: &Path) -> Result<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let original_metadata = std::fs::metadata(original)?;
+        let duplicate_metadata = std::fs::metadata(duplicate)?;
+        if original_metadata.dev() != duplicate_metadata.dev() {
             return Err(anyhow::anyhow!(
-                "Too many files with similar names exist (limit: {})",
-                config::MAX_FILENAME_ATTEMPTS
+                "{} is on a different filesystem than {}; hardlinks cannot cross filesystems",
+                duplicate.display(),
+                original.display()
             ));
         }
+        if original_metadata.ino() == duplicate_metadata.ino() {
+            return Ok(false);
+        }
     }
 
-    Ok(new_path)
+    let file_name = duplicate
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("file");
+    let temp_path = get_unique_filename(
+        &duplicate.with_file_name(format!(".{}.hardlink-tmp", file_name)),
+        NamingPolicy::Counter,
+        Path::exists,
+    )?;
+
+    hard_link_file(original, &temp_path)?;
+    if let Err(e) = std::fs::rename(&temp_path, duplicate) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+
+    Ok(true)
+}
+
+/// Returns the ancestors of `dir` (including `dir` itself) that do not exist
+/// yet, outermost first.
+pub fn missing_directories(dir: &Path) -> Vec<PathBuf> {
+    let mut missing: Vec<PathBuf> = dir
+        .ancestors()
+        .take_while(|ancestor| !ancestor.as_os_str().is_empty() && !ancestor.exists())
+        .map(Path::to_path_buf)
+        .collect();
+    missing.reverse();
+    missing
+}
+
+/// Size of `path` in bytes, or 0 if it cannot be read.
+pub fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0)
+}
+
+/// Free space on the volume holding `path`, which may not exist yet (its
+/// nearest existing ancestor is queried instead).
+pub fn available_space(path: &Path) -> std::io::Result<u64> {
+    let existing = path
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or_else(|| Path::new("."));
+    fs2::available_space(existing)
 }
 
 pub fn get_target_directory(base_path: &Path) -> Result<PathBuf> {
@@ -49,18 +245,196 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let base_path = temp_dir.path().join("test.txt");
 
-        let result = get_unique_filename(&base_path).unwrap();
+        let result = get_unique_filename(&base_path, NamingPolicy::Counter, Path::exists).unwrap();
         assert_eq!(result, base_path);
 
         fs::write(&base_path, "test").unwrap();
-        let result = get_unique_filename(&base_path).unwrap();
+        let result = get_unique_filename(&base_path, NamingPolicy::Counter, Path::exists).unwrap();
         assert_eq!(result, temp_dir.path().join("test_1.txt"));
 
         fs::write(&result, "test").unwrap();
-        let result2 = get_unique_filename(&base_path).unwrap();
+        let result2 = get_unique_filename(&base_path, NamingPolicy::Counter, Path::exists).unwrap();
         assert_eq!(result2, temp_dir.path().join("test_2.txt"));
     }
 
+    #[test]
+    fn test_get_unique_filename_with_claimed_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().join("test.txt");
+        let claimed = [base_path.clone(), temp_dir.path().join("test_1.txt")];
+
+        let result = get_unique_filename(&base_path, NamingPolicy::Counter, |path| {
+            claimed.iter().any(|c| c == path)
+        })
+        .unwrap();
+        assert_eq!(result, temp_dir.path().join("test_2.txt"));
+    }
+
+    #[test]
+    fn test_get_unique_filename_parenthetical() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().join("test.txt");
+        fs::write(&base_path, "test").unwrap();
+        fs::write(temp_dir.path().join("test (1).txt"), "test").unwrap();
+
+        let result =
+            get_unique_filename(&base_path, NamingPolicy::Parenthetical, Path::exists).unwrap();
+        assert_eq!(result, temp_dir.path().join("test (2).txt"));
+    }
+
+    #[test]
+    fn test_get_unique_filename_timestamp() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().join("test.txt");
+        fs::write(&base_path, "test").unwrap();
+
+        let result =
+            get_unique_filename(&base_path, NamingPolicy::Timestamp, Path::exists).unwrap();
+        let stem = result.file_stem().unwrap().to_str().unwrap();
+        let stamp = stem.strip_prefix("test_").unwrap();
+        assert!(stamp.len() >= 10 && stamp.chars().all(|c| c.is_ascii_digit()));
+        assert_eq!(result.extension().unwrap(), "txt");
+
+        // A second conflict in the same second gets a counter on top.
+        fs::write(&result, "test").unwrap();
+        let claimed = [base_path.clone(), result.clone()];
+        let second = get_unique_filename(&base_path, NamingPolicy::Timestamp, |path| {
+            claimed.iter().any(|c| c == path)
+        })
+        .unwrap();
+        assert_ne!(second, result);
+        assert!(!claimed.contains(&second));
+    }
+
+    #[test]
+    fn test_content_hashed_filename() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = temp_dir.path().join("first.jpg");
+        let same = temp_dir.path().join("same.jpg");
+        let other = temp_dir.path().join("other.jpg");
+        fs::write(&first, "image").unwrap();
+        fs::write(&same, "image").unwrap();
+        fs::write(&other, "another image").unwrap();
+
+        let target = temp_dir.path().join("out").join("photo.jpg");
+        let name = content_hashed_filename(&target, &first).unwrap();
+        assert_eq!(name.parent(), target.parent());
+        assert_eq!(name.extension().unwrap(), "jpg");
+        let stem = name.file_stem().unwrap().to_str().unwrap();
+        let suffix = stem.strip_prefix("photo_").unwrap();
+        assert_eq!(suffix.len(), 8);
+        assert!(suffix.chars().all(|c| c.is_ascii_hexdigit()));
+
+        assert_eq!(content_hashed_filename(&target, &same).unwrap(), name);
+        assert_ne!(content_hashed_filename(&target, &other).unwrap(), name);
+    }
+
+    #[test]
+    fn test_create_unique_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().join("test.txt");
+
+        let (first, _) = create_unique_file(&base_path, NamingPolicy::Counter).unwrap();
+        assert_eq!(first, base_path);
+        assert!(first.exists());
+
+        let (second, _) = create_unique_file(&base_path, NamingPolicy::Counter).unwrap();
+        assert_eq!(second, temp_dir.path().join("test_1.txt"));
+    }
+
+    #[test]
+    fn test_copy_to_unique_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.jpg");
+        let target = temp_dir.path().join("photo.jpg");
+        fs::write(&source, "image").unwrap();
+        fs::write(&target, "existing").unwrap();
+
+        let destination = copy_to_unique_file(&source, &target, NamingPolicy::Counter).unwrap();
+        assert_eq!(destination, temp_dir.path().join("photo_1.jpg"));
+        assert_eq!(fs::read_to_string(&destination).unwrap(), "image");
+        assert_eq!(fs::read_to_string(&target).unwrap(), "existing");
+
+        let missing = temp_dir.path().join("missing.jpg");
+        assert!(copy_to_unique_file(&missing, &target, NamingPolicy::Counter).is_err());
+        assert!(!temp_dir.path().join("photo_2.jpg").exists());
+    }
+
+    #[test]
+    fn test_move_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.jpg");
+        let destination = temp_dir.path().join("destination.jpg");
+        fs::write(&source, "image").unwrap();
+
+        move_file(&source, &destination).unwrap();
+
+        assert!(!source.exists());
+        assert_eq!(fs::read_to_string(&destination).unwrap(), "image");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_and_hard_link_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.jpg");
+        let symlink = temp_dir.path().join("symlink.jpg");
+        let hardlink = temp_dir.path().join("hardlink.jpg");
+        fs::write(&source, "image").unwrap();
+
+        symlink_file(&source, &symlink).unwrap();
+        hard_link_file(&source, &hardlink).unwrap();
+
+        assert!(fs::symlink_metadata(&symlink)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_to_string(&symlink).unwrap(), "image");
+        assert_eq!(fs::read_to_string(&hardlink).unwrap(), "image");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_replace_with_hard_link() {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let original = temp_dir.path().join("original.jpg");
+        let duplicate = temp_dir.path().join("duplicate.jpg");
+        fs::write(&original, "image").unwrap();
+        fs::write(&duplicate, "image").unwrap();
+
+        assert!(replace_with_hard_link(&original, &duplicate).unwrap());
+        assert!(!replace_with_hard_link(&original, &duplicate).unwrap());
+
+        let original_inode = fs::metadata(&original).unwrap().ino();
+        assert_eq!(fs::metadata(&duplicate).unwrap().ino(), original_inode);
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_missing_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let nested = temp_dir.path().join("a").join("b");
+
+        assert_eq!(
+            missing_directories(&nested),
+            vec![temp_dir.path().join("a"), nested.clone()]
+        );
+        assert!(missing_directories(temp_dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_available_space_of_missing_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("not").join("yet");
+
+        assert_eq!(
+            available_space(&missing).unwrap(),
+            available_space(temp_dir.path()).unwrap()
+        );
+    }
+
     #[test]
     fn test_get_target_directory() {
         let temp_dir = TempDir::new().unwrap();
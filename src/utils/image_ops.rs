@@ -0,0 +1,187 @@
+use anyhow::{Context, Result};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+pub const DEFAULT_JPEG_QUALITY: u8 = 85;
+
+/// Decodes `source` and writes it to `destination` as `format`. `quality`
+/// (1-100) only affects JPEG output; PNG and WebP are written losslessly.
+pub fn convert_image(
+    source: &Path,
+    destination: &Path,
+    format: ImageFormat,
+    quality: u8,
+) -> Result<()> {
+    let image =
+        image::open(source).with_context(|| format!("Failed to decode {}", source.display()))?;
+
+    write_image(&image, destination, format, quality)
+}
+
+/// Original and resulting dimensions of a resized image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResizeOutcome {
+    pub original: (u32, u32),
+    pub resized: (u32, u32),
+}
+
+impl ResizeOutcome {
+    pub fn was_resized(&self) -> bool {
+        self.original != self.resized
+    }
+}
+
+/// The largest size with the aspect ratio of `width` x `height` that fits
+/// within the given bounds. Images are never upscaled.
+pub fn fit_within(
+    width: u32,
+    height: u32,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+) -> (u32, u32) {
+    let scale_for = |size: u32, max: Option<u32>| {
+        max.filter(|&max| size > max)
+            .map_or(1.0, |max| f64::from(max) / f64::from(size))
+    };
+    let scale = scale_for(width, max_width).min(scale_for(height, max_height));
+    if scale >= 1.0 {
+        return (width, height);
+    }
+
+    let scaled = |size: u32| ((f64::from(size) * scale).round() as u32).max(1);
+    (scaled(width), scaled(height))
+}
+
+/// Writes a copy of `source` to `destination` that fits within the bounds,
+/// resampled with Lanczos3. Images already within bounds are copied as-is.
+pub fn resize_image(
+    source: &Path,
+    destination: &Path,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+) -> Result<ResizeOutcome> {
+    let original = image::image_dimensions(source)
+        .with_context(|| format!("Failed to read dimensions of {}", source.display()))?;
+    let resized = fit_within(original.0, original.1, max_width, max_height);
+    let outcome = ResizeOutcome { original, resized };
+
+    if !outcome.was_resized() {
+        std::fs::copy(source, destination)
+            .with_context(|| format!("Failed to copy {}", source.display()))?;
+        return Ok(outcome);
+    }
+
+    let format = ImageFormat::from_path(destination)
+        .with_context(|| format!("Unsupported output format: {}", destination.display()))?;
+    let image =
+        image::open(source).with_context(|| format!("Failed to decode {}", source.display()))?;
+
+    write_image(
+        &image.resize_exact(resized.0, resized.1, FilterType::Lanczos3),
+        destination,
+        format,
+        DEFAULT_JPEG_QUALITY,
+    )?;
+    Ok(outcome)
+}
+
+/// Encodes `image` to `destination`, removing the partial output on failure.
+fn write_image(
+    image: &DynamicImage,
+    destination: &Path,
+    format: ImageFormat,
+    quality: u8,
+) -> Result<()> {
+    let file = File::create(destination)
+        .with_context(|| format!("Failed to create {}", destination.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    let result = if format == ImageFormat::Jpeg {
+        // JPEG has no alpha channel.
+        JpegEncoder::new_with_quality(&mut writer, quality)
+            .encode_image(&image.to_rgb8())
+            .map_err(anyhow::Error::from)
+    } else {
+        image
+            .write_to(&mut writer, format)
+            .map_err(anyhow::Error::from)
+    };
+
+    if let Err(e) = result {
+        drop(writer);
+        let _ = std::fs::remove_file(destination);
+        return Err(e.context(format!("Failed to encode {}", destination.display())));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_convert_image() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.png");
+        RgbaImage::from_pixel(4, 3, Rgba([200, 10, 10, 128]))
+            .save(&source)
+            .unwrap();
+
+        let jpeg = temp_dir.path().join("converted.jpg");
+        convert_image(&source, &jpeg, ImageFormat::Jpeg, 80).unwrap();
+        assert_eq!(image::image_dimensions(&jpeg).unwrap(), (4, 3));
+
+        let webp = temp_dir.path().join("converted.webp");
+        convert_image(&source, &webp, ImageFormat::WebP, 80).unwrap();
+        assert_eq!(image::image_dimensions(&webp).unwrap(), (4, 3));
+    }
+
+    #[test]
+    fn test_fit_within() {
+        assert_eq!(fit_within(4000, 3000, Some(1600), None), (1600, 1200));
+        assert_eq!(fit_within(4000, 3000, None, Some(1500)), (2000, 1500));
+        assert_eq!(fit_within(4000, 3000, Some(1600), Some(600)), (800, 600));
+        assert_eq!(fit_within(800, 600, Some(1600), Some(1200)), (800, 600));
+        assert_eq!(fit_within(1000, 1, Some(10), None), (10, 1));
+    }
+
+    #[test]
+    fn test_resize_image() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source.png");
+        RgbaImage::from_pixel(40, 20, Rgba([0, 0, 255, 255]))
+            .save(&source)
+            .unwrap();
+
+        let resized = temp_dir.path().join("resized.png");
+        let outcome = resize_image(&source, &resized, Some(10), None).unwrap();
+        assert_eq!(outcome.resized, (10, 5));
+        assert_eq!(image::image_dimensions(&resized).unwrap(), (10, 5));
+
+        let copied = temp_dir.path().join("copied.png");
+        let outcome = resize_image(&source, &copied, Some(100), Some(100)).unwrap();
+        assert!(!outcome.was_resized());
+        assert_eq!(
+            std::fs::read(&copied).unwrap(),
+            std::fs::read(&source).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_convert_image_rejects_undecodable_source() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("broken.png");
+        let destination = temp_dir.path().join("broken.jpg");
+        std::fs::write(&source, "not an image").unwrap();
+
+        assert!(convert_image(&source, &destination, ImageFormat::Jpeg, 80).is_err());
+        assert!(!destination.exists());
+    }
+}
@@ -1,3 +1,16 @@
+pub mod config_file;
 pub mod date_utils;
+pub mod duplicate_utils;
+pub mod exif_utils;
 pub mod file_ops;
+pub mod hash_utils;
+pub mod image_ops;
+pub mod operation_log;
+pub mod path_template;
+pub mod prompt;
+pub mod scan_filter;
+pub mod size_utils;
+pub mod staged_tree;
+pub mod symlinks;
+pub mod undo_log;
 pub mod validation;
@@ -0,0 +1,73 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+static LOG_FILE: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// Opens `path` for appending; from then on every recorded action is written
+/// to it as one JSON line. Lines go straight to the file without buffering,
+/// so a run that is interrupted still leaves everything up to that point.
+pub fn open(path: &Path) -> Result<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file: {}", path.display()))?;
+
+    LOG_FILE
+        .set(Mutex::new(file))
+        .map_err(|_| anyhow::anyhow!("Log file is already open"))
+}
+
+/// Records an action on `path`, e.g. `copied` with the destination as the
+/// detail. Does nothing without `--log-file`.
+pub fn record(action: &str, path: &Path, detail: Option<&str>) {
+    write_line(serde_json::json!({
+        "time": Local::now().to_rfc3339(),
+        "action": action,
+        "path": path,
+        "detail": detail,
+    }));
+}
+
+/// Records an event that concerns the whole run rather than one file.
+pub fn record_run(action: &str, detail: Option<&str>) {
+    write_line(serde_json::json!({
+        "time": Local::now().to_rfc3339(),
+        "action": action,
+        "detail": detail,
+    }));
+}
+
+/// Records how the run ended and flushes the log to disk.
+pub fn finish(result: &Result<()>) {
+    match result {
+        Ok(()) => record_run("completed", None),
+        Err(e) => record_run("failed", Some(&e.to_string())),
+    }
+    sync();
+}
+
+/// Flushes the log to disk, e.g. before exiting on Ctrl-C.
+pub fn sync() {
+    if let Some(file) = LOG_FILE.get() {
+        if let Ok(file) = file.lock() {
+            let _ = file.sync_all();
+        }
+    }
+}
+
+fn write_line(line: serde_json::Value) {
+    let Some(file) = LOG_FILE.get() else {
+        return;
+    };
+
+    if let Ok(mut file) = file.lock() {
+        if let Err(e) = writeln!(file, "{}", line).and_then(|_| file.flush()) {
+            log::warn!("Failed to write to log file: {}", e);
+        }
+    }
+}
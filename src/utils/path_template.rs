@@ -0,0 +1,331 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use super::{date_utils, exif_utils};
+
+pub const DEFAULT_TEMPLATE: &str = "{year}/{month}/{day}";
+pub const TEMPLATE_TOKENS: &[&str] = &["year", "month", "day", "ext", "camera"];
+/// Places every file directly in the target directory.
+pub const FLAT_TEMPLATE: &str = "";
+pub const UNKNOWN_CAMERA: &str = "Unknown-Camera";
+
+pub const DEFAULT_RENAME_TEMPLATE: &str = "{date}_{seq}.{ext}";
+pub const RENAME_TOKENS: &[&str] = &["date", "seq", "ext", "orig"];
+
+pub struct TemplateValues<'a> {
+    pub year: &'a str,
+    pub month: &'a str,
+    pub day: &'a str,
+    pub ext: &'a str,
+    pub camera: &'a str,
+}
+
+impl TemplateValues<'_> {
+    fn get(&self, token: &str) -> Option<&str> {
+        match token {
+            "year" => Some(self.year),
+            "month" => Some(self.month),
+            "day" => Some(self.day),
+            "ext" => Some(self.ext),
+            "camera" => Some(self.camera),
+            _ => None,
+        }
+    }
+}
+
+pub fn validate_template(template: &str) -> Result<()> {
+    if template.trim().is_empty() {
+        return Err(anyhow::anyhow!("Template cannot be empty"));
+    }
+
+    validate_tokens(template, TEMPLATE_TOKENS)?;
+
+    if template.starts_with('/') || template.split('/').any(|component| component == "..") {
+        return Err(anyhow::anyhow!(
+            "Template must be a relative path without '..' components: {}",
+            template
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks a `rename --template`: it must produce a bare file name, so path
+/// separators are rejected.
+pub fn validate_rename_template(template: &str) -> Result<()> {
+    if template.trim().is_empty() {
+        return Err(anyhow::anyhow!("Template cannot be empty"));
+    }
+
+    validate_tokens(template, RENAME_TOKENS)?;
+
+    if template.contains(['/', '\\']) {
+        return Err(anyhow::anyhow!(
+            "Rename template must not contain path separators: {}",
+            template
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_tokens(template: &str, tokens: &[&str]) -> Result<()> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after_open = &rest[start + 1..];
+        let end = after_open
+            .find('}')
+            .ok_or_else(|| anyhow::anyhow!("Unclosed '{{' in template: {}", template))?;
+
+        let token = &after_open[..end];
+        if !tokens.contains(&token) {
+            return Err(anyhow::anyhow!(
+                "Unknown template token {{{}}}; supported tokens: {}",
+                token,
+                tokens
+                    .iter()
+                    .map(|t| format!("{{{}}}", t))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        rest = &after_open[end + 1..];
+    }
+
+    if rest.contains('}') {
+        return Err(anyhow::anyhow!("Unmatched '}}' in template: {}", template));
+    }
+
+    Ok(())
+}
+
+/// Expands a validated template into a relative directory path.
+pub fn render_template(template: &str, values: &TemplateValues) -> PathBuf {
+    expand_tokens(template, |token| values.get(token))
+        .split('/')
+        .filter(|component| !component.is_empty())
+        .collect()
+}
+
+/// Expands a validated rename template into a file name. `seq` is the
+/// 1-based position of the file within its date, zero-padded to four digits.
+pub fn render_file_name(template: &str, date: &str, seq: usize, file: &Path) -> String {
+    let seq = format!("{:04}", seq);
+    let ext = file
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_default();
+    let orig = file
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+
+    let name = expand_tokens(template, |token| match token {
+        "date" => Some(date),
+        "seq" => Some(seq.as_str()),
+        "ext" => Some(ext.as_str()),
+        "orig" => Some(orig),
+        _ => None,
+    });
+
+    // Files without an extension would otherwise end in a dangling dot.
+    match name.strip_suffix('.') {
+        Some(stripped) if ext.is_empty() && template.ends_with(".{ext}") => stripped.to_string(),
+        _ => name,
+    }
+}
+
+fn expand_tokens<'a>(template: &str, value: impl Fn(&str) -> Option<&'a str>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after_open = &rest[start + 1..];
+        match after_open.find('}') {
+            Some(end) => {
+                let token = &after_open[..end];
+                rendered.push_str(value(token).unwrap_or_default());
+                rest = &after_open[end + 1..];
+            }
+            None => {
+                rendered.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Resolves the directory, relative to the target root, that `file` from the
+/// `date` group is placed in. Returns `None` if the template needs date
+/// tokens and the date cannot be parsed.
+pub fn destination_subdir(template: &str, date: &str, file: &Path) -> Option<PathBuf> {
+    let date_parts = date_utils::parse_date_string(date);
+    let uses_date = ["{year}", "{month}", "{day}"]
+        .iter()
+        .any(|token| template.contains(token));
+    if date_parts.is_none() && uses_date {
+        return None;
+    }
+    let (year, month, day) = date_parts.unwrap_or_default();
+    let extension = file
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .unwrap_or_else(|| "other".to_string());
+
+    let camera = if template.contains("{camera}") {
+        exif_utils::read_camera_model(file)
+            .and_then(|model| sanitize_folder_name(&model))
+            .unwrap_or_else(|| UNKNOWN_CAMERA.to_string())
+    } else {
+        String::new()
+    };
+
+    let values = TemplateValues {
+        year: &year,
+        month: &month,
+        day: &day,
+        ext: &extension,
+        camera: &camera,
+    };
+    Some(render_template(template, &values))
+}
+
+/// Makes a metadata string usable as a single folder name by stripping path
+/// separators and surrounding whitespace.
+pub fn sanitize_folder_name(name: &str) -> Option<String> {
+    let sanitized: String = name
+        .chars()
+        .filter(|c| !matches!(c, '/' | '\\') && !c.is_control())
+        .collect();
+    let sanitized = sanitized.trim();
+
+    if sanitized.is_empty() || sanitized == "." || sanitized == ".." {
+        None
+    } else {
+        Some(sanitized.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values() -> TemplateValues<'static> {
+        TemplateValues {
+            year: "2023",
+            month: "01",
+            day: "15",
+            ext: "jpg",
+            camera: "X-T4",
+        }
+    }
+
+    #[test]
+    fn test_render_default_template() {
+        assert_eq!(
+            render_template(DEFAULT_TEMPLATE, &values()),
+            PathBuf::from("2023").join("01").join("15")
+        );
+    }
+
+    #[test]
+    fn test_render_custom_template() {
+        assert_eq!(
+            render_template("{year}-{month}/{ext}", &values()),
+            PathBuf::from("2023-01").join("jpg")
+        );
+        assert_eq!(
+            render_template("{camera}/{year}", &values()),
+            PathBuf::from("X-T4").join("2023")
+        );
+    }
+
+    #[test]
+    fn test_sanitize_folder_name() {
+        assert_eq!(
+            sanitize_folder_name("  Canon EOS R5  "),
+            Some("Canon EOS R5".to_string())
+        );
+        assert_eq!(
+            sanitize_folder_name("DSC/RX100\\"),
+            Some("DSCRX100".to_string())
+        );
+        assert_eq!(sanitize_folder_name("  "), None);
+        assert_eq!(sanitize_folder_name(".."), None);
+    }
+
+    #[test]
+    fn test_destination_subdir() {
+        let file = Path::new("/photos/IMG_001.JPG");
+        assert_eq!(
+            destination_subdir("{year}/{month}", "2023-01-15", file),
+            Some(PathBuf::from("2023").join("01"))
+        );
+        assert_eq!(
+            destination_subdir("{ext}", "2023-01-15", Path::new("/photos/README")),
+            Some(PathBuf::from("other"))
+        );
+        assert_eq!(
+            destination_subdir(DEFAULT_TEMPLATE, "2023.01.15", file),
+            Some(PathBuf::from("2023").join("01").join("15"))
+        );
+        assert_eq!(
+            destination_subdir(DEFAULT_TEMPLATE, "2023-01", file),
+            Some(PathBuf::from("2023").join("01"))
+        );
+        assert_eq!(destination_subdir("{year}", "unknown", file), None);
+        assert_eq!(
+            destination_subdir("{camera}", "2023-01-15", file),
+            Some(PathBuf::from(UNKNOWN_CAMERA))
+        );
+        assert_eq!(
+            destination_subdir(FLAT_TEMPLATE, "unknown", file),
+            Some(PathBuf::new())
+        );
+    }
+
+    #[test]
+    fn test_render_file_name() {
+        let file = Path::new("/photos/DSC0001.JPG");
+        assert_eq!(
+            render_file_name(DEFAULT_RENAME_TEMPLATE, "2023-01-15", 1, file),
+            "2023-01-15_0001.jpg"
+        );
+        assert_eq!(
+            render_file_name("{orig}-{date}.{ext}", "2023-01-15", 12, file),
+            "DSC0001-2023-01-15.jpg"
+        );
+        assert_eq!(
+            render_file_name(DEFAULT_RENAME_TEMPLATE, "2023-01-15", 3, Path::new("raw")),
+            "2023-01-15_0003"
+        );
+    }
+
+    #[test]
+    fn test_validate_rename_template() {
+        assert!(validate_rename_template(DEFAULT_RENAME_TEMPLATE).is_ok());
+        assert!(validate_rename_template("{orig}_{seq}.{ext}").is_ok());
+        assert!(validate_rename_template("{year}.{ext}").is_err());
+        assert!(validate_rename_template("{date}/{seq}.{ext}").is_err());
+        assert!(validate_rename_template("").is_err());
+    }
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template(DEFAULT_TEMPLATE).is_ok());
+        assert!(validate_template("{year}-{month}").is_ok());
+        assert!(validate_template("photos/{year}").is_ok());
+        assert!(validate_template("{yaer}").is_err());
+        assert!(validate_template("{year").is_err());
+        assert!(validate_template("year}").is_err());
+        assert!(validate_template("").is_err());
+        assert!(validate_template("/{year}").is_err());
+        assert!(validate_template("../{year}").is_err());
+    }
+}
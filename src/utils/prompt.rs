@@ -0,0 +1,33 @@
+use anyhow::Result;
+use dialoguer::{theme::ColorfulTheme, Select};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal()
+}
+
+/// Asks a yes/no question on the terminal, defaulting to "no". Returns false
+/// without asking when stdin is not interactive.
+pub fn confirm(question: &str) -> Result<bool> {
+    if !is_interactive() {
+        return Ok(false);
+    }
+
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Lets the user pick one of `items` with the arrow keys, starting at
+/// `default`. Returns `None` if the prompt was dismissed with Esc or `q`.
+pub fn select(question: &str, items: &[String], default: usize) -> Result<Option<usize>> {
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(question)
+        .items(items)
+        .default(default)
+        .interact_opt()?;
+    Ok(selection)
+}
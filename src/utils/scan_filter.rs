@@ -0,0 +1,832 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::size_utils::format_bytes;
+use super::{date_utils, operation_log};
+use crate::commands::{DateSource, Orientation};
+
+pub const IGNORE_FILE_NAME: &str = ".imageignore";
+
+/// Why a scanned file was left out of the results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    Excluded { pattern: String },
+    NotIncluded,
+    Ignored { ignore_file: PathBuf },
+    TooDeep { max_depth: usize },
+    TooSmall { min_size: u64 },
+    TooLarge { max_size: u64 },
+    Unreadable { error: String },
+    TooFewPixels { width: u32, height: u32 },
+    WrongOrientation { width: u32, height: u32 },
+    OutsideDateRange { date: NaiveDate },
+    NoDate,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::Excluded { pattern } => write!(f, "matches --exclude {}", pattern),
+            SkipReason::NotIncluded => write!(f, "matches no --include pattern"),
+            SkipReason::Ignored { ignore_file } => {
+                write!(f, "ignored by {}", ignore_file.display())
+            }
+            SkipReason::TooDeep { max_depth } => {
+                write!(f, "deeper than --max-depth {}", max_depth)
+            }
+            SkipReason::TooSmall { min_size } => {
+                write!(f, "smaller than --min-size {}", format_bytes(*min_size))
+            }
+            SkipReason::TooLarge { max_size } => {
+                write!(f, "larger than --max-size {}", format_bytes(*max_size))
+            }
+            SkipReason::Unreadable { error } => write!(f, "unreadable: {}", error),
+            SkipReason::TooFewPixels { width, height } => {
+                write!(f, "{}x{} is below --min-width/--min-height", width, height)
+            }
+            SkipReason::WrongOrientation { width, height } => write!(
+                f,
+                "{}x{} is {}, not --orientation",
+                width,
+                height,
+                Orientation::of(*width, *height).name()
+            ),
+            SkipReason::OutsideDateRange { date } => {
+                write!(f, "dated {}, outside --after/--before", date)
+            }
+            SkipReason::NoDate => write!(f, "no date to compare with --after/--before"),
+        }
+    }
+}
+
+impl SkipReason {
+    /// Whether the file was skipped because it could not be read, rather
+    /// than because a filter rejected it.
+    pub fn is_error(&self) -> bool {
+        matches!(self, SkipReason::Unreadable { .. })
+    }
+
+    /// The option or rule that skipped the file, used to summarize skips.
+    pub fn source(&self) -> &'static str {
+        match self {
+            SkipReason::Excluded { .. } => "--exclude",
+            SkipReason::NotIncluded => "--include",
+            SkipReason::Ignored { .. } => IGNORE_FILE_NAME,
+            SkipReason::TooDeep { .. } => "--max-depth",
+            SkipReason::TooSmall { .. } => "--min-size",
+            SkipReason::TooLarge { .. } => "--max-size",
+            SkipReason::Unreadable { .. } => "unreadable",
+            SkipReason::TooFewPixels { .. } => "--min-width/--min-height",
+            SkipReason::WrongOrientation { .. } => "--orientation",
+            SkipReason::OutsideDateRange { .. } | SkipReason::NoDate => "--after/--before",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: SkipReason,
+}
+
+/// Describes the files that were skipped because they could not be read.
+pub fn skip_errors(skipped: &[SkippedFile]) -> Vec<String> {
+    skipped
+        .iter()
+        .filter(|file| file.reason.is_error())
+        .map(|file| format!("{}: {}", file.path.display(), file.reason))
+        .collect()
+}
+
+/// Glob patterns matched against paths relative to the scanned directory.
+/// A pattern without a `/` matches a file or folder name at any depth, and a
+/// matching folder excludes everything below it.
+struct PatternSet {
+    patterns: Vec<String>,
+    set: GlobSet,
+}
+
+impl PatternSet {
+    fn new(patterns: &[String], flag: &str) -> Result<Option<Self>> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let normalized = if pattern.contains('/') {
+                pattern.trim_start_matches("./").to_string()
+            } else {
+                format!("**/{}", pattern)
+            };
+            let glob = Glob::new(&normalized)
+                .with_context(|| format!("Invalid {} pattern: {}", flag, pattern))?;
+            builder.add(glob);
+        }
+
+        Ok(Some(Self {
+            patterns: patterns.to_vec(),
+            set: builder.build()?,
+        }))
+    }
+
+    fn matching_pattern(&self, relative: &Path) -> Option<&str> {
+        relative
+            .ancestors()
+            .filter(|candidate| !candidate.as_os_str().is_empty())
+            .find_map(|candidate| {
+                self.set
+                    .matches(candidate)
+                    .first()
+                    .map(|&index| self.patterns[index].as_str())
+            })
+    }
+}
+
+/// Gitignore-style rules from one `.imageignore`, applying to the folder it
+/// sits in and everything below.
+struct IgnoreFile {
+    path: PathBuf,
+    rules: Gitignore,
+}
+
+impl IgnoreFile {
+    fn load(path: PathBuf) -> Result<Self> {
+        let directory = path.parent().unwrap_or(Path::new("."));
+        let mut builder = GitignoreBuilder::new(directory);
+        if let Some(e) = builder.add(&path) {
+            return Err(anyhow::anyhow!("Invalid {}: {}", path.display(), e));
+        }
+        let rules = builder
+            .build()
+            .with_context(|| format!("Invalid {}", path.display()))?;
+
+        Ok(Self { path, rules })
+    }
+}
+
+fn find_ignore_files(directory: &Path, recursive: bool, found: &mut Vec<IgnoreFile>) -> Result<()> {
+    let ignore_path = directory.join(IGNORE_FILE_NAME);
+    if ignore_path.is_file() {
+        found.push(IgnoreFile::load(ignore_path)?);
+    }
+
+    if recursive {
+        // Unreadable folders are reported by the scan itself.
+        let Ok(entries) = fs::read_dir(directory) else {
+            return Ok(());
+        };
+        for entry in entries.flatten() {
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                find_ignore_files(&entry.path(), recursive, found)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Filters applied to the files found by a scan. Excludes take precedence
+/// over includes.
+pub struct ScanFilter {
+    roots: Vec<PathBuf>,
+    exclude: Option<PatternSet>,
+    include: Option<PatternSet>,
+    ignore_files: Vec<IgnoreFile>,
+    max_depth: Option<usize>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    min_width: Option<u32>,
+    min_height: Option<u32>,
+    orientation: Orientation,
+    after: Option<NaiveDate>,
+    before: Option<NaiveDate>,
+    date_source: Option<DateSource>,
+}
+
+impl ScanFilter {
+    pub fn new(roots: &[&Path]) -> Self {
+        Self {
+            roots: roots.iter().map(|root| root.to_path_buf()).collect(),
+            exclude: None,
+            include: None,
+            ignore_files: Vec::new(),
+            max_depth: None,
+            min_size: None,
+            max_size: None,
+            min_width: None,
+            min_height: None,
+            orientation: Orientation::Any,
+            after: None,
+            before: None,
+            date_source: None,
+        }
+    }
+
+    pub fn with_exclude(mut self, patterns: &[String]) -> Result<Self> {
+        self.exclude = PatternSet::new(patterns, "--exclude")?;
+        Ok(self)
+    }
+
+    pub fn with_include(mut self, patterns: &[String]) -> Result<Self> {
+        self.include = PatternSet::new(patterns, "--include")?;
+        Ok(self)
+    }
+
+    /// Reads the `.imageignore` files of the scanned directories, including
+    /// those in subfolders when `recursive` is set. Rules in a deeper file
+    /// take precedence, as with `.gitignore`.
+    pub fn with_ignore_files(mut self, recursive: bool) -> Result<Self> {
+        let mut found = Vec::new();
+        for root in &self.roots {
+            find_ignore_files(root, recursive, &mut found)?;
+        }
+        found.sort_by_key(|ignore_file| Reverse(ignore_file.path.components().count()));
+
+        self.ignore_files = found;
+        Ok(self)
+    }
+
+    /// Skips files more than `max_depth` folders below the scanned directory;
+    /// depth 0 keeps only the files directly inside it.
+    pub fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Skips files outside `min_size..=max_size` bytes.
+    pub fn with_size_range(mut self, min_size: Option<u64>, max_size: Option<u64>) -> Self {
+        self.min_size = min_size;
+        self.max_size = max_size;
+        self
+    }
+
+    /// Skips images narrower than `min_width` or lower than `min_height`
+    /// pixels. Files whose dimensions cannot be read are skipped as well.
+    pub fn with_min_dimensions(mut self, min_width: Option<u32>, min_height: Option<u32>) -> Self {
+        self.min_width = min_width;
+        self.min_height = min_height;
+        self
+    }
+
+    /// Skips images whose width and height do not match `orientation`.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Skips files dated outside `after..=before`. The date comes from
+    /// `date_source` when given, falling back to the modification time.
+    pub fn with_date_range(
+        mut self,
+        after: Option<NaiveDate>,
+        before: Option<NaiveDate>,
+        date_source: Option<DateSource>,
+    ) -> Self {
+        self.after = after;
+        self.before = before;
+        self.date_source = date_source;
+        self
+    }
+
+    pub fn skip_reason(&self, path: &Path) -> Option<SkipReason> {
+        let relative = self.relative_path(path);
+
+        if let Some(max_depth) = self.max_depth {
+            let depth = relative.components().count().saturating_sub(1);
+            if depth > max_depth {
+                return Some(SkipReason::TooDeep { max_depth });
+            }
+        }
+
+        if let Some(pattern) = self
+            .exclude
+            .as_ref()
+            .and_then(|exclude| exclude.matching_pattern(relative))
+        {
+            return Some(SkipReason::Excluded {
+                pattern: pattern.to_string(),
+            });
+        }
+
+        if let Some(reason) = self.ignore_skip_reason(path, false) {
+            return Some(reason);
+        }
+
+        if let Some(include) = &self.include {
+            if include.matching_pattern(relative).is_none() {
+                return Some(SkipReason::NotIncluded);
+            }
+        }
+
+        self.size_skip_reason(path)
+            .or_else(|| self.dimension_skip_reason(path))
+            .or_else(|| self.date_skip_reason(path))
+    }
+
+    fn dimension_skip_reason(&self, path: &Path) -> Option<SkipReason> {
+        if self.min_width.is_none()
+            && self.min_height.is_none()
+            && self.orientation == Orientation::Any
+        {
+            return None;
+        }
+
+        let (width, height) = match image::image_dimensions(path) {
+            Ok(dimensions) => dimensions,
+            Err(e) => {
+                return Some(SkipReason::Unreadable {
+                    error: e.to_string(),
+                })
+            }
+        };
+
+        let too_narrow = self.min_width.is_some_and(|min_width| width < min_width);
+        let too_low = self
+            .min_height
+            .is_some_and(|min_height| height < min_height);
+        if too_narrow || too_low {
+            Some(SkipReason::TooFewPixels { width, height })
+        } else if !self.orientation.matches(width, height) {
+            Some(SkipReason::WrongOrientation { width, height })
+        } else {
+            None
+        }
+    }
+
+    fn date_skip_reason(&self, path: &Path) -> Option<SkipReason> {
+        if self.after.is_none() && self.before.is_none() {
+            return None;
+        }
+
+        let date = self
+            .date_source
+            .and_then(|source| date_utils::source_date(path, source))
+            .or_else(|| date_utils::modified_date(path))
+            .and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok());
+
+        let Some(date) = date else {
+            return Some(SkipReason::NoDate);
+        };
+
+        let too_early = self.after.is_some_and(|after| date < after);
+        let too_late = self.before.is_some_and(|before| date > before);
+        if too_early || too_late {
+            Some(SkipReason::OutsideDateRange { date })
+        } else {
+            None
+        }
+    }
+
+    /// Why nothing below `directory` can pass the filters: it is deeper than
+    /// `--max-depth`, excluded or ignored. Includes never rule out a folder,
+    /// since a pattern may still match files below it.
+    pub fn directory_skip_reason(&self, directory: &Path) -> Option<SkipReason> {
+        let relative = self.relative_path(directory);
+
+        if let Some(max_depth) = self.max_depth {
+            if relative.components().count() > max_depth {
+                return Some(SkipReason::TooDeep { max_depth });
+            }
+        }
+
+        if let Some(pattern) = self
+            .exclude
+            .as_ref()
+            .and_then(|exclude| exclude.matching_pattern(relative))
+        {
+            return Some(SkipReason::Excluded {
+                pattern: pattern.to_string(),
+            });
+        }
+
+        self.ignore_skip_reason(directory, true)
+    }
+
+    fn ignore_skip_reason(&self, path: &Path, is_dir: bool) -> Option<SkipReason> {
+        for ignore_file in &self.ignore_files {
+            if !path.starts_with(ignore_file.rules.path()) {
+                continue;
+            }
+
+            match ignore_file.rules.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => {
+                    return Some(SkipReason::Ignored {
+                        ignore_file: ignore_file.path.clone(),
+                    })
+                }
+                Match::Whitelist(_) => return None,
+                Match::None => {}
+            }
+        }
+
+        None
+    }
+
+    fn size_skip_reason(&self, path: &Path) -> Option<SkipReason> {
+        if self.min_size.is_none() && self.max_size.is_none() {
+            return None;
+        }
+
+        let size = match fs::metadata(path) {
+            Ok(metadata) => metadata.len(),
+            Err(e) => {
+                return Some(SkipReason::Unreadable {
+                    error: e.to_string(),
+                })
+            }
+        };
+
+        match (self.min_size, self.max_size) {
+            (Some(min_size), _) if size < min_size => Some(SkipReason::TooSmall { min_size }),
+            (_, Some(max_size)) if size > max_size => Some(SkipReason::TooLarge { max_size }),
+            _ => None,
+        }
+    }
+
+    /// Removes skipped files from date groups, dropping groups left empty.
+    pub fn filter_organized(
+        &self,
+        organized_images: HashMap<String, Vec<PathBuf>>,
+    ) -> (HashMap<String, Vec<PathBuf>>, Vec<SkippedFile>) {
+        let mut skipped = Vec::new();
+
+        let filtered = organized_images
+            .into_iter()
+            .map(|(date, files)| (date, self.partition(files, &mut skipped)))
+            .filter(|(_, files)| !files.is_empty())
+            .collect();
+
+        (filtered, skipped)
+    }
+
+    /// Removes skipped files from duplicate groups, dropping groups that no
+    /// longer contain a duplicate.
+    pub fn filter_groups(
+        &self,
+        duplicate_groups: Vec<Vec<PathBuf>>,
+    ) -> (Vec<Vec<PathBuf>>, Vec<SkippedFile>) {
+        let mut skipped = Vec::new();
+
+        let filtered = duplicate_groups
+            .into_iter()
+            .map(|group| self.partition(group, &mut skipped))
+            .filter(|group| group.len() > 1)
+            .collect();
+
+        (filtered, skipped)
+    }
+
+    /// Whether `file` passes the filters. Skipped files are logged and added
+    /// to `skipped`.
+    pub fn admit(&self, file: &Path, skipped: &mut Vec<SkippedFile>) -> bool {
+        match self.skip_reason(file) {
+            Some(reason) => {
+                log::info!("Skipped {}: {}", file.display(), reason);
+                operation_log::record("skipped", file, Some(&reason.to_string()));
+                skipped.push(SkippedFile {
+                    path: file.to_path_buf(),
+                    reason,
+                });
+                false
+            }
+            None => {
+                log::debug!("Scanned {}", file.display());
+                operation_log::record("scanned", file, None);
+                true
+            }
+        }
+    }
+
+    /// Whether a scan should descend into `directory`. Skipped folders are
+    /// logged but not counted, as their files are never looked at.
+    pub fn admit_directory(&self, directory: &Path) -> bool {
+        match self.directory_skip_reason(directory) {
+            Some(reason) => {
+                log::info!("Skipped {}: {}", directory.display(), reason);
+                operation_log::record("skipped", directory, Some(&reason.to_string()));
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn partition(&self, files: Vec<PathBuf>, skipped: &mut Vec<SkippedFile>) -> Vec<PathBuf> {
+        files
+            .into_iter()
+            .filter(|file| self.admit(file, skipped))
+            .collect()
+    }
+
+    fn relative_path<'a>(&self, path: &'a Path) -> &'a Path {
+        self.roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|p| p.to_string()).collect()
+    }
+
+    fn filter(exclude: &[&str]) -> ScanFilter {
+        filter_with_include(exclude, &[])
+    }
+
+    fn filter_with_include(exclude: &[&str], include: &[&str]) -> ScanFilter {
+        ScanFilter::new(&[Path::new("/photos")])
+            .with_exclude(&patterns(exclude))
+            .unwrap()
+            .with_include(&patterns(include))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_exclude_matches_relative_path() {
+        let filter = filter(&["exports/*.jpg"]);
+
+        assert!(filter
+            .skip_reason(Path::new("/photos/exports/a.jpg"))
+            .is_some());
+        assert!(filter
+            .skip_reason(Path::new("/photos/2023/exports/a.jpg"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_exclude_name_pattern_skips_folder_at_any_depth() {
+        let filter = filter(&[".thumbnails"]);
+
+        assert_eq!(
+            filter.skip_reason(Path::new("/photos/2023/.thumbnails/a.jpg")),
+            Some(SkipReason::Excluded {
+                pattern: ".thumbnails".to_string()
+            })
+        );
+        assert!(filter
+            .skip_reason(Path::new("/photos/2023/a.jpg"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_filter_groups_drops_groups_without_duplicates() {
+        let filter = filter(&["*.png"]);
+        let groups = vec![
+            vec![
+                PathBuf::from("/photos/a.jpg"),
+                PathBuf::from("/photos/b.png"),
+            ],
+            vec![
+                PathBuf::from("/photos/c.jpg"),
+                PathBuf::from("/photos/d.jpg"),
+            ],
+        ];
+
+        let (filtered, skipped) = filter.filter_groups(groups);
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].path, PathBuf::from("/photos/b.png"));
+    }
+
+    #[test]
+    fn test_include_limits_to_matching_files() {
+        let filter = filter_with_include(&[], &["IMG_*.jpg"]);
+
+        assert!(filter
+            .skip_reason(Path::new("/photos/2023/IMG_0001.jpg"))
+            .is_none());
+        assert_eq!(
+            filter.skip_reason(Path::new("/photos/2023/DSC_0001.jpg")),
+            Some(SkipReason::NotIncluded)
+        );
+    }
+
+    #[test]
+    fn test_exclude_wins_over_include() {
+        let filter = filter_with_include(&["edited"], &["IMG_*.jpg"]);
+
+        assert_eq!(
+            filter.skip_reason(Path::new("/photos/edited/IMG_0001.jpg")),
+            Some(SkipReason::Excluded {
+                pattern: "edited".to_string()
+            })
+        );
+        assert!(filter
+            .skip_reason(Path::new("/photos/raw/IMG_0001.jpg"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_no_patterns_keeps_everything() {
+        let filter = filter_with_include(&[], &[]);
+        assert!(filter.skip_reason(Path::new("/photos/a.png")).is_none());
+    }
+
+    #[test]
+    fn test_size_range_skips_files_outside_bounds() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let small = temp_dir.path().join("small.jpg");
+        let medium = temp_dir.path().join("medium.jpg");
+        let large = temp_dir.path().join("large.jpg");
+        fs::write(&small, vec![0u8; 10]).unwrap();
+        fs::write(&medium, vec![0u8; 100]).unwrap();
+        fs::write(&large, vec![0u8; 1000]).unwrap();
+
+        let filter = ScanFilter::new(&[temp_dir.path()]).with_size_range(Some(100), Some(500));
+
+        assert_eq!(
+            filter.skip_reason(&small),
+            Some(SkipReason::TooSmall { min_size: 100 })
+        );
+        assert!(filter.skip_reason(&medium).is_none());
+        assert_eq!(
+            filter.skip_reason(&large),
+            Some(SkipReason::TooLarge { max_size: 500 })
+        );
+        assert!(matches!(
+            filter.skip_reason(&temp_dir.path().join("missing.jpg")),
+            Some(SkipReason::Unreadable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_date_range_bounds_are_inclusive() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let day = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        let files = [
+            ("IMG_20221231_0001.jpg", false),
+            ("IMG_20230101_0001.jpg", true),
+            ("IMG_20231231_0001.jpg", true),
+            ("IMG_20240101_0001.jpg", false),
+        ];
+        for (name, _) in &files {
+            fs::write(temp_dir.path().join(name), b"x").unwrap();
+        }
+
+        let filter = ScanFilter::new(&[temp_dir.path()]).with_date_range(
+            Some(day(2023, 1, 1)),
+            Some(day(2023, 12, 31)),
+            Some(DateSource::Filename),
+        );
+
+        for (name, kept) in &files {
+            let reason = filter.skip_reason(&temp_dir.path().join(name));
+            assert_eq!(reason.is_none(), *kept, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_nested_ignore_files_apply_to_their_subtree() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let album = root.join("album");
+        fs::create_dir_all(album.join("exports")).unwrap();
+        fs::write(root.join(IGNORE_FILE_NAME), "*.png\nexports/\n").unwrap();
+        fs::write(album.join(IGNORE_FILE_NAME), "!keep.png\nedited_*\n").unwrap();
+
+        let filter = ScanFilter::new(&[root]).with_ignore_files(true).unwrap();
+
+        assert!(filter.skip_reason(&root.join("a.png")).is_some());
+        assert!(filter.skip_reason(&root.join("a.jpg")).is_none());
+        assert!(filter
+            .skip_reason(&album.join("exports").join("b.jpg"))
+            .is_some());
+        assert!(filter.skip_reason(&album.join("keep.png")).is_none());
+        assert_eq!(
+            filter.skip_reason(&album.join("edited_1.jpg")),
+            Some(SkipReason::Ignored {
+                ignore_file: album.join(IGNORE_FILE_NAME)
+            })
+        );
+        assert!(filter.skip_reason(&root.join("edited_1.jpg")).is_none());
+
+        let top_level_only = ScanFilter::new(&[root]).with_ignore_files(false).unwrap();
+        assert!(top_level_only
+            .skip_reason(&album.join("edited_1.jpg"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_max_depth_one_keeps_immediate_subdirectories_only() {
+        let filter = ScanFilter::new(&[Path::new("/photos")]).with_max_depth(Some(1));
+
+        assert!(filter.skip_reason(Path::new("/photos/a.jpg")).is_none());
+        assert!(filter
+            .skip_reason(Path::new("/photos/2023/a.jpg"))
+            .is_none());
+        assert_eq!(
+            filter.skip_reason(Path::new("/photos/2023/trip/a.jpg")),
+            Some(SkipReason::TooDeep { max_depth: 1 })
+        );
+    }
+
+    #[test]
+    fn test_directory_skip_reason_prunes_excluded_ignored_and_deep_folders() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join(IGNORE_FILE_NAME), "cache/\n").unwrap();
+
+        let filter = ScanFilter::new(&[root])
+            .with_exclude(&patterns(&[".thumbnails"]))
+            .unwrap()
+            .with_include(&patterns(&["IMG_*.jpg"]))
+            .unwrap()
+            .with_ignore_files(false)
+            .unwrap()
+            .with_max_depth(Some(1));
+
+        assert!(filter.directory_skip_reason(&root.join("2023")).is_none());
+        assert_eq!(
+            filter.directory_skip_reason(&root.join("2023").join("trip")),
+            Some(SkipReason::TooDeep { max_depth: 1 })
+        );
+        assert!(matches!(
+            filter.directory_skip_reason(&root.join(".thumbnails")),
+            Some(SkipReason::Excluded { .. })
+        ));
+        assert!(matches!(
+            filter.directory_skip_reason(&root.join("cache")),
+            Some(SkipReason::Ignored { .. })
+        ));
+    }
+
+    #[test]
+    fn test_min_dimensions_skip_small_and_unreadable_images() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let icon = temp_dir.path().join("icon.png");
+        let photo = temp_dir.path().join("photo.png");
+        let broken = temp_dir.path().join("broken.png");
+        image::RgbImage::new(16, 16).save(&icon).unwrap();
+        image::RgbImage::new(64, 32).save(&photo).unwrap();
+        fs::write(&broken, b"not an image").unwrap();
+
+        let filter = ScanFilter::new(&[temp_dir.path()]).with_min_dimensions(Some(32), Some(32));
+
+        assert_eq!(
+            filter.skip_reason(&icon),
+            Some(SkipReason::TooFewPixels {
+                width: 16,
+                height: 16
+            })
+        );
+        assert!(filter.skip_reason(&photo).is_none());
+
+        let (_, skipped) = filter.filter_groups(vec![vec![photo.clone(), broken.clone()]]);
+        assert_eq!(skip_errors(&skipped).len(), 1);
+    }
+
+    #[test]
+    fn test_orientation_combines_with_min_dimensions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let wide = temp_dir.path().join("wide.png");
+        let tall = temp_dir.path().join("tall.png");
+        let square = temp_dir.path().join("square.png");
+        let small_wide = temp_dir.path().join("small_wide.png");
+        image::RgbImage::new(64, 32).save(&wide).unwrap();
+        image::RgbImage::new(32, 64).save(&tall).unwrap();
+        image::RgbImage::new(48, 48).save(&square).unwrap();
+        image::RgbImage::new(16, 8).save(&small_wide).unwrap();
+
+        let landscape = ScanFilter::new(&[temp_dir.path()])
+            .with_min_dimensions(Some(32), None)
+            .with_orientation(Orientation::Landscape);
+        assert!(landscape.skip_reason(&wide).is_none());
+        assert_eq!(
+            landscape.skip_reason(&tall),
+            Some(SkipReason::WrongOrientation {
+                width: 32,
+                height: 64
+            })
+        );
+        assert!(landscape.skip_reason(&square).is_some());
+        assert!(matches!(
+            landscape.skip_reason(&small_wide),
+            Some(SkipReason::TooFewPixels { .. })
+        ));
+
+        let square_only = ScanFilter::new(&[temp_dir.path()]).with_orientation(Orientation::Square);
+        assert!(square_only.skip_reason(&square).is_none());
+        assert!(square_only.skip_reason(&wide).is_some());
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_rejected() {
+        let result = ScanFilter::new(&[Path::new("/photos")]).with_include(&patterns(&["["]));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::Result;
+
+pub const SIZE_UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+
+pub fn format_bytes(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+
+    while size >= 1024.0 && unit_index < SIZE_UNITS.len() - 1 {
+        size /= 1024.0;
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{} {}", bytes, SIZE_UNITS[unit_index])
+    } else {
+        format!("{:.1} {}", size, SIZE_UNITS[unit_index])
+    }
+}
+
+/// Parses a human size such as `500MB`, `1.5GB` or `2048` into bytes, using
+/// the same 1024-based units as [`format_bytes`]. `K`, `M` and `G` are
+/// accepted as shorthands.
+pub fn parse_size(value: &str) -> Result<u64> {
+    let trimmed = value.trim();
+    let unit_start = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(unit_start);
+
+    let invalid = || anyhow::anyhow!("Invalid size {:?} (expected e.g. 500MB or 1GB)", value);
+
+    let number: f64 = number.parse().map_err(|_| invalid())?;
+    let mut unit = unit.trim().to_uppercase();
+    if !unit.ends_with('B') {
+        unit.push('B');
+    }
+
+    let exponent = SIZE_UNITS
+        .iter()
+        .position(|known| *known == unit)
+        .ok_or_else(invalid)?;
+
+    Ok((number * 1024f64.powi(exponent as i32)).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GB");
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("2048").unwrap(), 2048);
+        assert_eq!(parse_size("10KB").unwrap(), 10 * 1024);
+        assert_eq!(parse_size("500MB").unwrap(), 500 * 1024 * 1024);
+        assert_eq!(parse_size("1.5 gb").unwrap(), 1536 * 1024 * 1024);
+        assert_eq!(parse_size("1G").unwrap(), 1024 * 1024 * 1024);
+        assert!(parse_size("").is_err());
+        assert!(parse_size("MB").is_err());
+        assert!(parse_size("5TB").is_err());
+        assert!(parse_size("five").is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Files from several directories gathered under one temporary directory, so
+/// the library, which scans a single directory, can compare them with each
+/// other. The directory is removed when the tree is dropped.
+pub struct StagedTree {
+    _directory: TempDir,
+    root: PathBuf,
+    originals: HashMap<PathBuf, PathBuf>,
+    /// Files that could not be staged.
+    pub errors: Vec<String>,
+}
+
+impl StagedTree {
+    /// Stages each of `files` in a numbered folder for the first of `roots`
+    /// containing it, keeping its path relative to that root. Files are hard
+    /// linked where possible and copied otherwise, e.g. when the temporary
+    /// directory is on another filesystem.
+    pub fn new(roots: &[&Path], files: &[PathBuf]) -> Result<Self> {
+        let directory = tempfile::Builder::new()
+            .prefix("image-manager-")
+            .tempdir()
+            .context("Failed to create a temporary directory")?;
+        // The library may report canonical paths, e.g. /private/var on macOS.
+        let root = fs::canonicalize(directory.path())
+            .context("Failed to resolve the temporary directory")?;
+
+        let mut tree = Self {
+            _directory: directory,
+            root,
+            originals: HashMap::new(),
+            errors: Vec::new(),
+        };
+
+        for file in files {
+            let Some((index, relative)) = roots
+                .iter()
+                .enumerate()
+                .find_map(|(index, root)| Some((index, file.strip_prefix(root).ok()?)))
+            else {
+                continue;
+            };
+
+            let staged = tree.root.join(index.to_string()).join(relative);
+            match stage_file(file, &staged) {
+                Ok(()) => {
+                    tree.originals.insert(staged, file.clone());
+                }
+                Err(e) => tree.errors.push(format!("{}: {}", file.display(), e)),
+            }
+        }
+
+        Ok(tree)
+    }
+
+    /// The directory to scan.
+    pub fn path(&self) -> &Path {
+        &self.root
+    }
+
+    /// Replaces the staged paths in `groups` with the original files.
+    pub fn restore_paths(&self, groups: Vec<Vec<PathBuf>>) -> Vec<Vec<PathBuf>> {
+        groups
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|path| self.originals.get(&path).cloned().unwrap_or(path))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn stage_file(file: &Path, staged: &Path) -> std::io::Result<()> {
+    if let Some(parent) = staged.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::hard_link(file, staged).is_err() {
+        fs::copy(file, staged)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restores_paths_from_both_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let phone = temp_dir.path().join("phone");
+        let archive = temp_dir.path().join("archive");
+        fs::create_dir_all(phone.join("2023")).unwrap();
+        fs::create_dir_all(&archive).unwrap();
+        let phone_file = phone.join("2023").join("IMG_1.jpg");
+        let archive_file = archive.join("IMG_1.jpg");
+        fs::write(&phone_file, "photo").unwrap();
+        fs::write(&archive_file, "photo").unwrap();
+
+        let tree = StagedTree::new(
+            &[&phone, &archive],
+            &[phone_file.clone(), archive_file.clone()],
+        )
+        .unwrap();
+        assert!(tree.errors.is_empty());
+
+        let staged_phone = tree.path().join("0").join("2023").join("IMG_1.jpg");
+        let staged_archive = tree.path().join("1").join("IMG_1.jpg");
+        assert_eq!(fs::read(&staged_archive).unwrap(), b"photo");
+
+        let unknown = PathBuf::from("/elsewhere/a.jpg");
+        let groups = tree.restore_paths(vec![vec![staged_phone, staged_archive, unknown.clone()]]);
+        assert_eq!(groups, vec![vec![phone_file, archive_file, unknown]]);
+    }
+}
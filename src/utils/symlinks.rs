@@ -0,0 +1,156 @@
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default)]
+pub struct LinkedDirectories {
+    /// Symlinked directories to scan in addition to the root, as paths below
+    /// the root.
+    pub directories: Vec<PathBuf>,
+    /// Links that were skipped because they lead into, or contain, a
+    /// directory that is already scanned.
+    pub loops: Vec<String>,
+}
+
+/// Finds the directory symlinks below `roots` that a recursive scan which
+/// does not follow links would miss, including links inside linked
+/// directories. A link is only followed when its canonical target neither
+/// lies inside nor contains a root or an already followed link, so no file
+/// is scanned twice; other links are reported as loops. Links to shorter
+/// targets are considered first, so a link to a folder wins over links into
+/// it.
+pub fn find_linked_directories(roots: &[&Path]) -> LinkedDirectories {
+    let mut scanned: Vec<PathBuf> = roots
+        .iter()
+        .filter_map(|root| fs::canonicalize(root).ok())
+        .collect();
+    let mut pending = Vec::new();
+    for root in roots {
+        collect_links(root, &mut pending);
+    }
+
+    let mut linked = LinkedDirectories::default();
+    loop {
+        pending.sort_by_key(|(link, target)| Reverse((target.components().count(), link.clone())));
+        let Some((link, target)) = pending.pop() else {
+            break;
+        };
+
+        if let Some(overlap) = scanned
+            .iter()
+            .find(|dir| target.starts_with(dir) || dir.starts_with(&target))
+        {
+            let reason = if target.starts_with(overlap) {
+                "directory is already scanned"
+            } else {
+                "directory contains one that is already scanned"
+            };
+            linked.loops.push(format!(
+                "Skipping symlink {} → {}: {}",
+                link.display(),
+                target.display(),
+                reason
+            ));
+            continue;
+        }
+
+        collect_links(&link, &mut pending);
+        scanned.push(target);
+        linked.directories.push(link);
+    }
+
+    linked.directories.sort();
+    linked
+}
+
+/// Collects the directory symlinks in `directory` and its real
+/// subdirectories, paired with their canonical targets.
+fn collect_links(directory: &Path, links: &mut Vec<(PathBuf, PathBuf)>) {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            collect_links(&path, links);
+        } else if file_type.is_symlink() && path.is_dir() {
+            if let Ok(target) = fs::canonicalize(&path) {
+                links.push((path, target));
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_finds_linked_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let library = temp_dir.path().join("library");
+        let external = temp_dir.path().join("external");
+        fs::create_dir_all(library.join("2023")).unwrap();
+        fs::create_dir_all(external.join("trip")).unwrap();
+        symlink(&external, library.join("2023").join("external")).unwrap();
+
+        let linked = find_linked_directories(&[&library]);
+
+        assert_eq!(
+            linked.directories,
+            vec![library.join("2023").join("external")]
+        );
+        assert!(linked.loops.is_empty());
+    }
+
+    #[test]
+    fn test_skips_symlink_loops() {
+        let temp_dir = TempDir::new().unwrap();
+        let library = temp_dir.path().join("library");
+        fs::create_dir_all(library.join("album")).unwrap();
+        symlink(&library, library.join("album").join("back")).unwrap();
+
+        let linked = find_linked_directories(&[&library]);
+
+        assert!(linked.directories.is_empty());
+        assert_eq!(linked.loops.len(), 1);
+    }
+
+    #[test]
+    fn test_skips_links_overlapping_a_followed_link() {
+        let temp_dir = TempDir::new().unwrap();
+        let library = temp_dir.path().join("library");
+        let external = temp_dir.path().join("external");
+        fs::create_dir_all(&library).unwrap();
+        fs::create_dir_all(external.join("sub")).unwrap();
+        symlink(external.join("sub"), library.join("a_sub")).unwrap();
+        symlink(&external, library.join("b_ext")).unwrap();
+
+        let linked = find_linked_directories(&[&library]);
+
+        assert_eq!(linked.directories, vec![library.join("b_ext")]);
+        assert_eq!(linked.loops.len(), 1);
+    }
+
+    #[test]
+    fn test_skips_links_into_another_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let phone = temp_dir.path().join("phone");
+        let archive = temp_dir.path().join("archive");
+        fs::create_dir_all(&phone).unwrap();
+        fs::create_dir_all(archive.join("2023")).unwrap();
+        symlink(archive.join("2023"), phone.join("archived")).unwrap();
+
+        let linked = find_linked_directories(&[&phone, &archive]);
+
+        assert!(linked.directories.is_empty());
+        assert_eq!(linked.loops.len(), 1);
+    }
+}
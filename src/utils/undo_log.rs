@@ -0,0 +1,226 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use filetime::FileTime;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::file_ops;
+
+pub const UNDO_LOG_FILE_NAME: &str = ".image-manager-undo.json";
+
+/// Record of everything an organize run created in the target directory, so
+/// `organize undo` can revert exactly that and nothing else.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UndoLog {
+    pub created_at: DateTime<Utc>,
+    pub operation: String,
+    pub directories: Vec<PathBuf>,
+    pub files: Vec<UndoEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UndoEntry {
+    pub path: PathBuf,
+    /// Original location of a moved file; undo moves it back there.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moved_from: Option<PathBuf>,
+    /// The file replaced one that already existed, so undo leaves it alone.
+    #[serde(default)]
+    pub replaced_existing: bool,
+    pub size: u64,
+    pub modified: i64,
+}
+
+impl UndoEntry {
+    fn matches(&self, metadata: &fs::Metadata) -> bool {
+        metadata.len() == self.size
+            && FileTime::from_last_modification_time(metadata).unix_seconds() == self.modified
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct UndoReport {
+    pub removed_files: usize,
+    pub restored_files: usize,
+    pub removed_directories: usize,
+    pub warnings: Vec<String>,
+}
+
+impl UndoLog {
+    pub fn new(operation: &str) -> Self {
+        Self {
+            created_at: Utc::now(),
+            operation: operation.to_string(),
+            directories: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.directories.is_empty() && self.files.is_empty()
+    }
+
+    pub fn record_file(
+        &mut self,
+        path: &Path,
+        moved_from: Option<&Path>,
+        replaced_existing: bool,
+    ) -> Result<()> {
+        let metadata = fs::symlink_metadata(path)?;
+        self.files.push(UndoEntry {
+            path: path.to_path_buf(),
+            moved_from: moved_from.map(Path::to_path_buf),
+            replaced_existing,
+            size: metadata.len(),
+            modified: FileTime::from_last_modification_time(&metadata).unix_seconds(),
+        });
+        Ok(())
+    }
+
+    pub fn write(&self, target_dir: &Path) -> Result<PathBuf> {
+        let log_path = target_dir.join(UNDO_LOG_FILE_NAME);
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(&log_path, content)
+            .with_context(|| format!("Failed to write undo log: {}", log_path.display()))?;
+        Ok(log_path)
+    }
+
+    pub fn read(target_dir: &Path) -> Result<Self> {
+        let log_path = target_dir.join(UNDO_LOG_FILE_NAME);
+        let content = fs::read_to_string(&log_path).with_context(|| {
+            format!(
+                "No undo log found in {} (expected {})",
+                target_dir.display(),
+                UNDO_LOG_FILE_NAME
+            )
+        })?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse undo log: {}", log_path.display()))
+    }
+}
+
+/// Reverts the last organize run recorded in `target_dir`: created files are
+/// removed and moved files are put back, newest first, then the directories
+/// the run created are removed if they are empty. Anything changed since the
+/// log was written is left in place and reported as a warning.
+pub fn revert(target_dir: &Path) -> Result<UndoReport> {
+    let log = UndoLog::read(target_dir)?;
+    let mut report = UndoReport::default();
+
+    for entry in log.files.iter().rev() {
+        if entry.replaced_existing {
+            report.warnings.push(format!(
+                "{} replaced a file that existed before organizing; left in place",
+                entry.path.display()
+            ));
+            continue;
+        }
+
+        let Ok(metadata) = fs::symlink_metadata(&entry.path) else {
+            report
+                .warnings
+                .push(format!("{} no longer exists", entry.path.display()));
+            continue;
+        };
+
+        if !entry.matches(&metadata) {
+            report.warnings.push(format!(
+                "{} was modified since it was organized; left in place",
+                entry.path.display()
+            ));
+            continue;
+        }
+
+        let result = match &entry.moved_from {
+            Some(original) if fs::symlink_metadata(original).is_ok() => {
+                report.warnings.push(format!(
+                    "Cannot move {} back: {} already exists",
+                    entry.path.display(),
+                    original.display()
+                ));
+                continue;
+            }
+            Some(original) => original
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .map_err(anyhow::Error::from)
+                .and_then(|()| file_ops::move_file(&entry.path, original))
+                .map(|()| report.restored_files += 1),
+            None => fs::remove_file(&entry.path)
+                .map_err(anyhow::Error::from)
+                .map(|()| report.removed_files += 1),
+        };
+
+        if let Err(e) = result {
+            report
+                .warnings
+                .push(format!("Failed to revert {}: {}", entry.path.display(), e));
+        }
+    }
+
+    let log_path = target_dir.join(UNDO_LOG_FILE_NAME);
+    fs::remove_file(&log_path)
+        .with_context(|| format!("Failed to remove undo log: {}", log_path.display()))?;
+
+    for directory in log.directories.iter().rev() {
+        if fs::remove_dir(directory).is_ok() {
+            report.removed_directories += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_revert_removes_only_created_items() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let target_dir = temp_dir.path().join("target");
+        let date_dir = target_dir.join("2023");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::create_dir_all(&date_dir).unwrap();
+
+        let existing = target_dir.join("existing.jpg");
+        let copied = date_dir.join("copied.jpg");
+        let modified = date_dir.join("modified.jpg");
+        let moved = date_dir.join("moved.jpg");
+        let moved_from = source_dir.join("moved.jpg");
+        for path in [&existing, &copied, &modified, &moved] {
+            fs::write(path, "image").unwrap();
+        }
+
+        let mut log = UndoLog::new("copy");
+        log.directories.push(date_dir.clone());
+        log.record_file(&copied, None, false).unwrap();
+        log.record_file(&modified, None, false).unwrap();
+        log.record_file(&moved, Some(&moved_from), false).unwrap();
+        log.write(&target_dir).unwrap();
+
+        fs::write(&modified, "edited image").unwrap();
+
+        let report = revert(&target_dir).unwrap();
+
+        assert_eq!(report.removed_files, 1);
+        assert_eq!(report.restored_files, 1);
+        assert_eq!(report.removed_directories, 0);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(existing.exists());
+        assert!(!copied.exists());
+        assert!(modified.exists());
+        assert!(!moved.exists());
+        assert_eq!(fs::read_to_string(&moved_from).unwrap(), "image");
+        assert!(!target_dir.join(UNDO_LOG_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn test_revert_without_log_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(revert(temp_dir.path()).is_err());
+    }
+}
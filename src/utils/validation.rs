@@ -1,5 +1,19 @@
-use anyhow::Result;
-use std::path::Path;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commands::{DuplicateScanMode, ExportArgs, GroupBy, LinkMode, ThresholdLevel};
+use crate::export::trait_impl::{split_groups_directory, validate_export_options};
+use crate::export::{is_stdout_path, ExportFormat};
+use crate::output::is_json_output;
+use crate::utils::size_utils::format_bytes;
+use crate::utils::{file_ops, path_template, prompt};
+
+/// Allocation unit assumed when estimating how much space a copy takes.
+const BLOCK_SIZE: u64 = 4096;
 
 pub fn validate_directory(path: &Path, description: &str) -> Result<()> {
     if !path.exists() {
@@ -38,6 +52,25 @@ pub fn validate_different_directories(source: &Path, target: &Path) -> Result<()
     Ok(())
 }
 
+pub fn validate_not_nested(first: &Path, second: &Path) -> Result<()> {
+    let first_canonical = first.canonicalize().unwrap_or_else(|_| first.to_path_buf());
+    let second_canonical = second
+        .canonicalize()
+        .unwrap_or_else(|_| second.to_path_buf());
+
+    if first_canonical.starts_with(&second_canonical)
+        || second_canonical.starts_with(&first_canonical)
+    {
+        return Err(anyhow::anyhow!(
+            "Directories cannot be nested inside each other: {} and {}",
+            first.display(),
+            second.display()
+        ));
+    }
+
+    Ok(())
+}
+
 pub fn validate_organize_args(args: &crate::commands::OrganizeArgs) -> Result<()> {
     validate_directory(&args.directory, "Source directory")?;
 
@@ -47,15 +80,238 @@ pub fn validate_organize_args(args: &crate::commands::OrganizeArgs) -> Result<()
         }
     }
 
+    if let Some(plan_path) = &args.from_plan {
+        if !plan_path.is_file() {
+            return Err(anyhow::anyhow!(
+                "Plan file does not exist: {}",
+                plan_path.display()
+            ));
+        }
+    }
+
     if args.copy && args.target_path.is_none() {
         return Err(anyhow::anyhow!(
             "--copy flag requires --target-path to be specified"
         ));
     }
 
+    if args.move_files && args.target_path.is_none() {
+        return Err(anyhow::anyhow!(
+            "--move flag requires --target-path to be specified"
+        ));
+    }
+
+    if args.copy && args.move_files {
+        return Err(anyhow::anyhow!("--copy and --move cannot be used together"));
+    }
+
+    if args.link_mode != LinkMode::Copy {
+        if args.move_files {
+            return Err(anyhow::anyhow!(
+                "--link-mode cannot be combined with --move"
+            ));
+        }
+
+        if args.target_path.is_none() {
+            return Err(anyhow::anyhow!(
+                "--link-mode requires --target-path to be specified"
+            ));
+        }
+    }
+
+    if args.verify {
+        if args.link_mode != LinkMode::Copy {
+            return Err(anyhow::anyhow!(
+                "--verify cannot be combined with --link-mode {}",
+                if args.link_mode == LinkMode::Symlink {
+                    "symlink"
+                } else {
+                    "hardlink"
+                }
+            ));
+        }
+
+        if !args.copy && !args.move_files {
+            return Err(anyhow::anyhow!("--verify requires --copy or --move"));
+        }
+    }
+
+    if args.copy_threads == Some(0) {
+        return Err(anyhow::anyhow!("--copy-threads must be at least 1"));
+    }
+
+    validate_unknown_dir(&args.unknown_dir)?;
+
+    if let Some(template) = &args.template {
+        if args.group_by == GroupBy::Extension {
+            return Err(anyhow::anyhow!(
+                "--template cannot be combined with --group-by extension"
+            ));
+        }
+        path_template::validate_template(template)?;
+    }
+
+    validate_size_range(args.min_size, args.max_size)?;
+    validate_date_range(args.after, args.before)?;
+
+    if args.follow_symlinks && !args.recursive {
+        return Err(anyhow::anyhow!("--follow-symlinks requires --recursive"));
+    }
+
+    validate_max_depth(args.max_depth, args.recursive)?;
+
+    validate_export_args(&args.export)?;
+
+    Ok(())
+}
+
+pub fn validate_unknown_dir(unknown_dir: &str) -> Result<()> {
+    let path = Path::new(unknown_dir);
+    if unknown_dir.trim().is_empty()
+        || path.is_absolute()
+        || path
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+    {
+        return Err(anyhow::anyhow!(
+            "--unknown-dir must be a relative folder name without '..', got: {:?}",
+            unknown_dir
+        ));
+    }
+    Ok(())
+}
+
+/// Checks before the scan that the export can be written: its directory must
+/// exist (or be created with `create`) and accept new files.
+pub fn validate_export_directory(export: &Path, create: bool) -> Result<()> {
+    if is_stdout_path(export) {
+        return Ok(());
+    }
+
+    let directory = match export.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    if !directory.exists() {
+        if !create {
+            return Err(anyhow::anyhow!(
+                "Export directory does not exist: {} (pass --export-mkdir to create it)",
+                directory.display()
+            ));
+        }
+        fs::create_dir_all(directory).with_context(|| {
+            format!("Failed to create export directory: {}", directory.display())
+        })?;
+    }
+
+    if !directory.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Export directory is not a directory: {}",
+            directory.display()
+        ));
+    }
+
+    let probe = directory.join(format!(".image-manager-write-test-{}", std::process::id()));
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .with_context(|| format!("Export directory is not writable: {}", directory.display()))?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(())
+}
+
+/// Estimates the space copying the organized files takes below the target:
+/// every file rounded up to whole blocks, plus a block for each date folder.
+pub fn estimate_copy_size(files: &HashMap<String, Vec<PathBuf>>) -> u64 {
+    let file_bytes: u64 = files
+        .values()
+        .flatten()
+        .map(|file| file_ops::file_size(file).div_ceil(BLOCK_SIZE) * BLOCK_SIZE)
+        .sum();
+    file_bytes + files.len() as u64 * BLOCK_SIZE
+}
+
+/// Fails when the organized files clearly won't fit on the volume that holds
+/// `target`. The date folders are created below `target`, so the free space of
+/// its nearest existing ancestor is what counts.
+pub fn validate_target_space(files: &HashMap<String, Vec<PathBuf>>, target: &Path) -> Result<()> {
+    let available = match file_ops::available_space(target) {
+        Ok(available) => available,
+        Err(e) => {
+            log::warn!(
+                "Could not determine free space on {}: {}",
+                target.display(),
+                e
+            );
+            return Ok(());
+        }
+    };
+
+    let required = estimate_copy_size(files);
+    if required > available {
+        return Err(anyhow::anyhow!(
+            "Not enough free space on {}: the copy needs about {}, only {} available (use --force to copy anyway)",
+            target.display(),
+            format_bytes(required),
+            format_bytes(available)
+        ));
+    }
+
+    Ok(())
+}
+
+/// `--output json` owns stdout, so an export can't be written there too.
+/// Checks the `--export` flags shared by every exporting command before the
+/// scan starts.
+pub fn validate_export_args(export: &ExportArgs) -> Result<()> {
+    if let Some(export_path) = &export.path {
+        validate_export_options(export_path, export.format, &export.options())?;
+        validate_export_directory(export_path, export.export_mkdir)?;
+    }
+
+    validate_manifest_target(export.manifest, export.path.as_deref())?;
+    validate_json_output_target(export.path.as_deref())
+}
+
+fn validate_json_output_target(export: Option<&Path>) -> Result<()> {
+    if is_json_output() && export.is_some_and(is_stdout_path) {
+        return Err(anyhow::anyhow!(
+            "--export - cannot be combined with --output json; both write to stdout"
+        ));
+    }
     Ok(())
 }
 
+fn validate_manifest_target(manifest: bool, export: Option<&Path>) -> Result<()> {
+    if manifest && export.is_none_or(is_stdout_path) {
+        return Err(anyhow::anyhow!(
+            "--manifest requires --export with a file path"
+        ));
+    }
+    Ok(())
+}
+
+/// `--sensitivity` takes precedence over `--threshold`; describes that when
+/// both are set so the override isn't silent.
+pub fn threshold_conflict(
+    threshold: Option<f32>,
+    sensitivity: Option<ThresholdLevel>,
+) -> Option<String> {
+    let (threshold, sensitivity) = (threshold?, sensitivity?);
+    let level = sensitivity
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default();
+
+    Some(format!(
+        "--sensitivity {} overrides the threshold {:.2}; pass only one of them",
+        level, threshold
+    ))
+}
+
 pub fn validate_similarity_threshold(threshold: f32) -> Result<()> {
     if !(0.0..=1.0).contains(&threshold) {
         return Err(anyhow::anyhow!(
@@ -66,20 +322,264 @@ pub fn validate_similarity_threshold(threshold: f32) -> Result<()> {
     Ok(())
 }
 
+pub fn validate_size_range(min_size: Option<u64>, max_size: Option<u64>) -> Result<()> {
+    if let (Some(min_size), Some(max_size)) = (min_size, max_size) {
+        if min_size > max_size {
+            return Err(anyhow::anyhow!(
+                "--min-size ({}) must not be larger than --max-size ({})",
+                format_bytes(min_size),
+                format_bytes(max_size)
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_date_range(after: Option<NaiveDate>, before: Option<NaiveDate>) -> Result<()> {
+    if let (Some(after), Some(before)) = (after, before) {
+        if after > before {
+            return Err(anyhow::anyhow!(
+                "--after ({}) must not be later than --before ({})",
+                after,
+                before
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_max_depth(max_depth: Option<usize>, recursive: bool) -> Result<()> {
+    if max_depth.is_some() && !recursive {
+        return Err(anyhow::anyhow!(
+            "--max-depth only applies to recursive scans; add --recursive"
+        ));
+    }
+    Ok(())
+}
+
+pub fn parse_csv_delimiter(value: &str) -> Result<u8> {
+    if value == "tab" || value == "\\t" {
+        return Ok(b'\t');
+    }
+
+    let mut chars = value.chars();
+    let delimiter = match (chars.next(), chars.next()) {
+        (Some(c), None) => c,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "CSV delimiter must be a single character (use \"tab\" for tab-separated output), got: {:?}",
+                value
+            ))
+        }
+    };
+
+    if !delimiter.is_ascii() {
+        return Err(anyhow::anyhow!(
+            "CSV delimiter must be an ASCII character, got: {:?}",
+            delimiter
+        ));
+    }
+
+    if delimiter.is_ascii_alphanumeric() || matches!(delimiter, '"' | '\n' | '\r') {
+        return Err(anyhow::anyhow!(
+            "CSV delimiter cannot be a letter, digit, quote or line break, got: {:?}",
+            delimiter
+        ));
+    }
+
+    Ok(delimiter as u8)
+}
+
 pub fn validate_duplicates_args(args: &crate::commands::DuplicatesArgs) -> Result<()> {
     validate_directory(&args.directory, "Source directory")?;
 
+    if let Some(compare_directory) = &args.compare_directory {
+        validate_directory(compare_directory, "Compare directory")?;
+        validate_different_directories(&args.directory, compare_directory)?;
+        validate_not_nested(&args.directory, compare_directory)?;
+    }
+
     if let Some(threshold) = args.threshold {
         validate_similarity_threshold(threshold)?;
     }
 
+    if let Some(conflict) = threshold_conflict(args.threshold, args.sensitivity) {
+        if args.strict {
+            return Err(anyhow::anyhow!(conflict));
+        }
+        log::warn!("{}", conflict);
+    }
+
+    validate_max_depth(args.max_depth, args.recursive)?;
+
+    if args.follow_symlinks {
+        if !args.recursive {
+            return Err(anyhow::anyhow!("--follow-symlinks requires --recursive"));
+        }
+        if args.mode != DuplicateScanMode::Exact {
+            return Err(anyhow::anyhow!("Following symlinks requires --mode exact"));
+        }
+    }
+
+    if args.min_group_size < 2 {
+        return Err(anyhow::anyhow!("--min-group-size must be at least 2"));
+    }
+
+    validate_size_range(args.min_size, args.max_size)?;
+    validate_date_range(args.after, args.before)?;
+
+    if args.yes && !args.delete && !args.interactive && !args.hardlink {
+        return Err(anyhow::anyhow!(
+            "--yes requires --delete, --interactive or --hardlink"
+        ));
+    }
+
+    if args.interactive && !args.yes && !prompt::is_interactive() {
+        return Err(anyhow::anyhow!(
+            "--interactive needs an interactive terminal; pass --yes to keep the --keep choice for every group, or use --delete --yes"
+        ));
+    }
+
+    if let Some(export_path) = &args.export.path {
+        if args.split_groups {
+            validate_split_groups_target(export_path, args.export.format)?;
+        }
+    }
+    validate_export_args(&args.export)?;
+
+    Ok(())
+}
+
+fn validate_split_groups_target(export_path: &Path, format: ExportFormat) -> Result<()> {
+    if format != ExportFormat::Csv {
+        return Err(anyhow::anyhow!(
+            "--split-groups only writes CSV files, not {}; pass --export-format csv",
+            format.name()
+        ));
+    }
+
+    if is_stdout_path(export_path) {
+        return Err(anyhow::anyhow!(
+            "--split-groups writes a directory of files and cannot export to stdout"
+        ));
+    }
+
+    let directory = split_groups_directory(export_path);
+    if directory.exists() && !directory.is_dir() {
+        return Err(anyhow::anyhow!(
+            "--split-groups needs {} to be a directory, but it is a file",
+            directory.display()
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn validate_stats_args(args: &crate::commands::StatsArgs) -> Result<()> {
+    validate_directory(&args.directory, "Source directory")?;
+
+    validate_export_args(&args.export)?;
+
+    Ok(())
+}
+
+pub fn validate_rename_args(args: &crate::commands::RenameArgs) -> Result<()> {
+    validate_directory(&args.directory, "Source directory")?;
+    path_template::validate_rename_template(&args.template)?;
+
+    validate_export_args(&args.export)?;
+
+    Ok(())
+}
+
+pub fn validate_exif_args(args: &crate::commands::ExifArgs) -> Result<()> {
+    validate_directory(&args.directory, "Source directory")?;
+
+    validate_export_args(&args.export)?;
+
+    Ok(())
+}
+
+pub fn validate_convert_args(args: &crate::commands::ConvertArgs) -> Result<()> {
+    validate_directory(&args.directory, "Source directory")?;
+
+    if args.target_path.exists() {
+        validate_directory(&args.target_path, "Target directory")?;
+        validate_different_directories(&args.directory, &args.target_path)?;
+    }
+
+    Ok(())
+}
+
+pub fn validate_dedupe_args(args: &crate::commands::DedupeArgs) -> Result<()> {
+    validate_directory(&args.directory, "Source directory")?;
+
+    if let Some(template) = &args.template {
+        path_template::validate_template(template)?;
+    }
+
+    if args.target_path.exists() {
+        validate_directory(&args.target_path, "Target directory")?;
+    }
+    validate_different_directories(&args.directory, &args.target_path)?;
+    validate_not_nested(&args.directory, &args.target_path)?;
+
+    validate_export_args(&args.export)?;
+
+    Ok(())
+}
+
+pub fn validate_watch_args(args: &crate::commands::WatchArgs) -> Result<()> {
+    validate_directory(&args.directory, "Watched directory")?;
+
+    if let Some(template) = &args.template {
+        path_template::validate_template(template)?;
+    }
+
+    if args.target_path.exists() {
+        validate_directory(&args.target_path, "Target directory")?;
+    }
+    validate_different_directories(&args.directory, &args.target_path)?;
+    validate_not_nested(&args.directory, &args.target_path)?;
+
+    Ok(())
+}
+
+pub fn validate_verify_args(args: &crate::commands::VerifyArgs) -> Result<()> {
+    if !args.plan.is_file() {
+        return Err(anyhow::anyhow!(
+            "Plan file does not exist: {}",
+            args.plan.display()
+        ));
+    }
+
+    if let Some(target_path) = &args.target_path {
+        validate_directory(target_path, "Target directory")?;
+    }
+
+    Ok(())
+}
+
+pub fn validate_resize_args(args: &crate::commands::ResizeArgs) -> Result<()> {
+    validate_directory(&args.directory, "Source directory")?;
+
+    if args.max_width.is_none() && args.max_height.is_none() {
+        return Err(anyhow::anyhow!(
+            "Specify at least one of --max-width or --max-height"
+        ));
+    }
+
+    if args.target_path.exists() {
+        validate_directory(&args.target_path, "Target directory")?;
+        validate_different_directories(&args.directory, &args.target_path)?;
+    }
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs;
     use tempfile::TempDir;
 
     #[test]
@@ -112,6 +612,36 @@ mod tests {
         assert!(validate_similarity_threshold(1.1).is_err());
     }
 
+    #[test]
+    fn test_validate_unknown_dir() {
+        assert!(validate_unknown_dir("Unknown").is_ok());
+        assert!(validate_unknown_dir("misc/undated").is_ok());
+        assert!(validate_unknown_dir("").is_err());
+        assert!(validate_unknown_dir("../Unknown").is_err());
+        assert!(validate_unknown_dir("/tmp/Unknown").is_err());
+    }
+
+    #[test]
+    fn test_validate_size_range() {
+        assert!(validate_size_range(None, None).is_ok());
+        assert!(validate_size_range(Some(1024), None).is_ok());
+        assert!(validate_size_range(Some(1024), Some(1024)).is_ok());
+        assert!(validate_size_range(Some(2048), Some(1024)).is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_delimiter() {
+        assert_eq!(parse_csv_delimiter(",").unwrap(), b',');
+        assert_eq!(parse_csv_delimiter(";").unwrap(), b';');
+        assert_eq!(parse_csv_delimiter("tab").unwrap(), b'\t');
+        assert_eq!(parse_csv_delimiter("\\t").unwrap(), b'\t');
+        assert!(parse_csv_delimiter(",;").is_err());
+        assert!(parse_csv_delimiter("").is_err());
+        assert!(parse_csv_delimiter("ä").is_err());
+        assert!(parse_csv_delimiter("a").is_err());
+        assert!(parse_csv_delimiter("\"").is_err());
+    }
+
     #[test]
     fn test_validate_different_directories() {
         let temp_dir = TempDir::new().unwrap();
@@ -123,4 +653,103 @@ mod tests {
         assert!(validate_different_directories(&dir1, &dir2).is_ok());
         assert!(validate_different_directories(&dir1, &dir1).is_err());
     }
+
+    #[test]
+    fn test_validate_not_nested() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir1 = temp_dir.path().join("dir1");
+        let dir2 = temp_dir.path().join("dir2");
+        let inner = dir1.join("inner");
+        fs::create_dir_all(&inner).unwrap();
+        fs::create_dir(&dir2).unwrap();
+
+        assert!(validate_not_nested(&dir1, &dir2).is_ok());
+        assert!(validate_not_nested(&dir1, &inner).is_err());
+        assert!(validate_not_nested(&inner, &dir1).is_err());
+    }
+
+    #[test]
+    fn test_validate_export_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("reports").join("plan.csv");
+
+        assert!(validate_export_directory(Path::new("-"), false).is_ok());
+        assert!(validate_export_directory(&temp_dir.path().join("plan.csv"), false).is_ok());
+        assert!(validate_export_directory(&missing, false).is_err());
+        assert!(validate_export_directory(&missing, true).is_ok());
+        assert!(temp_dir.path().join("reports").is_dir());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_validate_export_directory_read_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let read_only = temp_dir.path().join("read-only");
+        fs::create_dir(&read_only).unwrap();
+        fs::set_permissions(&read_only, fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Permission bits don't apply to root, so the probe would succeed.
+        if fs::File::create(read_only.join("probe")).is_ok() {
+            fs::set_permissions(&read_only, fs::Permissions::from_mode(0o755)).unwrap();
+            eprintln!("skipping test_validate_export_directory_read_only: running with permission overrides");
+            return;
+        }
+
+        let result = validate_export_directory(&read_only.join("plan.csv"), false);
+        fs::set_permissions(&read_only, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(result.unwrap_err().to_string().contains("not writable"));
+    }
+
+    #[test]
+    fn test_validate_export_directory_parent_is_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("plan.csv");
+        fs::write(&file, "").unwrap();
+
+        for create in [false, true] {
+            let error = validate_export_directory(&file.join("plan.csv"), create).unwrap_err();
+            assert!(error.to_string().contains("is not a directory"));
+        }
+    }
+
+    #[test]
+    fn test_estimate_copy_size_rounds_to_blocks() {
+        let temp_dir = TempDir::new().unwrap();
+        let small = temp_dir.path().join("small.jpg");
+        let large = temp_dir.path().join("large.jpg");
+        fs::write(&small, vec![0u8; 10]).unwrap();
+        fs::write(&large, vec![0u8; 5000]).unwrap();
+
+        let files = HashMap::from([("2023-01-15".to_string(), vec![small, large])]);
+
+        assert_eq!(estimate_copy_size(&files), 4 * BLOCK_SIZE);
+        assert!(validate_target_space(&files, &temp_dir.path().join("target")).is_ok());
+    }
+
+    #[test]
+    fn test_threshold_conflict_only_when_both_are_set() {
+        assert!(threshold_conflict(None, None).is_none());
+        assert!(threshold_conflict(Some(0.9), None).is_none());
+        assert!(threshold_conflict(None, Some(ThresholdLevel::High)).is_none());
+
+        let conflict = threshold_conflict(Some(0.9), Some(ThresholdLevel::High)).unwrap();
+        assert!(conflict.contains("--sensitivity high"));
+    }
+
+    #[test]
+    fn test_threshold_conflict_fails_with_strict() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut args = crate::commands::DuplicatesArgs {
+            directory: temp_dir.path().to_path_buf(),
+            threshold: Some(0.9),
+            sensitivity: Some(ThresholdLevel::Low),
+            ..Default::default()
+        };
+        assert!(validate_duplicates_args(&args).is_ok());
+
+        args.strict = true;
+        assert!(validate_duplicates_args(&args).is_err());
+    }
 }